@@ -2,7 +2,16 @@ use proc_macro::TokenStream;
 use syn::{spanned::Spanned, Error as SynError, Result as SynResult};
 
 #[derive(Debug)]
-pub enum AttributeData {
+pub struct AttributeData {
+    pub output: OutputData,
+    pub error_type: Option<String>,
+    pub impl_only: bool,
+    pub fallible: bool,
+    pub use_default: bool,
+}
+
+#[derive(Debug)]
+pub enum OutputData {
     Default,
     Full {
         output_type: String,
@@ -12,10 +21,62 @@ pub enum AttributeData {
 
 pub fn parse_attributes(attr: TokenStream) -> SynResult<AttributeData> {
     if attr.is_empty() {
-        return Ok(AttributeData::Default);
+        return Ok(AttributeData {
+            output: OutputData::Default,
+            error_type: None,
+            impl_only: false,
+            fallible: false,
+            use_default: false,
+        });
     }
 
     let tokens = attr.to_string();
+    let mut tokens = tokens.as_str();
+    let mut error_type = None;
+    let mut impl_only = false;
+    let mut fallible = false;
+    let mut use_default = false;
+
+    loop {
+        let (rest, last) = tokens.rsplit_once(',').unwrap_or(("", tokens));
+        if is_error_argument(last) && error_type.is_none() {
+            error_type = Some(parse_error_argument(last)?);
+        } else if last.trim() == "impl_only" && !impl_only {
+            impl_only = true;
+        } else if last.trim() == "fallible" && !fallible {
+            fallible = true;
+        } else if last.trim() == "default" && !use_default {
+            use_default = true;
+        } else {
+            break;
+        }
+        tokens = rest;
+    }
+
+    if tokens.trim().is_empty() {
+        if fallible {
+            return Err(SynError::new(
+                tokens.span(),
+                "`fallible` requires an output type and a post-processor function",
+            ));
+        }
+
+        return Ok(AttributeData {
+            output: OutputData::Default,
+            error_type,
+            impl_only,
+            fallible,
+            use_default,
+        });
+    }
+
+    if impl_only {
+        return Err(SynError::new(
+            tokens.span(),
+            "`impl_only` can't be combined with an output type and a post-processor function",
+        ));
+    }
+
     let Some((output_type, post_processor)) = tokens.rsplit_once(',') else {
         return Err(SynError::new(
             tokens.span(),
@@ -23,8 +84,35 @@ pub fn parse_attributes(attr: TokenStream) -> SynResult<AttributeData> {
         ));
     };
 
-    Ok(AttributeData::Full {
-        output_type: output_type.trim().to_string(),
-        post_processor: post_processor.trim().to_string(),
+    Ok(AttributeData {
+        output: OutputData::Full {
+            output_type: output_type.trim().to_string(),
+            post_processor: post_processor.trim().to_string(),
+        },
+        error_type,
+        impl_only,
+        fallible,
+        use_default,
     })
 }
+
+fn is_error_argument(argument: &str) -> bool {
+    argument
+        .split_once('=')
+        .is_some_and(|(name, _)| name.trim() == "error")
+}
+
+fn parse_error_argument(argument: &str) -> SynResult<String> {
+    let (_, value) = argument
+        .split_once('=')
+        .unwrap_or_else(|| unreachable!("`argument` should be checked by `is_error_argument`"));
+    let value = value.trim();
+
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(error_type) if !error_type.trim().is_empty() => Ok(error_type.trim().to_string()),
+        _ => Err(SynError::new(
+            value.span(),
+            "expects `error = \"...\"` to receive an error type in a string literal",
+        )),
+    }
+}
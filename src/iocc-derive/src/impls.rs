@@ -6,34 +6,39 @@ use syn::spanned::Spanned;
 use syn::token::Comma;
 use syn::visit_mut::{self, VisitMut};
 use syn::{
-    AngleBracketedGenericArguments, Attribute, Error as SynError, FnArg, GenericArgument, Ident,
-    ImplItem, ImplItemFn, ItemImpl, Meta, Path, PathArguments, Result as SynResult, ReturnType,
-    Signature, Type, TypePath,
+    AngleBracketedGenericArguments, Attribute, Error as SynError, FnArg, GenericArgument, Generics,
+    Ident, ImplItem, ImplItemFn, ItemImpl, Meta, Path, PathArguments, Result as SynResult,
+    ReturnType, Signature, Type, TypePath, Visibility,
 };
 
-use crate::attrs::AttributeData;
+use crate::attrs::{AttributeData, OutputData};
 
 #[derive(Debug)]
 struct ConstructorData {
     self_type: TypePath,
+    generics: Generics,
+    visibility: Visibility,
     identifier: Ident,
     arguments: Vec<ArgumentData>,
+    ordering_dependencies: Vec<Type>,
     return_type: ReturnTypeData,
 }
 
 #[derive(Debug)]
 struct ArgumentData {
     span: Span,
+    ty: Type,
     qualifier: QualifierData,
 }
 
 #[derive(Debug)]
-enum QualifierData {
+pub(crate) enum QualifierData {
     None,
     Named(TokenStream2),
     Qualified(TokenStream2),
     CollectAny,
     CollectKeyType,
+    Extra,
 }
 
 #[derive(Debug)]
@@ -47,12 +52,20 @@ struct AttributeRemovalVisitor;
 impl AttributeRemovalVisitor {
     fn is_custom_attribute(attr: &Attribute) -> bool {
         if let Meta::Path(path) = &attr.meta {
-            if path.segments.first().is_some_and(|s| s.ident == "inject") {
+            if path
+                .segments
+                .first()
+                .is_some_and(|s| s.ident == "inject" || s.ident == "extra")
+            {
                 return true;
             }
         } else if let Meta::List(list) = &attr.meta {
             if let Some(s) = list.path.segments.first() {
-                if s.ident == "named" || s.ident == "qualified" || s.ident == "collect" {
+                if s.ident == "named"
+                    || s.ident == "qualified"
+                    || s.ident == "collect"
+                    || s.ident == "depends_on"
+                {
                     return true;
                 }
             }
@@ -85,8 +98,22 @@ pub fn expand_implementation(
     };
 
     let self_type = get_self_type(&impls)?;
-    let signature = get_constructor_signature(&impls.items, impls.span())?;
-    let ctor_data = parse_constructor(self_type, signature)?;
+    let generics = impls.generics.clone();
+    let ctor_data = if attr_data.use_default {
+        check_no_constructor(&impls.items, impls.span())?;
+        ConstructorData {
+            self_type,
+            generics,
+            visibility: Visibility::Inherited,
+            identifier: Ident::new("default", Span::call_site()),
+            arguments: Vec::new(),
+            ordering_dependencies: Vec::new(),
+            return_type: ReturnTypeData::Infallible,
+        }
+    } else {
+        let (visibility, signature, attrs) = get_constructor_signature(&impls.items, impls.span())?;
+        parse_constructor(self_type, generics, visibility, signature, attrs)?
+    };
 
     let expanded = expand_component_implementation(ctor_data, attr_data)?;
 
@@ -107,20 +134,23 @@ fn get_self_type(impls: &ItemImpl) -> SynResult<TypePath> {
     }
 }
 
-fn get_constructor_signature(items: &[ImplItem], impl_span: Span) -> SynResult<Signature> {
+fn get_constructor_signature(
+    items: &[ImplItem],
+    impl_span: Span,
+) -> SynResult<(Visibility, Signature, Vec<Attribute>)> {
     let ctors: Vec<_> = items
         .iter()
         .filter_map(filter_and_map_item_fn)
         .filter(is_annotated_with_inject)
         .collect();
 
-    let signature = if ctors.len() > 1 {
+    let (visibility, signature, attrs) = if ctors.len() > 1 {
         return Err(SynError::new(
             impl_span,
             "only one associated function can be annotated with `#[inject]`",
         ));
     } else if let Some(&ctor) = ctors.first() {
-        ctor.sig.clone()
+        (ctor.vis.clone(), ctor.sig.clone(), ctor.attrs.clone())
     } else {
         return Err(SynError::new(
             impl_span,
@@ -135,7 +165,22 @@ fn get_constructor_signature(items: &[ImplItem], impl_span: Span) -> SynResult<S
         ));
     }
 
-    Ok(signature)
+    Ok((visibility, signature, attrs))
+}
+
+fn check_no_constructor(items: &[ImplItem], impl_span: Span) -> SynResult<()> {
+    if items
+        .iter()
+        .filter_map(filter_and_map_item_fn)
+        .any(|item_fn| is_annotated_with_inject(&item_fn))
+    {
+        return Err(SynError::new(
+            impl_span,
+            "`default` can't be combined with an associated function annotated with `#[inject]`",
+        ));
+    }
+
+    Ok(())
 }
 
 fn filter_and_map_item_fn(item: &ImplItem) -> Option<&ImplItemFn> {
@@ -153,21 +198,51 @@ fn is_annotated_with_inject(item_fn: &&ImplItemFn) -> bool {
     })
 }
 
-fn parse_constructor(self_type: TypePath, signature: Signature) -> SynResult<ConstructorData> {
+fn parse_constructor(
+    self_type: TypePath,
+    generics: Generics,
+    visibility: Visibility,
+    signature: Signature,
+    attrs: Vec<Attribute>,
+) -> SynResult<ConstructorData> {
     let identifier = signature.ident;
     let arguments = parse_constructor_arguments(signature.inputs)?;
+    let ordering_dependencies = parse_ordering_dependencies(attrs)?;
     let return_type = parse_constructor_return_type(signature.output, &self_type)?;
 
     Ok(ConstructorData {
         self_type,
+        generics,
+        visibility,
         identifier,
         arguments,
+        ordering_dependencies,
         return_type,
     })
 }
 
+fn parse_ordering_dependencies(attrs: Vec<Attribute>) -> SynResult<Vec<Type>> {
+    let mut dependencies = Vec::new();
+
+    for attr in attrs {
+        if !attr.path().is_ident("depends_on") {
+            continue;
+        }
+        let Meta::List(list) = attr.meta else {
+            return Err(SynError::new(
+                attr.meta.span(),
+                "expects `#[depends_on(...)]` to receive a list of types",
+            ));
+        };
+        let types = list.parse_args_with(Punctuated::<Type, Comma>::parse_terminated)?;
+        dependencies.extend(types);
+    }
+
+    Ok(dependencies)
+}
+
 fn parse_constructor_arguments(inputs: Punctuated<FnArg, Comma>) -> SynResult<Vec<ArgumentData>> {
-    inputs
+    let arguments = inputs
         .into_iter()
         .map(|arg| {
             if let FnArg::Typed(arg) = arg {
@@ -176,13 +251,31 @@ fn parse_constructor_arguments(inputs: Punctuated<FnArg, Comma>) -> SynResult<Ve
                 unreachable!("a constructor should not have a receiver argument");
             }
         })
-        .map(|arg| (arg.span(), arg.attrs))
-        .map(|(span, attrs)| parse_argument_attributes(attrs).map(|attr| (span, attr)))
-        .map(|res| res.map(|(span, qualifier)| ArgumentData { span, qualifier }))
-        .collect()
+        .map(|arg| (arg.span(), *arg.ty, arg.attrs))
+        .map(|(span, ty, attrs)| parse_argument_attributes(attrs).map(|attr| (span, ty, attr)))
+        .map(|res| {
+            res.map(|(span, ty, qualifier)| ArgumentData {
+                span,
+                ty,
+                qualifier,
+            })
+        })
+        .collect::<SynResult<Vec<_>>>()?;
+
+    let mut extras = arguments
+        .iter()
+        .filter(|arg| matches!(arg.qualifier, QualifierData::Extra));
+    if let (Some(_), Some(second)) = (extras.next(), extras.next()) {
+        return Err(SynError::new(
+            second.span,
+            "only one argument can be annotated with `#[extra]`",
+        ));
+    }
+
+    Ok(arguments)
 }
 
-fn parse_argument_attributes(attrs: Vec<Attribute>) -> SynResult<QualifierData> {
+pub(crate) fn parse_argument_attributes(attrs: Vec<Attribute>) -> SynResult<QualifierData> {
     let mut res = None;
 
     for attr in attrs {
@@ -191,9 +284,24 @@ fn parse_argument_attributes(attrs: Vec<Attribute>) -> SynResult<QualifierData>
                 let list_span = list.span();
                 let attr_name = &list.path.segments.first().unwrap().ident;
 
+                // Qualifiers are passed to the key constructors as they are,
+                // so any expression works, including macro calls like
+                // `concat!(...)` and `env!(...)`.
                 let data = if attr_name == "named" {
+                    if list.tokens.is_empty() {
+                        return Err(SynError::new(
+                            list_span,
+                            "expects `#[named(...)]` to receive a `&'static str`",
+                        ));
+                    }
                     QualifierData::Named(list.tokens)
                 } else if attr_name == "qualified" {
+                    if list.tokens.is_empty() {
+                        return Err(SynError::new(
+                            list_span,
+                            "expects `#[qualified(...)]` to receive a `TypedQualifier` value",
+                        ));
+                    }
                     QualifierData::Qualified(list.tokens)
                 } else if attr_name == "collect" {
                     let param = list.tokens.to_string();
@@ -216,14 +324,22 @@ fn parse_argument_attributes(attrs: Vec<Attribute>) -> SynResult<QualifierData>
                 if res.is_some() {
                     return Err(SynError::new(
                         list_span,
-                        "only one attribute of `#[named(...)]`, `#[qualified(...)]` or `#[collect(...)]` is allowed",
+                        "only one attribute of `#[named(...)]`, `#[qualified(...)]`, `#[collect(...)]` or `#[extra]` is allowed",
                     ));
                 }
                 res = Some(data);
             }
             Meta::Path(path) => {
                 let attr_name = &path.segments.first().unwrap().ident;
-                if attr_name == "named" {
+                if attr_name == "extra" {
+                    if res.is_some() {
+                        return Err(SynError::new(
+                            path.span(),
+                            "only one attribute of `#[named(...)]`, `#[qualified(...)]`, `#[collect(...)]` or `#[extra]` is allowed",
+                        ));
+                    }
+                    res = Some(QualifierData::Extra);
+                } else if attr_name == "named" {
                     return Err(SynError::new(
                         path.span(),
                         "expects `#[named(...)]` to receive a `&'static str`",
@@ -346,26 +462,74 @@ fn parse_result_return_type(
     }
 }
 
+/// Expands to an expression fetching a dependency from `injector` as
+/// described by `qualifier`, or taking the `extra` argument.
+pub(crate) fn expand_dependency_fetch(qualifier: &QualifierData) -> TokenStream2 {
+    match qualifier {
+        QualifierData::None => quote! { injector.get(iocc::key::of())? },
+        QualifierData::Named(name) => quote! { injector.get(iocc::key::named(#name))? },
+        QualifierData::Qualified(qualifier) => {
+            quote! { injector.get(iocc::key::qualified(#qualifier))? }
+        }
+        QualifierData::CollectAny => quote! { injector.collect(iocc::key::AnyPattern::new())? },
+        QualifierData::CollectKeyType => {
+            quote! { injector.collect(iocc::key::KeyTypePattern::new())? }
+        }
+        QualifierData::Extra => quote! { extra },
+    }
+}
+
+/// Expands to an expression describing a dependency of type `ty` as
+/// described by `qualifier`, or [`None`] for the `extra` argument.
+fn expand_dependency_descriptor(ty: &Type, qualifier: &QualifierData) -> Option<TokenStream2> {
+    let descriptor = quote! { iocc::provider::DependencyDescriptor };
+    Some(match qualifier {
+        QualifierData::None => quote! { #descriptor::of::<#ty>() },
+        QualifierData::Named(name) => quote! { #descriptor::named::<#ty>(#name) },
+        QualifierData::Qualified(qualifier) => {
+            quote! { #descriptor::qualified::<#ty, _>(#qualifier) }
+        }
+        QualifierData::CollectAny => quote! { #descriptor::collect_any::<#ty, _>() },
+        QualifierData::CollectKeyType => quote! { #descriptor::collect_key_type::<#ty, _, _>() },
+        QualifierData::Extra => return None,
+    })
+}
+
 fn expand_component_implementation(
     ctor_data: ConstructorData,
     attr_data: AttributeData,
 ) -> SynResult<TokenStream2> {
     let self_type = &ctor_data.self_type;
     let constructor = &ctor_data.identifier;
+    let (impl_generics, _, where_clause) = ctor_data.generics.split_for_impl();
+
+    let associated_type_constructed =
+        if let OutputData::Full { output_type, .. } = &attr_data.output {
+            let output_type = syn::parse_str::<TypePath>(output_type).unwrap();
+            quote! { type Constructed = #output_type; }
+        } else {
+            quote! { type Constructed = #self_type; }
+        };
 
-    let associated_type_constructed = if let AttributeData::Full { output_type, .. } = &attr_data {
-        let output_type = syn::parse_str::<TypePath>(output_type).unwrap();
-        quote! { type Constructed = #output_type; }
+    let declared_error_type = attr_data
+        .error_type
+        .as_deref()
+        .map(syn::parse_str::<Type>)
+        .transpose()?;
+
+    let error_type = if let Some(error_type) = &declared_error_type {
+        quote! { #error_type }
+    } else if let ReturnTypeData::Result { error_type } = &ctor_data.return_type {
+        quote! { #error_type }
     } else {
-        quote! { type Constructed = #self_type; }
+        quote! { std::convert::Infallible }
     };
 
-    let associated_type_error =
-        if let ReturnTypeData::Result { error_type } = &ctor_data.return_type {
-            quote! { type Error = #error_type; }
-        } else {
-            quote! { type Error = std::convert::Infallible; }
-        };
+    let get_ordering_dep_statements = ctor_data
+        .ordering_dependencies
+        .iter()
+        .map(|ty| quote! { let _ = injector.get(iocc::key::of::<#ty>())?; })
+        .collect::<TokenStream2>();
 
     let get_dep_statements = ctor_data
         .arguments
@@ -373,21 +537,8 @@ fn expand_component_implementation(
         .enumerate()
         .map(|(i, arg)| {
             let dep = Ident::new(&format!("dep{i}"), arg.span);
-            match &arg.qualifier {
-                QualifierData::None => quote! { let #dep = injector.get(iocc::key::of())?; },
-                QualifierData::Named(name) => {
-                    quote! { let #dep = injector.get(iocc::key::named(#name))?; }
-                }
-                QualifierData::Qualified(qualifier) => {
-                    quote! { let #dep = injector.get(iocc::key::qualified(#qualifier))?; }
-                }
-                QualifierData::CollectAny => {
-                    quote! { let #dep = injector.collect(iocc::key::AnyPattern::new())?; }
-                }
-                QualifierData::CollectKeyType => {
-                    quote! { let #dep = injector.collect(iocc::key::KeyTypePattern::new())?; }
-                }
-            }
+            let fetch = expand_dependency_fetch(&arg.qualifier);
+            quote! { let #dep = #fetch; }
         })
         .collect::<TokenStream2>();
 
@@ -401,38 +552,154 @@ fn expand_component_implementation(
         })
         .collect::<TokenStream2>();
 
-    let wire_deps = if let ReturnTypeData::Infallible = &ctor_data.return_type {
-        quote! { Ok(Ok(#self_type::#constructor(#dep_args))) }
+    let wire_deps = if attr_data.use_default {
+        quote! { Ok(Ok(<#self_type as std::default::Default>::default())) }
+    } else if let ReturnTypeData::Infallible = &ctor_data.return_type {
+        quote! { Ok(Ok(<#self_type>::#constructor(#dep_args))) }
+    } else if let Some(error_type) = &declared_error_type {
+        quote! {
+            Ok(<#self_type>::#constructor(#dep_args).map_err(
+                <#error_type as std::convert::From<_>>::from
+            ))
+        }
+    } else {
+        quote! { Ok(<#self_type>::#constructor(#dep_args)) }
+    };
+
+    let extra_type = ctor_data
+        .arguments
+        .iter()
+        .find(|arg| matches!(arg.qualifier, QualifierData::Extra))
+        .map(|arg| &arg.ty);
+
+    let extra_param = if let Some(extra_type) = extra_type {
+        quote! { , extra: #extra_type }
+    } else {
+        quote! {}
+    };
+
+    if attr_data.impl_only {
+        let visibility = &ctor_data.visibility;
+        return Ok(quote! {
+            impl #impl_generics #self_type #where_clause {
+                #[doc(hidden)]
+                #visibility fn __inject<I>(injector: &I #extra_param) -> std::result::Result<
+                    std::result::Result<Self, #error_type>,
+                    iocc::container::injector::InjectorError
+                >
+                where
+                    I: iocc::container::injector::TypedInjector + ?Sized
+                {
+                    #get_ordering_dep_statements
+                    #get_dep_statements
+                    #wire_deps
+                }
+            }
+        });
+    }
+
+    let post_process = match &attr_data.output {
+        OutputData::Full { post_processor, .. } if attr_data.fallible => {
+            let post_processor = syn::parse_str::<Path>(post_processor).unwrap();
+            quote! {
+                fn post_process(self) -> Self::Constructed {
+                    match Self::try_post_process(self) {
+                        Ok(obj) => obj,
+                        Err(_) => panic!(
+                            "the fallible post-processor of `{}` fails, use `try_post_process` instead",
+                            std::any::type_name::<Self>(),
+                        ),
+                    }
+                }
+
+                fn try_post_process(self) -> std::result::Result<Self::Constructed, Self::Error> {
+                    #post_processor(self).map_err(std::convert::Into::into)
+                }
+            }
+        }
+        OutputData::Full { post_processor, .. } => {
+            let post_processor = syn::parse_str::<Path>(post_processor).unwrap();
+            quote! {
+                fn post_process(self) -> Self::Constructed {
+                    #post_processor(self)
+                }
+            }
+        }
+        OutputData::Default => quote! {
+            fn post_process(self) -> Self::Constructed {
+                self
+            }
+        },
+    };
+
+    let is_leaf = if ctor_data.ordering_dependencies.is_empty()
+        && ctor_data
+            .arguments
+            .iter()
+            .all(|arg| matches!(arg.qualifier, QualifierData::Extra))
+    {
+        quote! {
+            fn is_leaf() -> bool {
+                true
+            }
+        }
     } else {
-        quote! { Ok(#self_type::#constructor(#dep_args)) }
+        quote! {}
     };
 
-    let post_process_body = if let AttributeData::Full { post_processor, .. } = &attr_data {
-        let post_processor = syn::parse_str::<Path>(post_processor).unwrap();
-        quote! { #post_processor(self) }
+    let dependencies = if is_leaf.is_empty() {
+        let descriptors = ctor_data
+            .ordering_dependencies
+            .iter()
+            .map(|ty| quote! { iocc::provider::DependencyDescriptor::of::<#ty>(), })
+            .chain(ctor_data.arguments.iter().filter_map(|arg| {
+                expand_dependency_descriptor(&arg.ty, &arg.qualifier)
+                    .map(|descriptor| quote! { #descriptor, })
+            }))
+            .collect::<TokenStream2>();
+        quote! {
+            fn dependencies() -> std::vec::Vec<iocc::provider::DependencyDescriptor> {
+                std::vec![#descriptors]
+            }
+        }
     } else {
-        quote! { self }
+        quote! {}
+    };
+
+    let (component_trait, construct) = if let Some(extra_type) = extra_type {
+        (
+            quote! { iocc::provider::component::ComponentWith<#extra_type> },
+            quote! { construct_with },
+        )
+    } else {
+        (
+            quote! { iocc::provider::component::Component },
+            quote! { construct },
+        )
     };
 
     Ok(quote! {
-        impl iocc::provider::component::Component for #self_type {
+        impl #impl_generics #component_trait for #self_type #where_clause {
             #associated_type_constructed
-            #associated_type_error
+            type Error = #error_type;
 
-            fn construct<I>(injector: &I) -> std::result::Result<
+            fn #construct<I>(injector: &I #extra_param) -> std::result::Result<
                 std::result::Result<Self, Self::Error>,
                 iocc::container::injector::InjectorError
             >
             where
                 I: iocc::container::injector::TypedInjector + ?Sized
             {
+                #get_ordering_dep_statements
                 #get_dep_statements
                 #wire_deps
             }
 
-            fn post_process(self) -> Self::Constructed {
-                #post_process_body
-            }
+            #post_process
+
+            #is_leaf
+
+            #dependencies
         }
     })
 }
@@ -0,0 +1,64 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse, Data, DeriveInput, Error as SynError, Fields, Index, Result as SynResult};
+
+use crate::impls::{self, QualifierData};
+
+pub fn expand_inject(item: TokenStream) -> SynResult<TokenStream2> {
+    let input: DeriveInput = parse(item)?;
+
+    let Data::Struct(data) = input.data else {
+        return Err(SynError::new(
+            input.ident.span(),
+            "`Inject` can only be derived for structs",
+        ));
+    };
+
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let qualifier = impls::parse_argument_attributes(field.attrs.clone())?;
+            if let QualifierData::Extra = qualifier {
+                let attr = field
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.path().is_ident("extra"));
+                return Err(SynError::new_spanned(
+                    attr,
+                    "`#[extra]` isn't supported by `Inject`",
+                ));
+            }
+            let fetch = impls::expand_dependency_fetch(&qualifier);
+            Ok(if let Some(ident) = &field.ident {
+                quote! { #ident: #fetch, }
+            } else {
+                let index = Index::from(i);
+                quote! { #index: #fetch, }
+            })
+        })
+        .collect::<SynResult<TokenStream2>>()?;
+
+    let construct = match &data.fields {
+        Fields::Unit => quote! { Self },
+        Fields::Named(_) | Fields::Unnamed(_) => quote! { Self { #fields } },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics iocc::container::injector::Inject for #ident #ty_generics #where_clause {
+            fn inject_from<I>(
+                injector: &I,
+            ) -> std::result::Result<Self, iocc::container::injector::InjectorError>
+            where
+                I: iocc::container::injector::TypedInjector + ?Sized
+            {
+                Ok(#construct)
+            }
+        }
+    })
+}
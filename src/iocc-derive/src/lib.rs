@@ -2,6 +2,8 @@
 
 mod attrs;
 mod impls;
+mod inject;
+mod qualifier;
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
@@ -20,3 +22,19 @@ fn component_impl(attr: TokenStream, item: TokenStream) -> SynResult<TokenStream
     let expanded = impls::expand_implementation(item, attr_data)?;
     Ok(expanded)
 }
+
+#[proc_macro_derive(Qualifier, attributes(qualifier))]
+pub fn derive_qualifier(item: TokenStream) -> TokenStream {
+    match qualifier::expand_qualifier(item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Inject, attributes(named, qualified, collect, extra))]
+pub fn derive_inject(item: TokenStream) -> TokenStream {
+    match inject::expand_inject(item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
@@ -0,0 +1,60 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse, Attribute, DeriveInput, Error as SynError, Result as SynResult};
+
+pub fn expand_qualifier(item: TokenStream) -> SynResult<TokenStream2> {
+    let input: DeriveInput = parse(item)?;
+
+    if !input.generics.params.is_empty() {
+        return Err(SynError::new(
+            input.generics.span(),
+            "`Qualifier` can't be derived for generic types",
+        ));
+    }
+
+    let ident = &input.ident;
+    let display = parse_qualifier_attributes(&input.attrs)?;
+
+    let debug_impl = if display {
+        quote! {
+            impl std::fmt::Debug for #ident {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(self, f)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        const _: () = {
+            fn assert_qualifier<Q: iocc::key::TypedQualifier>() {}
+            let _ = assert_qualifier::<#ident>;
+        };
+
+        #debug_impl
+    })
+}
+
+fn parse_qualifier_attributes(attrs: &[Attribute]) -> SynResult<bool> {
+    let mut display = false;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("qualifier"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("display") && !display {
+                display = true;
+                Ok(())
+            } else {
+                Err(meta.error("expects `#[qualifier(display)]`"))
+            }
+        })?;
+    }
+
+    Ok(display)
+}
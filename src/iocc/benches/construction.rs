@@ -0,0 +1,61 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::hint;
+use std::sync::Arc;
+use std::thread;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+const SINGLETONS: u32 = 100;
+const THREADS: u32 = 4;
+
+struct IndependentModule;
+
+impl Module for IndependentModule {
+    type Scope = SingletonScope;
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for id in 0..SINGLETONS {
+            bind::<Arc<u64>>()
+                .to_raw_closure(move |_| {
+                    let value = (0..1000u64).fold(id as u64, |acc, n| hint::black_box(acc ^ n));
+                    Ok(Ok::<_, Infallible>(Arc::new(value)))
+                })
+                .qualified_by(id)
+                .within(SingletonScope)
+                .set_on(configurer);
+        }
+
+        Ok(())
+    }
+}
+
+fn construct_independent_singletons(c: &mut Criterion) {
+    c.bench_function("construct independent singletons across threads", |b| {
+        b.iter_batched(
+            || Container::init(IndependentModule).unwrap(),
+            |container| {
+                thread::scope(|s| {
+                    for offset in 0..THREADS {
+                        let container = &container;
+                        s.spawn(move || {
+                            for id in (offset..SINGLETONS).step_by(THREADS as usize) {
+                                let value = container.get(key::qualified::<Arc<u64>>(id)).unwrap();
+                                hint::black_box(value);
+                            }
+                        });
+                    }
+                });
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, construct_independent_singletons);
+criterion_main!(benches);
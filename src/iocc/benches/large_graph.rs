@@ -0,0 +1,113 @@
+//! A synthetic graph of 200 component bindings with distinct types, which is
+//! mainly used to check how much code the provider dispatch instantiates per
+//! binding. Besides running it, measure the build with
+//!
+//! ```sh
+//! cargo build --release --bench large_graph --timings
+//! ```
+//!
+//! and count the `Component::construct` instances in the debug build with
+//!
+//! ```sh
+//! cargo build --bench large_graph
+//! nm -C target/debug/deps/large_graph-* | grep -c 'Node<.*>.*construct'
+//! ```
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::hint;
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use iocc::container::injector::InjectorError;
+use iocc::prelude::*;
+use iocc::provider::component::Component;
+use iocc::scope::SingletonScope;
+
+struct Config {
+    seed: usize,
+}
+
+struct Node<const N: usize> {
+    value: usize,
+}
+
+impl<const N: usize> Component for Node<N> {
+    type Constructed = Arc<Self>;
+
+    type Error = Infallible;
+
+    fn construct<I>(injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let config = injector.get(key::of::<Arc<Config>>())?;
+        Ok(Ok(Self {
+            value: config.seed + N,
+        }))
+    }
+
+    fn post_process(self) -> Self::Constructed {
+        Arc::new(self)
+    }
+}
+
+/// Invokes `$f!` with each node index from 0 to 199.
+macro_rules! for_each_node {
+    ($f:ident) => {
+        for_each_node!(@tens $f; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);
+    };
+    (@tens $f:ident; $($tens:literal)*) => {
+        $(for_each_node!(@ones $f; $tens; 0 1 2 3 4 5 6 7 8 9);)*
+    };
+    (@ones $f:ident; $tens:literal; $($ones:literal)*) => {
+        $($f!({ 10 * $tens + $ones });)*
+    };
+}
+
+struct LargeGraphModule;
+
+impl Module for LargeGraphModule {
+    type Scope = SingletonScope;
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        bind::<Arc<Config>>()
+            .to_instance(Arc::new(Config { seed: 1 }))
+            .within(SingletonScope)
+            .set_on(configurer);
+
+        macro_rules! bind_node {
+            ($n:expr) => {
+                bind::<Arc<Node<$n>>>()
+                    .within(SingletonScope)
+                    .to_component::<Node<$n>>()
+                    .set_on(configurer);
+            };
+        }
+        for_each_node!(bind_node);
+
+        Ok(())
+    }
+}
+
+fn resolve_large_graph(c: &mut Criterion) {
+    c.bench_function("resolve 200 distinct component bindings", |b| {
+        b.iter(|| {
+            let container = Container::init(LargeGraphModule).unwrap();
+            let mut sum = 0;
+            macro_rules! get_node {
+                ($n:expr) => {
+                    sum += container.get(key::of::<Arc<Node<$n>>>()).unwrap().value;
+                };
+            }
+            for_each_node!(get_node);
+            hint::black_box(sum)
+        })
+    });
+}
+
+criterion_group!(benches, resolve_large_graph);
+criterion_main!(benches);
@@ -0,0 +1,186 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::hint;
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+const DEPTH: u32 = 64;
+
+struct Node {
+    _next: Option<Arc<Node>>,
+}
+
+#[derive(Clone, Copy)]
+struct Leaf;
+
+#[component]
+impl Leaf {
+    #[inject]
+    fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NonLeaf;
+
+#[derive(Clone)]
+struct Large {
+    _bytes: [u8; 4096],
+}
+
+struct ChainModule;
+
+impl Module for ChainModule {
+    type Scope = SingletonScope;
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for id in 0..DEPTH {
+            bind::<Arc<Node>>()
+                .to_raw_closure(move |injector| {
+                    let next = if id == 0 {
+                        None
+                    } else {
+                        Some(injector.get(key::qualified::<Arc<Node>>(id - 1))?)
+                    };
+                    Ok(Ok::<_, Infallible>(Arc::new(Node { _next: next })))
+                })
+                .qualified_by(id)
+                .within(SingletonScope)
+                .set_on(configurer);
+
+            bind::<u32>()
+                .to_raw_closure(move |injector| {
+                    let next = if id == 0 {
+                        0
+                    } else {
+                        injector.get(key::qualified::<u32>(id - 1))?
+                    };
+                    Ok(Ok::<_, Infallible>(next + 1))
+                })
+                .qualified_by(id)
+                .set_on(configurer);
+
+            bind::<u64>()
+                .to_raw_closure(move |injector| {
+                    let _: Leaf = injector.get(key::of())?;
+                    let next = if id == 0 {
+                        0
+                    } else {
+                        injector.get(key::qualified::<u64>(id - 1))?
+                    };
+                    Ok(Ok::<_, Infallible>(next + 1))
+                })
+                .qualified_by(id)
+                .set_on(configurer);
+
+            bind::<i64>()
+                .to_raw_closure(move |injector| {
+                    let _: NonLeaf = injector.get(key::of())?;
+                    let next = if id == 0 {
+                        0
+                    } else {
+                        injector.get(key::qualified::<i64>(id - 1))?
+                    };
+                    Ok(Ok::<_, Infallible>(next + 1))
+                })
+                .qualified_by(id)
+                .set_on(configurer);
+        }
+
+        bind::<Large>()
+            .to_instance(Large { _bytes: [1; 4096] })
+            .set_on(configurer);
+        bind::<Leaf>().set_on(configurer);
+        bind::<NonLeaf>()
+            .to_raw_closure(|_| Ok(Ok::<_, Infallible>(NonLeaf)))
+            .set_on(configurer);
+
+        Ok(())
+    }
+}
+
+fn resolve_deep_graph(c: &mut Criterion) {
+    let container = Container::init(ChainModule).unwrap();
+    let _ = container
+        .get(key::qualified::<Arc<Node>>(DEPTH - 1))
+        .unwrap();
+
+    c.bench_function("resolve constructed singletons", |b| {
+        b.iter(|| {
+            for id in 0..DEPTH {
+                let node = container.get(key::qualified::<Arc<Node>>(id)).unwrap();
+                hint::black_box(node);
+            }
+        })
+    });
+
+    c.bench_function("resolve constructed singletons with get_arc", |b| {
+        b.iter(|| {
+            for id in 0..DEPTH {
+                let node = container.get_arc(key::qualified::<Arc<Node>>(id)).unwrap();
+                hint::black_box(node);
+            }
+        })
+    });
+
+    c.bench_function("resolve deep transient chain", |b| {
+        b.iter(|| {
+            let value = container.get(key::qualified::<u32>(DEPTH - 1)).unwrap();
+            hint::black_box(value);
+        })
+    });
+}
+
+fn resolve_leaf_dependencies(c: &mut Criterion) {
+    let container = Container::init(ChainModule).unwrap();
+
+    c.bench_function("resolve deep chain with leaf dependencies", |b| {
+        b.iter(|| {
+            let value = container.get(key::qualified::<u64>(DEPTH - 1)).unwrap();
+            hint::black_box(value);
+        })
+    });
+
+    c.bench_function("resolve deep chain with non-leaf dependencies", |b| {
+        b.iter(|| {
+            let value = container.get(key::qualified::<i64>(DEPTH - 1)).unwrap();
+            hint::black_box(value);
+        })
+    });
+}
+
+fn resolve_large_values(c: &mut Criterion) {
+    let container = Container::init(ChainModule).unwrap();
+
+    c.bench_function("resolve large values", |b| {
+        b.iter(|| {
+            let value = container.get(key::of::<Large>()).unwrap();
+            hint::black_box(value);
+        })
+    });
+
+    c.bench_function("resolve large values with resolve_into", |b| {
+        let mut slot = None;
+        b.iter(|| {
+            container
+                .resolve_into(key::of::<Large>(), &mut slot)
+                .unwrap();
+            hint::black_box(&slot);
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    resolve_deep_graph,
+    resolve_leaf_dependencies,
+    resolve_large_values
+);
+criterion_main!(benches);
@@ -0,0 +1,487 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::container::core::ContainerCore;
+use crate::container::registry::{ConfigurerImpl, DuplicatePolicy, RegistryError};
+use crate::container::Container;
+use crate::module::Module;
+use crate::scope::Scope;
+use crate::util::hash::KeyBuildHasher;
+
+/// A builder of root [`Container`]s, which collects modules and
+/// container-wide options.
+///
+/// Use [`Container::builder`] to create a [`ContainerBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let container = Container::<SingletonScope>::builder()
+///     .module(module::from_fn(|configurer| {
+///         bind::<Arc<i32>>()
+///             .to_instance(Arc::new(42))
+///             .within(SingletonScope)
+///             .set_on(configurer);
+///         Ok(())
+///     }))
+///     .eager(true)
+///     .build()
+///     .unwrap();
+/// assert!(container.is_constructed(key::of::<Arc<i32>>()));
+/// ```
+pub struct ContainerBuilder<S: Scope> {
+    configurer: ConfigurerImpl<S>,
+    eager: bool,
+    wait_timeout: Option<Duration>,
+    track_usage: bool,
+    collect_stats: bool,
+}
+
+impl<S: Scope> ContainerBuilder<S> {
+    pub(super) fn new() -> Self {
+        Self {
+            configurer: ConfigurerImpl::new(),
+            eager: false,
+            wait_timeout: None,
+            track_usage: false,
+            collect_stats: false,
+        }
+    }
+
+    /// Sets up `module` in the container. Errors reported by modules are
+    /// collected and returned by [`ContainerBuilder::build`].
+    pub fn module<M>(mut self, module: M) -> Self
+    where
+        M: Module<Scope = S>,
+    {
+        module.setup(&mut self.configurer);
+        self
+    }
+
+    /// Sets how bindings are handled if their keys are already bound, which
+    /// reports [`RegistryError::KeyDuplicated`] by default.
+    ///
+    /// The policy only applies to modules set up after it's set, so it
+    /// should usually be set before any module.
+    pub fn duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.configurer.set_duplicate_policy(policy);
+        self
+    }
+
+    /// Enables the binding group `group`, reverting a previous
+    /// [`ContainerBuilder::disable_group`]. All groups are enabled by
+    /// default.
+    ///
+    /// Like [`ContainerBuilder::duplicate_policy`], it only applies to
+    /// modules set up afterwards.
+    pub fn enable_group(mut self, group: impl Into<String>) -> Self {
+        self.configurer.set_group_enabled(group, true);
+        self
+    }
+
+    /// Disables the binding group `group`, so that [`GroupedModule`]s of the
+    /// group set up afterwards don't bind anything.
+    ///
+    /// [`GroupedModule`]: crate::module::GroupedModule
+    pub fn disable_group(mut self, group: impl Into<String>) -> Self {
+        self.configurer.set_group_enabled(group, false);
+        self
+    }
+
+    /// Sets whether all shared objects bound in the singleton scope are
+    /// constructed when the container is built, which is disabled by default.
+    pub fn eager(mut self, eager: bool) -> Self {
+        self.eager = eager;
+        self
+    }
+
+    /// Sets how long a thread waits for another thread constructing the same
+    /// shared object, after which the request fails with
+    /// [`InjectorError::ConstructionTimeout`] instead of hanging. Threads
+    /// wait forever by default.
+    ///
+    /// [`InjectorError::ConstructionTimeout`]: crate::container::injector::InjectorError::ConstructionTimeout
+    pub fn wait_timeout(mut self, timeout: Duration) -> Self {
+        self.wait_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether the container records requested keys, so that bindings
+    /// never requested can be listed by [`Container::unused_keys`], which is
+    /// disabled by default to avoid the overhead.
+    pub fn track_usage(mut self, track_usage: bool) -> Self {
+        self.track_usage = track_usage;
+        self
+    }
+
+    /// Sets whether the container counts and times constructions per key,
+    /// which are reported by [`Container::stats`] and disabled by default to
+    /// avoid the overhead.
+    pub fn collect_stats(mut self, collect_stats: bool) -> Self {
+        self.collect_stats = collect_stats;
+        self
+    }
+
+    /// Sets the [`BuildHasher`] of the container's maps keyed by [`Key`]s,
+    /// e.g. a DoS-resistant one if qualifiers are derived from untrusted
+    /// input. Unlike other options, it also applies to modules set up
+    /// before.
+    ///
+    /// The hasher is dispatched dynamically and builds a boxed [`Hasher`]
+    /// for each lookup of a qualified key, so the default one should be
+    /// kept unless it's needed.
+    ///
+    /// [`Key`]: crate::key::Key
+    /// [`Hasher`]: std::hash::Hasher
+    pub fn hasher<H>(mut self, hasher: H) -> Self
+    where
+        H: BuildHasher + Send + Sync + 'static,
+        H::Hasher: 'static,
+    {
+        self.configurer
+            .set_key_hasher(KeyBuildHasher::custom(hasher));
+        self
+    }
+
+    /// Builds the container.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any module fails to set up, any singleton object
+    /// fails to be constructed when eager construction is enabled, or any
+    /// startup hook fails.
+    pub fn build(mut self) -> Result<Container<S>, RegistryError> {
+        let startup_hooks = self.configurer.take_startup_hooks();
+        let core = ContainerCore::new_root(Arc::new(self.configurer.finish()?))
+            .with_wait_timeout(self.wait_timeout)
+            .with_usage_tracking(self.track_usage)
+            .with_stats(self.collect_stats);
+        let container = Container::from_core(core);
+        if self.eager {
+            container
+                .init_eager()
+                .map_err(|source| RegistryError::EagerConstruction { source })?;
+        }
+        for hook in startup_hooks {
+            hook(&container).map_err(|source| RegistryError::Startup { source })?;
+        }
+        Ok(container)
+    }
+}
+
+impl<S: Scope> Debug for ContainerBuilder<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ContainerBuilder<S>")
+            .field("eager", &self.eager)
+            .field("wait_timeout", &self.wait_timeout)
+            .field("track_usage", &self.track_usage)
+            .field("collect_stats", &self.collect_stats)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::hash::DefaultHasher;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::TypedConfigurer;
+    use crate::key::{self, Key};
+    use crate::module;
+    use crate::module::dsl::bind;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    #[test]
+    fn container_builder_build_succeeds() {
+        let container = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<Arc<i32>>()
+                    .to_instance(Arc::new(42))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                Ok(())
+            }))
+            .module(module::from_fn(|configurer| {
+                bind::<Arc<i64>>()
+                    .to_instance(Arc::new(42))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                Ok(())
+            }))
+            .build()
+            .unwrap();
+
+        assert!(!container.is_constructed(key::of::<Arc<i32>>()));
+        assert!(!container.is_constructed(key::of::<Arc<i64>>()));
+    }
+
+    #[test]
+    fn container_builder_duplicate_policy_succeeds() {
+        let make_module = || {
+            module::from_fn(|configurer| {
+                bind::<i32>().to_instance(1).set_on(configurer);
+                bind::<i32>().to_instance(2).set_on(configurer);
+                bind::<Arc<i64>>()
+                    .to_instance(Arc::new(1))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                bind::<Arc<i64>>()
+                    .to_instance(Arc::new(2))
+                    .set_on(configurer);
+                Ok(())
+            })
+        };
+
+        let container = Container::<SingletonScope>::builder()
+            .duplicate_policy(DuplicatePolicy::LastWins)
+            .module(make_module())
+            .build()
+            .unwrap();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 2);
+        assert_eq!(*container.get(key::of::<Arc<i64>>()).unwrap(), 2);
+
+        let container = Container::<SingletonScope>::builder()
+            .duplicate_policy(DuplicatePolicy::FirstWins)
+            .module(make_module())
+            .build()
+            .unwrap();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 1);
+        assert_eq!(*container.get(key::of::<Arc<i64>>()).unwrap(), 1);
+    }
+
+    #[test]
+    fn container_builder_hasher_succeeds() {
+        #[derive(Clone, Default)]
+        struct CountingBuildHasher(Arc<AtomicUsize>);
+
+        impl BuildHasher for CountingBuildHasher {
+            type Hasher = DefaultHasher;
+
+            fn build_hasher(&self) -> Self::Hasher {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                DefaultHasher::new()
+            }
+        }
+
+        let hasher = CountingBuildHasher::default();
+        let container = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<i32>()
+                    .qualified_by(1)
+                    .to_instance(1)
+                    .set_on(configurer);
+                bind::<i32>()
+                    .qualified_by(2)
+                    .to_instance(2)
+                    .set_on(configurer);
+                bind::<Arc<i64>>()
+                    .to_instance(Arc::new(3))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                Ok(())
+            }))
+            .hasher(hasher.clone())
+            .build()
+            .unwrap();
+
+        let built = hasher.0.load(Ordering::Relaxed);
+        assert!(built > 0);
+        assert_eq!(container.get(key::qualified::<i32>(1)).unwrap(), 1);
+        assert_eq!(container.get(key::qualified::<i32>(2)).unwrap(), 2);
+        assert_eq!(*container.get(key::of::<Arc<i64>>()).unwrap(), 3);
+        assert!(hasher.0.load(Ordering::Relaxed) > built);
+    }
+
+    #[test]
+    fn container_builder_track_usage_succeeds_when_eager() {
+        let container = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<Arc<i32>>()
+                    .to_instance(Arc::new(1))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                bind::<Arc<u8>>()
+                    .to_instance(Arc::new(2))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                Ok(())
+            }))
+            .eager(true)
+            .track_usage(true)
+            .build()
+            .unwrap();
+        assert!(container.is_constructed(key::of::<Arc<i32>>()));
+        assert_eq!(container.unused_keys().len(), 2);
+
+        container.health_check();
+        assert_eq!(container.unused_keys().len(), 2);
+
+        container.get(key::of::<Arc<i32>>()).unwrap();
+        let keys = container.unused_keys();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].as_ref(), &key::of::<Arc<u8>>() as &dyn Key);
+    }
+
+    #[test]
+    fn container_builder_collect_stats_succeeds() {
+        let make_module = || {
+            module::from_fn(|configurer| {
+                bind::<i32>().to_instance(1).set_on(configurer);
+                bind::<Arc<u8>>()
+                    .to_instance(Arc::new(2))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                bind::<i64>()
+                    .to_raw_closure(|_| Ok(Err::<i64, _>("unavailable")))
+                    .set_on(configurer);
+                Ok(())
+            })
+        };
+
+        let container = Container::<SingletonScope>::builder()
+            .module(make_module())
+            .collect_stats(true)
+            .build()
+            .unwrap();
+        container.get(key::of::<i32>()).unwrap();
+        container.get(key::of::<i32>()).unwrap();
+        container.get(key::of::<Arc<u8>>()).unwrap();
+        container.get(key::of::<Arc<u8>>()).unwrap();
+        assert!(container.get(key::of::<i64>()).is_err());
+
+        let stats = container.stats();
+        assert_eq!(stats.len(), 3);
+        let transient = &stats[&(Box::new(key::of::<i32>()) as Box<dyn Key>)];
+        assert_eq!(transient.construct_count, 2);
+        assert!(transient.last_error.is_none());
+        let shared = &stats[&(Box::new(key::of::<Arc<u8>>()) as Box<dyn Key>)];
+        assert_eq!(shared.construct_count, 1);
+        let failed = &stats[&(Box::new(key::of::<i64>()) as Box<dyn Key>)];
+        assert_eq!(failed.construct_count, 1);
+        assert!(failed.last_error.is_some());
+
+        let container = Container::<SingletonScope>::builder()
+            .module(make_module())
+            .build()
+            .unwrap();
+        container.get(key::of::<i32>()).unwrap();
+        assert!(container.stats().is_empty());
+    }
+
+    #[test]
+    fn container_builder_track_usage_succeeds() {
+        let make_module = || {
+            module::from_fn(|configurer| {
+                bind::<i32>().to_instance(1).set_on(configurer);
+                bind::<i64>().to_instance(2).set_on(configurer);
+                bind::<Arc<u8>>()
+                    .to_instance(Arc::new(3))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                Ok(())
+            })
+        };
+
+        let container = Container::<SingletonScope>::builder()
+            .module(make_module())
+            .track_usage(true)
+            .build()
+            .unwrap();
+        assert_eq!(container.unused_keys().len(), 3);
+
+        container.get(key::of::<i32>()).unwrap();
+        container.get_arc(key::of::<Arc<u8>>()).unwrap();
+        let keys = container.unused_keys();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].as_ref(), &key::of::<i64>() as &dyn Key);
+
+        let container = Container::<SingletonScope>::builder()
+            .module(make_module())
+            .build()
+            .unwrap();
+        assert!(container.unused_keys().is_empty());
+    }
+
+    #[test]
+    fn container_builder_build_fails_when_key_is_duplicated() {
+        let res = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<i32>().to_instance(1).set_on(configurer);
+                bind::<i32>().to_instance(2).set_on(configurer);
+                Ok(())
+            }))
+            .build();
+
+        assert!(matches!(res, Err(RegistryError::KeyDuplicated { .. })));
+    }
+
+    #[test]
+    fn container_builder_build_fails_when_eager_construction_fails() {
+        let res = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<Arc<i32>>()
+                    .to_raw_closure(|_| Ok(Err::<_, &'static str>("unavailable")))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                bind::<Arc<i64>>()
+                    .to_raw_closure(|_| Ok(Ok::<_, Infallible>(Arc::new(42))))
+                    .set_on(configurer);
+                Ok(())
+            }))
+            .eager(true)
+            .build();
+
+        assert!(matches!(res, Err(RegistryError::EagerConstruction { .. })));
+    }
+
+    #[test]
+    fn container_builder_build_runs_startup_hooks_succeeds() {
+        let container = Container::<SingletonScope>::builder()
+            .module(module::from_fn(|configurer| {
+                bind::<Arc<i32>>()
+                    .to_instance(Arc::new(42))
+                    .within(SingletonScope)
+                    .set_on(configurer);
+                configurer.on_startup(|container| {
+                    container.get(key::of::<Arc<i32>>())?;
+                    Ok(())
+                });
+                Ok(())
+            }))
+            .build()
+            .unwrap();
+
+        assert!(container.is_constructed(key::of::<Arc<i32>>()));
+    }
+
+    #[test]
+    fn container_builder_build_fails_when_startup_hook_fails() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let ran_after_failure = Arc::clone(&ran);
+        let res = Container::<SingletonScope>::builder()
+            .module(module::from_fn(move |configurer| {
+                configurer.on_startup(|_| Err("unavailable".into()));
+                let ran = Arc::clone(&ran_after_failure);
+                configurer.on_startup(move |_| {
+                    ran.store(true, Ordering::SeqCst);
+                    Ok(())
+                });
+                Ok(())
+            }))
+            .build();
+
+        assert!(matches!(res, Err(RegistryError::Startup { .. })));
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+}
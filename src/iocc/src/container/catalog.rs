@@ -0,0 +1,192 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::container::registry::{ConfigurerImpl, ProviderMap, Registry, RegistryError};
+use crate::container::snapshot::{BindingSnapshot, ContainerSnapshot};
+use crate::container::Container;
+use crate::key::Key;
+use crate::module::Module;
+use crate::scope::Scope;
+
+/// An immutable set of bindings produced by modules, which can be inspected
+/// without resolving any object.
+///
+/// A [`Catalog`] is the output of the configuration phase of a container,
+/// detached from a live resolver. It helps testing module definitions in
+/// isolation, and can be turned into a root [`Container`] later.
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::container::Catalog;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let module = module::from_fn(|configurer| {
+///     bind::<i32>().to_instance(42).set_on(configurer);
+///     Ok(())
+/// });
+///
+/// let catalog = Catalog::<SingletonScope>::from_module(module).unwrap();
+/// assert!(catalog.contains(&key::of::<i32>()));
+/// assert_eq!(catalog.keys().count(), 1);
+/// ```
+pub struct Catalog<S: Scope> {
+    providers: ProviderMap<S>,
+}
+
+impl<S: Scope> Catalog<S> {
+    /// Sets up `module` and collects all its bindings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any module fails to set up.
+    pub fn from_module<M>(module: M) -> Result<Self, RegistryError>
+    where
+        M: Module<Scope = S>,
+    {
+        let mut configurer = ConfigurerImpl::new();
+        module.setup(&mut configurer);
+        Ok(Self {
+            providers: configurer.finish()?,
+        })
+    }
+
+    /// Returns the keys of all bindings in an unspecified order.
+    pub fn keys(&self) -> impl Iterator<Item = &dyn Key> {
+        self.providers.entries().map(|entry| entry.dyn_key())
+    }
+
+    /// Returns true if there is a binding of `key`.
+    pub fn contains(&self, key: &dyn Key) -> bool {
+        self.providers.get(key).is_some()
+    }
+
+    /// Describes the binding of `key`, or returns [`None`] if there isn't
+    /// one.
+    pub fn entry(&self, key: &dyn Key) -> Option<BindingSnapshot> {
+        self.providers.get(key).map(BindingSnapshot::new)
+    }
+
+    /// Describes all bindings, like [`Container::snapshot`].
+    pub fn snapshot(&self) -> ContainerSnapshot {
+        ContainerSnapshot::new(self.providers.entries())
+    }
+
+    /// Returns a copy of the catalog with its own copies of all providers, or
+    /// [`None`] if any provider can't be cloned, as reported by
+    /// [`Provider::clone_box`]. Containers created from the copies don't
+    /// share any cached object.
+    ///
+    /// [`Provider::clone_box`]: crate::provider::Provider::clone_box
+    pub fn try_clone(&self) -> Option<Self> {
+        self.providers
+            .try_clone()
+            .map(|providers| Self { providers })
+    }
+
+    /// Creates a root [`Container`] resolving objects from the bindings.
+    ///
+    /// Startup hooks registered by modules are discarded by the catalog, so
+    /// they don't run here.
+    pub fn into_container(self) -> Container<S> {
+        Container::new_root(self.providers)
+    }
+}
+
+impl<S: Scope> Registry for Catalog<S> {
+    type Scope = S;
+
+    fn init<M>(module: M) -> Result<Self, RegistryError>
+    where
+        M: Module<Scope = Self::Scope>,
+    {
+        Self::from_module(module)
+    }
+}
+
+impl<S: Scope> Debug for Catalog<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Catalog<S>")
+            .field("providers", &self.providers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    use crate::container::injector::TypedInjector;
+    use crate::container::snapshot::BindingLifetime;
+    use crate::key;
+    use crate::module;
+    use crate::module::dsl::bind;
+    use crate::provider::closure::RawClosureProvider;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    #[test]
+    fn catalog_from_module_succeeds() {
+        let catalog = Catalog::<SingletonScope>::from_module(module::from_fn(|configurer| {
+            bind::<i32>().to_instance(42).set_on(configurer);
+            bind::<Arc<i64>>()
+                .to_instance(Arc::new(42))
+                .within(SingletonScope)
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        assert_eq!(catalog.keys().count(), 2);
+        assert!(catalog.contains(&key::of::<i32>()));
+        assert!(!catalog.contains(&key::named::<i32>("name")));
+
+        let entry = catalog.entry(&key::of::<Arc<i64>>()).unwrap();
+        assert_eq!(
+            entry.lifetime,
+            BindingLifetime::Shared { scope: "Singleton" }
+        );
+        assert!(catalog.entry(&key::of::<i64>()).is_none());
+
+        let container = catalog.into_container();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+    }
+
+    #[test]
+    fn catalog_from_module_fails_when_key_is_duplicated() {
+        let res = Catalog::<SingletonScope>::from_module(module::from_fn(|configurer| {
+            bind::<i32>().to_instance(1).set_on(configurer);
+            bind::<i32>().to_instance(2).set_on(configurer);
+            Ok(())
+        }));
+
+        assert!(matches!(res, Err(RegistryError::KeyDuplicated { .. })));
+    }
+
+    #[test]
+    fn catalog_try_clone_succeeds() {
+        let catalog = Catalog::<SingletonScope>::from_module(module::from_fn(|configurer| {
+            bind::<i32>().to_instance(42).set_on(configurer);
+            bind::<Arc<i64>>()
+                .to_provider(RawClosureProvider::new(|_| {
+                    Ok(Ok::<_, Infallible>(Arc::new(42i64)))
+                }))
+                .within(SingletonScope)
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let cloned = catalog.try_clone().unwrap();
+        assert_eq!(cloned.keys().count(), 2);
+
+        let first = catalog.into_container();
+        let second = cloned.into_container();
+        assert_eq!(second.get(key::of::<i32>()).unwrap(), 42);
+        let object1 = first.get(key::of::<Arc<i64>>()).unwrap();
+        let object2 = second.get(key::of::<Arc<i64>>()).unwrap();
+        assert!(!Arc::ptr_eq(&object1, &object2));
+    }
+}
@@ -1,45 +1,191 @@
-use std::any::TypeId;
-use std::collections::HashMap;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap as StdHashMap, HashSet};
+use std::hash::BuildHasher;
+use std::mem;
 use std::sync::Arc;
 use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
 
-use oneshot::{Receiver, Sender};
-use parking_lot::{RwLock, RwLockWriteGuard};
+use oneshot::{Receiver, RecvTimeoutError, Sender};
+use parking_lot::{Mutex, ReentrantMutex, RwLock, RwLockWriteGuard};
 
-use crate::container::injector::{CallContext, Injector, InjectorError, ObjectMap};
+use crate::container::injector::{
+    CallContext, ConstructedVisitor, Downgrade, Injector, InjectorError, ObjectEntry, ObjectMap,
+};
 use crate::container::registry::{ProviderEntry, ProviderMap};
-use crate::container::Managed;
+use crate::container::snapshot::BindingLifetime;
+use crate::container::{ContainerSnapshot, KeyStats, LocalManaged, Managed, ResolutionPlan};
 use crate::key::Key;
-use crate::provider::{Provider, SharedProvider};
-use crate::scope::Scope;
+use crate::provider::{LocalProvider, Provider, SharedProvider};
+use crate::scope::{Scope, ScopeContext};
+use crate::util::hash::{KeyBuildHasher, KeyMap};
+
+/// The number of shards shared objects are distributed to by their keys.
+///
+/// Each shard is guarded by its own lock, so that constructing or reading an
+/// object doesn't block unrelated objects in other shards.
+const MANAGED_SHARDS: usize = 16;
 
 pub struct ContainerCore<S: Scope> {
     parent: Option<Arc<Self>>,
     providers: Arc<ProviderMap<S>>,
-    managed: RwLock<SharedManagedObjectData>,
+    managed: [RwLock<SharedManagedObjectData>; MANAGED_SHARDS],
+    hasher: KeyBuildHasher,
+    local: Mutex<KeyMap<Box<dyn Key>, LocalObject>>,
     scope: S,
+    wait_timeout: Option<Duration>,
+    used_keys: Option<Arc<UsedKeys>>,
+    stats: Option<Arc<Stats>>,
 }
 
+type LocalObject = Arc<ReentrantMutex<Box<dyn LocalManaged>>>;
+
+type UsedKeys = Mutex<HashSet<Box<dyn Key>, KeyBuildHasher>>;
+
+type Stats = Mutex<KeyMap<Box<dyn Key>, KeyStats>>;
+
 impl<S: Scope> ContainerCore<S> {
     pub fn new_root(providers: Arc<ProviderMap<S>>) -> Self {
+        debug_assert!(
+            S::SINGLETON.super_scope().is_none(),
+            "`S::SINGLETON` should be the longest scope"
+        );
         Self::new_impl(None, providers, S::SINGLETON)
     }
 
     pub fn new_sub(parent: Arc<Self>) -> Option<Self> {
-        if let Some(scope) = parent.scope.sub_scope() {
+        let scope = parent.scope.sub_scope()?;
+        Self::new_sub_in(parent, scope)
+    }
+
+    /// Creates a sub-container of `scope`, which should be one of the sub
+    /// scopes of the parent's scope.
+    pub fn new_sub_in(parent: Arc<Self>, scope: S) -> Option<Self> {
+        if parent.scope.sub_scopes().contains(&scope) {
             let providers = Arc::clone(&parent.providers);
-            Some(Self::new_impl(Some(parent), providers, scope))
+            providers.notify_scope_enter(scope);
+            let wait_timeout = parent.wait_timeout;
+            let used_keys = parent.used_keys.clone();
+            let stats = parent.stats.clone();
+            let mut core =
+                Self::new_impl(Some(parent), providers, scope).with_wait_timeout(wait_timeout);
+            core.used_keys = used_keys;
+            core.stats = stats;
+            Some(core)
         } else {
             None
         }
     }
 
     fn new_impl(parent: Option<Arc<Self>>, providers: Arc<ProviderMap<S>>, scope: S) -> Self {
+        let hasher = providers.hasher().clone();
         Self {
             parent,
             providers,
-            managed: RwLock::new(SharedManagedObjectData::new()),
+            managed: std::array::from_fn(|_| {
+                RwLock::new(SharedManagedObjectData::new(hasher.clone()))
+            }),
+            local: Mutex::new(KeyMap::with_hasher(hasher.clone())),
+            hasher,
             scope,
+            wait_timeout: None,
+            used_keys: None,
+            stats: None,
+        }
+    }
+
+    /// Sets how long to wait for another thread constructing the same shared
+    /// object, which is inherited by sub-containers. [`None`] means waiting
+    /// forever.
+    pub fn with_wait_timeout(mut self, wait_timeout: Option<Duration>) -> Self {
+        self.wait_timeout = wait_timeout;
+        self
+    }
+
+    /// Sets whether keys of requested objects are recorded, which is shared
+    /// with sub-containers and reported by [`ContainerCore::unused_keys`].
+    pub fn with_usage_tracking(mut self, enabled: bool) -> Self {
+        self.used_keys = enabled.then(|| {
+            let used_keys = HashSet::with_hasher(self.hasher.clone());
+            Arc::new(Mutex::new(used_keys))
+        });
+        self
+    }
+
+    /// Returns keys of all bindings never requested from this container or
+    /// any container sharing its usage tracking, or nothing if usage
+    /// tracking is disabled.
+    pub fn unused_keys(&self) -> Vec<Box<dyn Key>> {
+        let Some(used_keys) = self.used_keys.as_ref() else {
+            return Vec::new();
+        };
+        let used_keys = used_keys.lock();
+        let mut keys: Vec<_> = self
+            .providers
+            .entries()
+            .map(|entry| entry.dyn_key())
+            .filter(|key| !used_keys.contains(*key))
+            .map(|key| key.dyn_clone())
+            .collect();
+        keys.sort_by_cached_key(|key| key.to_string());
+        keys.dedup();
+        keys
+    }
+
+    /// Sets whether constructions are counted and timed per key, which is
+    /// shared with sub-containers and reported by [`ContainerCore::stats`].
+    pub fn with_stats(mut self, enabled: bool) -> Self {
+        self.stats = enabled.then(|| {
+            let stats = KeyMap::with_hasher(self.hasher.clone());
+            Arc::new(Mutex::new(stats))
+        });
+        self
+    }
+
+    /// Returns the construction statistics of all keys constructed at least
+    /// once, or nothing if statistics aren't collected.
+    pub fn stats(&self) -> StdHashMap<Box<dyn Key>, KeyStats> {
+        let Some(stats) = self.stats.as_ref() else {
+            return StdHashMap::new();
+        };
+        let stats = stats.lock();
+        stats
+            .iter()
+            .map(|(key, stats)| (key.dyn_clone(), stats.clone()))
+            .collect()
+    }
+
+    /// Runs `construct` for the object of `key`, recording it in the
+    /// statistics if they're collected.
+    fn measure_construction<T>(
+        &self,
+        key: &dyn Key,
+        construct: impl FnOnce() -> Result<T, InjectorError>,
+    ) -> Result<T, InjectorError> {
+        let Some(stats) = self.stats.as_ref() else {
+            return construct();
+        };
+        let start = Instant::now();
+        let res = construct();
+        let duration = start.elapsed();
+
+        let mut stats = stats.lock();
+        if let Some(entry) = stats.get_mut(key) {
+            entry.record(duration, res.as_ref().err());
+        } else {
+            let mut entry = KeyStats::default();
+            entry.record(duration, res.as_ref().err());
+            stats.insert(key.dyn_clone(), entry);
+        }
+        res
+    }
+
+    fn mark_used(&self, key: &dyn Key) {
+        if let Some(used_keys) = self.used_keys.as_ref() {
+            let mut used_keys = used_keys.lock();
+            if !used_keys.contains(key) {
+                used_keys.insert(key.dyn_clone());
+            }
         }
     }
 
@@ -47,37 +193,255 @@ impl<S: Scope> ContainerCore<S> {
         self.scope
     }
 
-    fn get_object(&self, context: &CallContext) -> Result<Box<dyn Managed>, InjectorError> {
+    pub fn scope_chain(&self) -> Vec<S> {
+        let mut chain = Vec::new();
+        let mut core = Some(self);
+        while let Some(current) = core {
+            chain.push(current.scope);
+            core = current.parent.as_deref();
+        }
+        chain.reverse();
+        chain
+    }
+
+    pub fn is_constructed(&self, key: &dyn Key) -> bool {
+        let mut core = Some(self);
+        while let Some(current) = core {
+            if current.try_get_constructed_object(key).is_some()
+                || current.local.lock().contains_key(key)
+            {
+                return true;
+            }
+            core = current.parent.as_deref();
+        }
+        false
+    }
+
+    pub fn clear_scope_cache(&self) -> usize {
+        // Evicted objects are dropped after releasing the locks, in case their
+        // destructors access the container again.
+        let objects: Vec<_> = self
+            .managed
+            .iter()
+            .map(|shard| {
+                mem::replace(
+                    &mut shard.write().objects,
+                    ObjectMap::new(self.hasher.clone()),
+                )
+            })
+            .collect();
+        objects.iter().map(ObjectMap::len).sum()
+    }
+
+    pub fn health_check(&self) -> StdHashMap<Box<dyn Key>, Result<(), InjectorError>> {
+        self.singleton_keys()
+            .map(|key| (key.dyn_clone(), self.get_singleton(key).map(|_| ())))
+            .collect()
+    }
+
+    pub fn snapshot(&self) -> ContainerSnapshot {
+        ContainerSnapshot::new(self.providers.entries())
+    }
+
+    /// Explains how [`ContainerCore::get_object`] or
+    /// [`ContainerCore::with_local_object`] would resolve `key`, following
+    /// the same forwarding rules without constructing anything.
+    pub fn resolve_path(&self, key: &dyn Key) -> Result<ResolutionPlan<S>, InjectorError> {
+        let entry = self.try_get_provider_by_key(key)?;
+        let (cached, constructed) = match entry {
+            ProviderEntry::Shared { scope, .. } | ProviderEntry::Local { scope, .. }
+                if self.should_forward_request_to_parent(*scope) =>
+            {
+                if let Some(parent) = self.parent.as_ref() {
+                    return parent.resolve_path(key);
+                } else {
+                    unreachable!("Parent context should exist")
+                }
+            }
+            ProviderEntry::Shared { scope, .. } if *scope == self.scope => {
+                (true, self.try_get_constructed_object(key).is_some())
+            }
+            ProviderEntry::Local { scope, .. } if *scope == self.scope => {
+                (true, self.local.lock().contains_key(key))
+            }
+            _ => (false, false),
+        };
+
+        Ok(ResolutionPlan {
+            scope: self.scope,
+            lifetime: BindingLifetime::new(entry),
+            cached,
+            constructed,
+            dependencies: entry.dependencies(),
+        })
+    }
+
+    pub fn init_eager(&self) -> Result<(), InjectorError> {
+        self.singleton_keys()
+            .try_for_each(|key| self.get_singleton(key).map(|_| ()))
+    }
+
+    /// Gets a singleton object on behalf of the container itself, which
+    /// unlike requests from users doesn't mark `key` as used, so that
+    /// [`ContainerCore::unused_keys`] still reports it.
+    fn get_singleton(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+        let context = CallContext::new(key);
+        self.get_object_impl(&context, self)
+    }
+
+    fn singleton_keys(&self) -> impl Iterator<Item = &dyn Key> {
+        self.providers.entries().filter_map(|entry| match entry {
+            ProviderEntry::Shared { key, scope, .. } if *scope == S::SINGLETON => {
+                Some(key.as_ref())
+            }
+            _ => None,
+        })
+    }
+
+    /// Gets the object identified by the key of `context`. Objects which
+    /// aren't cached by any container are constructed against `injector`,
+    /// which allows decorators to take part in resolving their dependencies.
+    pub fn get_object(
+        &self,
+        context: &CallContext,
+        injector: &dyn Injector,
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        self.mark_used(context.key());
+        self.get_object_impl(context, injector)
+    }
+
+    fn get_object_impl(
+        &self,
+        context: &CallContext,
+        injector: &dyn Injector,
+    ) -> Result<Box<dyn Managed>, InjectorError> {
         let key = context.key();
+        if ScopeContext::is_key(key) {
+            return Ok(Box::new(ScopeContext::new(&self.scope_chain())));
+        }
         if let Some(object) = self.try_get_constructed_object(key) {
             return Ok(object);
         }
 
         match self.try_get_provider_by_key(key)? {
             ProviderEntry::Shared {
-                provider, scope, ..
+                provider,
+                scope,
+                weak,
+                ..
             } => {
                 if self.should_forward_request_to_parent(*scope) {
                     self.get_object_from_parent(context)
                 } else if *scope == self.scope {
-                    self.get_shared_object_from_self(provider.as_ref(), context)
+                    self.get_shared_object_from_self(provider.as_ref(), *weak, context)
                 } else {
-                    self.get_unbounded_object_from_self(provider.upcast_provider(), context)
+                    self.get_unbounded_object_from_self(
+                        provider.upcast_provider(),
+                        context,
+                        injector,
+                    )
                 }
             }
             ProviderEntry::Owned { provider, .. } => {
-                self.get_unbounded_object_from_self(provider.as_ref(), context)
+                self.get_unbounded_object_from_self(provider.as_ref(), context, injector)
             }
+            // Local objects are owned by containers and never handed out.
+            ProviderEntry::Local { .. } => Err(InjectorError::NotFound {
+                key: key.dyn_clone(),
+            }),
         }
     }
 
+    /// Passes the cached shared object of `key` to `visitor`, looking it up in
+    /// the same container [`ContainerCore::get_object`] would, and returns
+    /// false if it isn't constructed yet.
+    pub fn with_constructed_object(
+        &self,
+        key: &dyn Key,
+        visitor: &mut dyn ConstructedVisitor,
+    ) -> bool {
+        self.mark_used(key);
+        {
+            let managed = self.managed_shard(key).read();
+            if let Some(entry) = managed.objects.get(key) {
+                return entry.visit(visitor);
+            }
+        }
+
+        match (self.providers.get_in(key, self.scope), self.parent.as_ref()) {
+            (Some(ProviderEntry::Shared { scope, .. }), Some(parent))
+                if self.should_forward_request_to_parent(*scope) =>
+            {
+                parent.with_constructed_object(key, visitor)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn with_local_object<F, R>(&self, key: &dyn Key, f: F) -> Result<R, InjectorError>
+    where
+        F: FnOnce(&dyn LocalManaged) -> R,
+    {
+        self.mark_used(key);
+        let ProviderEntry::Local {
+            provider, scope, ..
+        } = self.try_get_provider_by_key(key)?
+        else {
+            return Err(InjectorError::NotFound {
+                key: key.dyn_clone(),
+            });
+        };
+
+        if self.should_forward_request_to_parent(*scope) {
+            if let Some(parent) = self.parent.as_ref() {
+                parent.with_local_object(key, f)
+            } else {
+                unreachable!("Parent context should exist")
+            }
+        } else if *scope == self.scope {
+            let object = self.get_local_object_from_self(provider.as_ref(), key)?;
+            let object = object.lock();
+            Ok(f(object.as_ref()))
+        } else {
+            let context = CallContext::new(key).with_scope(self.scope.to_str());
+            let object =
+                self.measure_construction(key, || provider.dyn_provide_local(self, &context))?;
+            Ok(f(object.as_ref()))
+        }
+    }
+
+    fn get_local_object_from_self(
+        &self,
+        provider: &dyn LocalProvider,
+        key: &dyn Key,
+    ) -> Result<LocalObject, InjectorError> {
+        // Holding the lock during construction is fine, since a local provider
+        // only has access to the `Injector`, which never reaches local objects.
+        let mut local = self.local.lock();
+        if let Some(object) = local.get(key) {
+            return Ok(Arc::clone(object));
+        }
+
+        let context = CallContext::new(key).with_scope(self.scope.to_str());
+        let object =
+            self.measure_construction(key, || provider.dyn_provide_local(self, &context))?;
+        let object = Arc::new(ReentrantMutex::new(object));
+        local.insert(key.dyn_clone(), Arc::clone(&object));
+        Ok(object)
+    }
+
+    fn managed_shard(&self, key: &dyn Key) -> &RwLock<SharedManagedObjectData> {
+        let index = self.hasher.hash_one(key) as usize % MANAGED_SHARDS;
+        &self.managed[index]
+    }
+
     fn try_get_constructed_object(&self, key: &dyn Key) -> Option<Box<dyn Managed>> {
-        let objects = &self.managed.read().objects;
-        objects.get(key).map(|entry| entry.clone_managed())
+        let objects = &self.managed_shard(key).read().objects;
+        objects.get(key).and_then(ObjectEntry::clone_managed)
     }
 
     fn try_get_provider_by_key(&self, key: &dyn Key) -> Result<&ProviderEntry<S>, InjectorError> {
-        if let Some(provider) = self.providers.get(key) {
+        if let Some(provider) = self.providers.get_in(key, self.scope) {
             Ok(provider)
         } else {
             Err(InjectorError::NotFound {
@@ -96,7 +460,9 @@ impl<S: Scope> ContainerCore<S> {
         context: &CallContext,
     ) -> Result<Box<dyn Managed>, InjectorError> {
         if let Some(parent) = self.parent.as_ref() {
-            parent.get_object(context)
+            // `self` has already marked the key if the request came from a
+            // user, and must not mark it on its own behalf.
+            parent.get_object_impl(context, parent.as_ref())
         } else {
             // If the parent context doesn't exist, `self` must be a root
             // context whose scope is `S::SINGLETON`, and no other scope
@@ -109,19 +475,34 @@ impl<S: Scope> ContainerCore<S> {
     fn get_shared_object_from_self(
         &self,
         provider: &dyn SharedProvider,
+        weak: Option<Downgrade>,
         context: &CallContext,
     ) -> Result<Box<dyn Managed>, InjectorError> {
         let key = context.key();
-        let mut managed = self.managed.write();
+        loop {
+            let managed = self.managed_shard(key).write();
 
-        if let Some(context) = managed.constructing.get_mut(key) {
-            if context.is_constructed_by_current_thread() {
-                Err(self.stop_construction_on_cyclic_dependency(managed, key))
-            } else {
-                self.wait_for_constructed_object(managed, key)
+            // Another thread may have constructed the object since it was
+            // looked up without the write lock.
+            if let Some(object) = managed
+                .objects
+                .get(key)
+                .and_then(ObjectEntry::clone_managed)
+            {
+                return Ok(object);
+            }
+
+            let Some(object_context) = managed.constructing.get(key) else {
+                return self.construct_shared_object(managed, provider, weak, context);
+            };
+            if object_context.is_constructed_by_current_thread() {
+                return Err(self.stop_construction_on_cyclic_dependency(managed, key));
+            }
+            // A weakly cached object may be dropped before a waiter reads it,
+            // in which case it's constructed again.
+            if let Some(object) = self.wait_for_constructed_object(managed, key)? {
+                return Ok(object);
             }
-        } else {
-            self.construct_shared_object(managed, provider, context)
         }
     }
 
@@ -142,7 +523,7 @@ impl<S: Scope> ContainerCore<S> {
         &self,
         managed: RwLockWriteGuard<SharedManagedObjectData>,
         key: &dyn Key,
-    ) -> Result<Box<dyn Managed>, InjectorError> {
+    ) -> Result<Option<Box<dyn Managed>>, InjectorError> {
         let receiver = self.register_waiter_on_object_context(managed, key);
         self.get_object_on_object_context_response(receiver, key)
     }
@@ -164,17 +545,36 @@ impl<S: Scope> ContainerCore<S> {
         &self,
         receiver: Receiver<WaitResponse>,
         key: &dyn Key,
-    ) -> Result<Box<dyn Managed>, InjectorError> {
-        match receiver.recv() {
-            Ok(WaitResponse::Constructed) => {
-                let managed = self.managed.read();
-                let Some(object) = managed.objects.get(key) else {
-                    unreachable!("`object` should already be put into `self.managed.objects`")
-                };
-                Ok(object.clone_managed())
+    ) -> Result<Option<Box<dyn Managed>>, InjectorError> {
+        let response = if let Some(timeout) = self.wait_timeout {
+            match receiver.recv_timeout(timeout) {
+                Ok(response) => response,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(InjectorError::ConstructionTimeout {
+                        key: key.dyn_clone(),
+                    })
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    unreachable!("the peer should send a message")
+                }
+            }
+        } else {
+            receiver
+                .recv()
+                .unwrap_or_else(|_| unreachable!("the peer should send a message"))
+        };
+
+        match response {
+            // The object may be evicted by `clear_scope_cache` before it's read
+            // here, in which case the caller constructs it again.
+            WaitResponse::Constructed => {
+                let managed = self.managed_shard(key).read();
+                Ok(managed
+                    .objects
+                    .get(key)
+                    .and_then(ObjectEntry::clone_managed))
             }
-            Ok(WaitResponse::Error(err)) => Err(err),
-            Err(_) => unreachable!("the peer should send a message"),
+            WaitResponse::Error(err) => Err(err),
         }
     }
 
@@ -182,6 +582,7 @@ impl<S: Scope> ContainerCore<S> {
         &self,
         mut managed: RwLockWriteGuard<SharedManagedObjectData>,
         provider: &dyn SharedProvider,
+        weak: Option<Downgrade>,
         context: &CallContext,
     ) -> Result<Box<dyn Managed>, InjectorError> {
         let key = context.key();
@@ -190,15 +591,17 @@ impl<S: Scope> ContainerCore<S> {
         managed.constructing.insert(key.dyn_clone(), object_context);
         drop(managed);
 
-        match provider.dyn_provide_shared(self, context) {
+        let context = context.with_scope(self.scope.to_str());
+        match self.measure_construction(key, || provider.dyn_provide_shared(self, &context)) {
             Ok(object) => {
-                let mut managed = self.managed.write();
-                managed.objects.insert(key.dyn_clone(), object.dyn_clone());
+                let mut managed = self.managed_shard(key).write();
+                let entry = ObjectEntry::new(object.as_ref(), weak);
+                managed.objects.insert(key.dyn_clone(), entry);
                 self.notify_waiters(managed, key, WaitResponse::Constructed);
                 Ok(object.upcast_managed())
             }
             Err(err) => {
-                let managed = self.managed.write();
+                let managed = self.managed_shard(key).write();
                 self.notify_waiters(managed, key, WaitResponse::Error(err.clone()));
                 Err(err)
             }
@@ -221,14 +624,24 @@ impl<S: Scope> ContainerCore<S> {
         &self,
         provider: &dyn Provider,
         context: &CallContext,
+        injector: &dyn Injector,
     ) -> Result<Box<dyn Managed>, InjectorError> {
         let key = context.key();
-        if context.trace().previous_exist_key(key) {
+        if !provider.dyn_is_leaf() && context.trace().previous_exist_key(key) {
             Err(InjectorError::CyclicDependency {
                 key: key.dyn_clone(),
             })
         } else {
-            provider.dyn_provide(self, context)
+            let context = context.with_scope(self.scope.to_str());
+            self.measure_construction(key, || provider.dyn_provide(injector, &context))
+        }
+    }
+}
+
+impl<S: Scope> Drop for ContainerCore<S> {
+    fn drop(&mut self) {
+        if self.parent.is_some() {
+            self.providers.notify_scope_exit(self.scope);
         }
     }
 }
@@ -236,7 +649,7 @@ impl<S: Scope> ContainerCore<S> {
 impl<S: Scope> Injector for ContainerCore<S> {
     fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
         let context = CallContext::new(key);
-        self.get_object(&context)
+        self.get_object(&context, self)
     }
 
     fn dyn_get_dependency<'a>(
@@ -245,24 +658,41 @@ impl<S: Scope> Injector for ContainerCore<S> {
         context: &'a CallContext<'a>,
     ) -> Result<Box<dyn Managed>, InjectorError> {
         let context = context.append(key);
-        self.get_object(&context)
+        self.get_object(&context, self)
+    }
+
+    fn dyn_get_with_context(
+        &self,
+        key: &dyn Key,
+        user_data: &(dyn Any + Sync),
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        let context = CallContext::new(key).with_user_data(user_data);
+        self.get_object(&context, self)
     }
 
     fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
         self.providers.keys(type_id)
     }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.providers.contains_target(type_id)
+    }
+
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        self.with_constructed_object(key, visitor)
+    }
 }
 
 struct SharedManagedObjectData {
     objects: ObjectMap,
-    constructing: HashMap<Box<dyn Key>, ConstructingObjectContext>,
+    constructing: KeyMap<Box<dyn Key>, ConstructingObjectContext>,
 }
 
 impl SharedManagedObjectData {
-    fn new() -> Self {
+    fn new(hasher: KeyBuildHasher) -> Self {
         Self {
-            objects: ObjectMap::new(),
-            constructing: HashMap::new(),
+            objects: ObjectMap::new(hasher.clone()),
+            constructing: KeyMap::with_hasher(hasher),
         }
     }
 }
@@ -304,11 +734,13 @@ enum WaitResponse {
 #[cfg(test)]
 mod tests {
     use std::convert::Infallible;
+    use std::sync::mpsc;
 
     use crate::container::injector::TypedInjector;
     use crate::key;
     use crate::provider::closure::RawClosureProvider;
     use crate::provider::instance::InstanceProvider;
+    use crate::provider::{TypedProvider, TypedSharedProvider};
     use crate::scope::WebScope;
 
     use super::*;
@@ -391,10 +823,10 @@ mod tests {
         let object = sub_context.get(key).unwrap();
         assert_eq!(object.id, 0u32);
 
-        let managed = root_context.managed.read();
+        let managed = root_context.managed_shard(&key).read();
         assert!(managed.objects.get(&key).is_some());
 
-        let managed = sub_context.managed.read();
+        let managed = sub_context.managed_shard(&key).read();
         assert!(managed.objects.get(&key).is_none());
     }
 
@@ -494,6 +926,173 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn shared_context_get_fails_when_waiting_times_out() {
+        let (release, blocker) = mpsc::channel::<()>();
+        let blocker = Mutex::new(blocker);
+        let mut providers = ProviderMap::new();
+        providers.insert_shared(
+            Box::new(key::of::<Arc<i32>>()),
+            Box::new(RawClosureProvider::new(move |_| {
+                // Blocks until `release` is dropped.
+                let _ = blocker.lock().recv();
+                Ok(Ok::<_, Infallible>(Arc::new(42)))
+            })),
+            WebScope::Singleton,
+        );
+
+        let context = Arc::new(
+            ContainerCore::new_root(Arc::new(providers))
+                .with_wait_timeout(Some(Duration::from_millis(10))),
+        );
+        let key: &dyn Key = &key::of::<Arc<i32>>();
+        let ctx = Arc::clone(&context);
+        let constructor = thread::spawn(move || ctx.get(key::of::<Arc<i32>>()));
+        while !context
+            .managed_shard(key)
+            .read()
+            .constructing
+            .contains_key(key)
+        {
+            thread::yield_now();
+        }
+
+        assert!(matches!(
+            context.get(key::of::<Arc<i32>>()),
+            Err(InjectorError::ConstructionTimeout { .. })
+        ));
+        drop(release);
+        assert_eq!(*constructor.join().unwrap().unwrap(), 42);
+    }
+
+    #[test]
+    fn shared_context_get_succeeds_when_cache_is_cleared_while_waiting() {
+        let (release, blocker) = mpsc::channel::<()>();
+        let blocker = Mutex::new(blocker);
+        let mut providers = ProviderMap::new();
+        providers.insert_shared(
+            Box::new(key::of::<Arc<i32>>()),
+            Box::new(RawClosureProvider::new(move |_| {
+                // Only the first construction blocks, until `release` is dropped.
+                let _ = blocker.lock().recv();
+                Ok(Ok::<_, Infallible>(Arc::new(42)))
+            })),
+            WebScope::Singleton,
+        );
+
+        let context = Arc::new(ContainerCore::new_root(Arc::new(providers)));
+        let key: &dyn Key = &key::of::<Arc<i32>>();
+        let waiters = |context: &ContainerCore<WebScope>| {
+            let managed = context.managed_shard(key).read();
+            managed.constructing.get(key).map(|c| c.waiters.len())
+        };
+
+        let ctx = Arc::clone(&context);
+        let constructor = thread::spawn(move || ctx.get(key::of::<Arc<i32>>()));
+        while waiters(&context).is_none() {
+            thread::yield_now();
+        }
+        let ctx = Arc::clone(&context);
+        let waiter = thread::spawn(move || ctx.get(key::of::<Arc<i32>>()));
+        while waiters(&context) != Some(1) {
+            thread::yield_now();
+        }
+
+        drop(release);
+        while !waiter.is_finished() {
+            context.clear_scope_cache();
+        }
+        assert_eq!(*constructor.join().unwrap().unwrap(), 42);
+        assert_eq!(*waiter.join().unwrap().unwrap(), 42);
+    }
+
+    #[test]
+    fn shared_context_is_constructed_succeeds() {
+        let mut providers = ProviderMap::new();
+        providers.insert_shared(
+            Box::new(key::qualified::<Arc<TestObject>>(0u32)),
+            TestObject::get_provider(0u32),
+            WebScope::Singleton,
+        );
+        providers.insert_shared(
+            Box::new(key::qualified::<Arc<TestObject>>(1u32)),
+            TestObject::get_provider(1u32),
+            WebScope::Session,
+        );
+        providers.insert(
+            Box::new(key::of::<i32>()),
+            Box::new(InstanceProvider::new(0i32)),
+        );
+
+        let root_context = Arc::new(ContainerCore::new_root(Arc::new(providers)));
+        let sub_context = Arc::new(ContainerCore::new_sub(Arc::clone(&root_context)).unwrap());
+        let singleton_key = key::qualified::<Arc<TestObject>>(0u32);
+        let session_key = key::qualified::<Arc<TestObject>>(1u32);
+
+        assert!(!sub_context.is_constructed(&singleton_key));
+        assert!(!sub_context.is_constructed(&session_key));
+
+        let _ = sub_context.get(singleton_key).unwrap();
+        let _ = sub_context.get(session_key).unwrap();
+        let _ = sub_context.get(key::of::<i32>()).unwrap();
+
+        assert!(sub_context.is_constructed(&singleton_key));
+        assert!(sub_context.is_constructed(&session_key));
+        assert!(root_context.is_constructed(&singleton_key));
+        assert!(!root_context.is_constructed(&session_key));
+        assert!(!sub_context.is_constructed(&key::of::<i32>()));
+    }
+
+    #[derive(Debug)]
+    struct ScopeNameProvider;
+
+    impl TypedProvider for ScopeNameProvider {
+        type Output = Arc<&'static str>;
+
+        fn provide<I>(
+            &self,
+            _injector: &I,
+            context: &CallContext<'_>,
+        ) -> Result<Self::Output, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Arc::new(context.current_scope().unwrap()))
+        }
+    }
+
+    impl TypedSharedProvider for ScopeNameProvider {}
+
+    #[test]
+    fn shared_context_get_stamps_current_scope_into_context() {
+        let mut providers = ProviderMap::new();
+        providers.insert_shared(
+            Box::new(key::named::<Arc<&'static str>>("singleton")),
+            Box::new(ScopeNameProvider),
+            WebScope::Singleton,
+        );
+        providers.insert_shared(
+            Box::new(key::named::<Arc<&'static str>>("session")),
+            Box::new(ScopeNameProvider),
+            WebScope::Session,
+        );
+        providers.insert(
+            Box::new(key::named::<Arc<&'static str>>("transient")),
+            Box::new(ScopeNameProvider),
+        );
+
+        let root_context = Arc::new(ContainerCore::new_root(Arc::new(providers)));
+        let session_context = Arc::new(ContainerCore::new_sub(Arc::clone(&root_context)).unwrap());
+        let request_context = ContainerCore::new_sub(session_context).unwrap();
+
+        let name = request_context.get(key::named::<Arc<&'static str>>("singleton"));
+        assert_eq!(*name.unwrap(), "Singleton");
+        let name = request_context.get(key::named::<Arc<&'static str>>("session"));
+        assert_eq!(*name.unwrap(), "Session");
+        let name = request_context.get(key::named::<Arc<&'static str>>("transient"));
+        assert_eq!(*name.unwrap(), "Request");
+    }
+
     #[test]
     fn shared_context_get_fails_when_key_not_found() {
         let providers: ProviderMap<WebScope> = ProviderMap::new();
@@ -1,11 +1,18 @@
-use std::any::TypeId;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::container::core::ContainerCore;
-use crate::container::injector::{CallContext, Injector, InjectorError};
-use crate::container::registry::{ConfigurerImpl, ProviderMap, Registry, RegistryError};
-use crate::container::Managed;
-use crate::key::Key;
+use crate::container::injector::{
+    CallContext, ConstructedVisitor, Injector, InjectorError, TypedInjector,
+};
+use crate::container::registry::{ProviderMap, Registry, RegistryError};
+use crate::container::{
+    ContainerBuilder, ContainerSnapshot, KeyStats, Local, LocalManaged, Managed, ResolutionPlan,
+    ScopedInjector,
+};
+use crate::key::{Key, TypedKey, TypedQualifier};
 use crate::module::Module;
 use crate::scope::Scope;
 
@@ -15,20 +22,264 @@ pub struct Container<S: Scope> {
 }
 
 impl<S: Scope> Container<S> {
-    fn new_root(providers: ProviderMap<S>) -> Self {
-        let core = Arc::new(ContainerCore::new_root(Arc::new(providers)));
+    pub(super) fn new_root(providers: ProviderMap<S>) -> Self {
+        Self::from_core(ContainerCore::new_root(Arc::new(providers)))
+    }
+
+    pub(super) fn from_core(core: ContainerCore<S>) -> Self {
+        let core = Arc::new(core);
         Self { core }
     }
 
+    /// Returns a [`ContainerBuilder`] to configure a new root container.
+    pub fn builder() -> ContainerBuilder<S> {
+        ContainerBuilder::new()
+    }
+
     pub fn sub_container(&self) -> Option<Self> {
         ContainerCore::new_sub(Arc::clone(&self.core)).map(|core| Self {
             core: Arc::new(core),
         })
     }
 
+    /// Creates nested sub-containers level by level until reaching the
+    /// `target` scope, and returns the innermost one, or [`None`] if `target`
+    /// isn't strictly within the current scope.
+    ///
+    /// Intermediate sub-containers are created as usual, so listeners are
+    /// notified for each skipped level, and objects of those scopes are
+    /// shared by the returned container and its sub-containers. If the scope
+    /// hierarchy branches, the levels are the super scopes of `target`, so
+    /// the branch leading to `target` is entered.
+    pub fn sub_container_to(&self, target: S) -> Option<Self> {
+        let current = self.current_scope();
+        if !target.within(current) || target == current {
+            return None;
+        }
+
+        let mut path = vec![target];
+        while let Some(scope) = path.last().and_then(|scope| scope.super_scope()) {
+            if scope == current {
+                break;
+            }
+            path.push(scope);
+        }
+
+        let mut container = self.clone();
+        for scope in path.into_iter().rev() {
+            let core = ContainerCore::new_sub_in(Arc::clone(&container.core), scope)?;
+            container = Self {
+                core: Arc::new(core),
+            };
+        }
+        Some(container)
+    }
+
     pub fn current_scope(&self) -> S {
         self.core.current_scope()
     }
+
+    /// Returns the scopes of all containers from the root to this container,
+    /// e.g. `[Singleton, Session, Request]` for a request container.
+    pub fn scope_chain(&self) -> Vec<S> {
+        self.core.scope_chain()
+    }
+
+    /// Gets the object identified by `key` like [`TypedInjector::get`], along
+    /// with the wall-clock time spent on this call.
+    ///
+    /// The duration only reflects the work done by this call. Getting a shared
+    /// object which has already been constructed takes nearly no time, while
+    /// the first construction of an object includes constructing all its
+    /// dependencies not constructed yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object can't be retrieved.
+    ///
+    /// [`TypedInjector::get`]: crate::container::injector::TypedInjector::get
+    pub fn get_timed<K: TypedKey>(&self, key: K) -> Result<(K::Target, Duration), InjectorError> {
+        let start = Instant::now();
+        let object = self.get(key)?;
+        Ok((object, start.elapsed()))
+    }
+
+    /// Returns true if the shared object identified by `key` has already been
+    /// constructed in this container or any of its parents. No construction
+    /// is triggered, and keys of transient objects always report `false`.
+    pub fn is_constructed<K: TypedKey>(&self, key: K) -> bool {
+        self.core.is_constructed(&key)
+    }
+
+    /// Returns true if there is any object of the target type `T`, regardless
+    /// of its qualifier. Unlike checking [`Injector::keys`], no key is
+    /// enumerated.
+    pub fn has_any<T: Managed>(&self) -> bool {
+        self.contains_target(TypeId::of::<T>())
+    }
+
+    /// Returns a [`ScopedInjector`] which resolves objects of type `T`
+    /// qualified by `from` as if they were qualified by `to`. More overrides
+    /// can be added with [`ScopedInjector::with_qualifier_override`].
+    pub fn with_qualifier_override<T: Managed>(
+        &self,
+        from: impl TypedQualifier,
+        to: impl TypedQualifier,
+    ) -> ScopedInjector<S> {
+        ScopedInjector::new(Arc::clone(&self.core)).with_qualifier_override::<T>(from, to)
+    }
+
+    /// Returns a description of all bindings of the container, which doesn't
+    /// include any object.
+    pub fn snapshot(&self) -> ContainerSnapshot {
+        self.core.snapshot()
+    }
+
+    /// Returns keys of all bindings which have never been requested, sorted
+    /// by their names, so that bindings nothing consumes can be found.
+    ///
+    /// Requests are only recorded if the container is built with
+    /// [`ContainerBuilder::track_usage`], and are shared by the root container
+    /// and all its sub-containers. Without usage tracking, no key is
+    /// returned.
+    pub fn unused_keys(&self) -> Vec<Box<dyn Key>> {
+        self.core.unused_keys()
+    }
+
+    /// Returns how many times each key has been constructed and how long the
+    /// constructions took in total, along with the latest construction error.
+    ///
+    /// Both constructions of transient objects and first constructions of
+    /// shared or local objects are counted, including failed ones, while
+    /// requests served from a cache are not. The duration of a construction
+    /// includes constructing its dependencies, so durations of dependent keys
+    /// overlap.
+    ///
+    /// Statistics are only collected if the container is built with
+    /// [`ContainerBuilder::collect_stats`], and are shared by the root
+    /// container and all its sub-containers. Otherwise, nothing is returned.
+    pub fn stats(&self) -> HashMap<Box<dyn Key>, KeyStats> {
+        self.core.stats()
+    }
+
+    /// Explains how `key` would be resolved from this container without
+    /// constructing anything, e.g. which container would construct the
+    /// object and whether it's cached there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InjectorError::NotFound`] if `key` isn't bound.
+    pub fn resolve_path(&self, key: &dyn Key) -> Result<ResolutionPlan<S>, InjectorError> {
+        self.core.resolve_path(key)
+    }
+
+    /// Converts the container into a type-erased [`Injector`], which doesn't
+    /// mention the scope type `S`.
+    ///
+    /// This lets scope-agnostic code hold a resolver, such as framework glue
+    /// storing it in a field. The returned handle shares objects with `self`
+    /// and other clones of it, but gives up APIs depending on the scope, like
+    /// [`Container::sub_container`], so sub-containers should be created
+    /// before erasing the scope.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use iocc::prelude::*;
+    /// # use iocc::container::injector::Injector;
+    /// # use iocc::module;
+    /// # use iocc::scope::SingletonScope;
+    /// let module = module::from_fn(|configurer| {
+    ///     bind::<i32>().to_instance(42).set_on(configurer);
+    ///     Ok(())
+    /// });
+    /// let container = Container::<SingletonScope>::init(module).unwrap();
+    ///
+    /// let injector: Arc<dyn Injector> = container.into_injector();
+    /// assert_eq!(injector.get(key::of::<i32>()).unwrap(), 42);
+    /// ```
+    pub fn into_injector(self) -> Arc<dyn Injector> {
+        self.core
+    }
+
+    pub(super) fn init_eager(&self) -> Result<(), InjectorError> {
+        self.core.init_eager()
+    }
+
+    /// Tries to resolve every shared object bound in the singleton scope, and
+    /// reports the result for each key.
+    ///
+    /// Objects not constructed yet are constructed, and failures don't stop
+    /// the remaining objects from being checked, so the report is complete.
+    /// This makes it suitable for implementing a readiness probe.
+    pub fn health_check(&self) -> HashMap<Box<dyn Key>, Result<(), InjectorError>> {
+        self.core.health_check()
+    }
+
+    /// Drops all shared objects constructed in this container, so that they
+    /// will be constructed again on next access, and returns the number of
+    /// evicted objects. Objects cached by parent containers are kept.
+    ///
+    /// This races with concurrent constructions in the same container, whose
+    /// objects may be cached right after eviction, so it should be called at
+    /// a quiescent point, e.g. when invalidating a session.
+    pub fn clear_scope_cache(&self) -> usize {
+        self.core.clear_scope_cache()
+    }
+
+    /// Calls `f` with a reference to the local object identified by `key`,
+    /// and returns its result.
+    ///
+    /// The local object is constructed on first access in the container whose
+    /// scope matches its own, and the same object is reused afterwards. If the
+    /// object's scope is shorter than the current scope, a temporary object is
+    /// constructed and dropped once `f` returns. While `f` is running, other
+    /// threads accessing the same object are blocked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` isn't registered as a local object, or the
+    /// object construction fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::cell::Cell;
+    /// # use std::convert::Infallible;
+    /// # use iocc::prelude::*;
+    /// # use iocc::container::Local;
+    /// # use iocc::container::registry::TypedConfigurer;
+    /// # use iocc::module;
+    /// # use iocc::provider::local::LocalClosureProvider;
+    /// # use iocc::scope::SingletonScope;
+    /// let module = module::from_fn(|configurer| {
+    ///     configurer.register_local(
+    ///         key::of::<Local<Cell<i32>>>(),
+    ///         LocalClosureProvider::new(|_| Ok(Ok::<_, Infallible>(Cell::new(0)))),
+    ///         SingletonScope,
+    ///     );
+    ///     Ok(())
+    /// });
+    /// let container = Container::<SingletonScope>::init(module).unwrap();
+    ///
+    /// let key = key::of::<Local<Cell<i32>>>();
+    /// container.with_local(key, |cell| cell.set(42)).unwrap();
+    /// assert_eq!(container.with_local(key, |cell| cell.get()).unwrap(), 42);
+    /// ```
+    pub fn with_local<K, T, F, R>(&self, key: K, f: F) -> Result<R, InjectorError>
+    where
+        K: TypedKey<Target = Local<T>>,
+        T: LocalManaged,
+        F: FnOnce(&T) -> R,
+    {
+        self.core.with_local_object(&key, |object| {
+            let Some(object) = object.as_any().downcast_ref::<T>() else {
+                unreachable!("the object's type should be `T`")
+            };
+            f(object)
+        })
+    }
 }
 
 impl<S: Scope> Registry for Container<S> {
@@ -38,9 +289,7 @@ impl<S: Scope> Registry for Container<S> {
     where
         M: Module<Scope = Self::Scope>,
     {
-        let mut configurer = ConfigurerImpl::new();
-        module.setup(&mut configurer);
-        configurer.finish().map(Self::new_root)
+        Self::builder().module(module).build()
     }
 }
 
@@ -57,13 +306,30 @@ impl<S: Scope> Injector for Container<S> {
         self.core.dyn_get_dependency(key, context)
     }
 
+    fn dyn_get_with_context(
+        &self,
+        key: &dyn Key,
+        user_data: &(dyn Any + Sync),
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        self.core.dyn_get_with_context(key, user_data)
+    }
+
     fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
         self.core.keys(type_id)
     }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.core.contains_target(type_id)
+    }
+
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        self.core.dyn_with_constructed(key, visitor)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::any;
     use std::collections::HashMap;
     use std::convert::Infallible;
     use std::error::Error;
@@ -71,12 +337,19 @@ mod tests {
 
     use parking_lot::Mutex;
 
+    use std::cell::RefCell;
+
     use crate::container::injector::TypedInjector;
     use crate::container::registry::{Configurer, TypedConfigurer};
+    use crate::container::BindingLifetime;
     use crate::key::{self, KeyTypePattern};
+    use crate::module::{bind, Configuration};
+    use crate::provider::closure::RawClosureProvider;
     use crate::provider::component::{Component, ComponentProvider};
     use crate::provider::instance::InstanceProvider;
-    use crate::scope::SingletonScope;
+    use crate::provider::local::LocalClosureProvider;
+    use crate::provider::{DependencyDescriptor, TypedProvider};
+    use crate::scope::{ActorScope, ScopeContext, SingletonScope, WebScope};
 
     use super::*;
 
@@ -121,6 +394,10 @@ mod tests {
         fn post_process(self) -> Self::Constructed {
             Arc::new(self)
         }
+
+        fn dependencies() -> Vec<DependencyDescriptor> {
+            vec![DependencyDescriptor::of::<Arc<String>>()]
+        }
     }
 
     struct TestModule;
@@ -176,4 +453,601 @@ mod tests {
         assert_eq!(objects.get(&"1"), Some(&1));
         assert_eq!(objects.get(&"2"), Some(&2));
     }
+
+    #[test]
+    fn container_with_local_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::of::<i32>(), InstanceProvider::new(1));
+            configurer.register_local(
+                key::of::<Local<RefCell<Vec<i32>>>>(),
+                LocalClosureProvider::new(|injector| {
+                    Ok(Ok::<_, Infallible>(RefCell::new(vec![
+                        injector.get(key::of::<i32>())?
+                    ])))
+                }),
+                WebScope::Session,
+            );
+            Ok(())
+        });
+        let key = key::of::<Local<RefCell<Vec<i32>>>>();
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        root.with_local(key, |list| list.borrow_mut().push(2))
+            .unwrap();
+        assert!(!root.is_constructed(key));
+
+        let session = root.sub_container().unwrap();
+        session
+            .with_local(key, |list| list.borrow_mut().push(2))
+            .unwrap();
+        assert!(session.is_constructed(key));
+
+        let request = session.sub_container().unwrap();
+        request
+            .with_local(key, |list| list.borrow_mut().push(3))
+            .unwrap();
+        let list = request
+            .with_local(key, |list| list.borrow().clone())
+            .unwrap();
+        assert_eq!(list, vec![1, 2, 3]);
+
+        assert!(matches!(
+            session.get(key),
+            Err(InjectorError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn container_with_local_fails_when_key_is_not_local() {
+        let container = Container::init(TestModule).unwrap();
+
+        let res = container.with_local(key::of::<Local<RefCell<i32>>>(), |_| ());
+        assert!(matches!(res, Err(InjectorError::NotFound { .. })));
+    }
+
+    #[test]
+    fn container_scope_chain_succeeds() {
+        let root = Container::<WebScope>::init(Configuration::new()).unwrap();
+        assert_eq!(root.scope_chain(), vec![WebScope::Singleton]);
+
+        let request = root.sub_container().unwrap().sub_container().unwrap();
+        assert_eq!(
+            request.scope_chain(),
+            vec![WebScope::Singleton, WebScope::Session, WebScope::Request]
+        );
+    }
+
+    #[test]
+    fn container_sub_container_to_succeeds() {
+        let root = Container::<WebScope>::init(Configuration::new()).unwrap();
+
+        let request = root.sub_container_to(WebScope::Request).unwrap();
+        assert_eq!(
+            request.scope_chain(),
+            vec![WebScope::Singleton, WebScope::Session, WebScope::Request]
+        );
+
+        let session = root.sub_container_to(WebScope::Session).unwrap();
+        assert_eq!(session.current_scope(), WebScope::Session);
+    }
+
+    #[test]
+    fn container_sub_container_to_fails_when_target_is_unreachable() {
+        let root = Container::<WebScope>::init(Configuration::new()).unwrap();
+        assert!(root.sub_container_to(WebScope::Singleton).is_none());
+
+        let request = root.sub_container_to(WebScope::Request).unwrap();
+        assert!(request.sub_container_to(WebScope::Session).is_none());
+        assert!(request.sub_container_to(WebScope::Request).is_none());
+    }
+
+    #[test]
+    fn container_scope_listeners_succeeds() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let module = crate::module::from_fn({
+            let events = Arc::clone(&events);
+            move |configurer| {
+                let enter_events = Arc::clone(&events);
+                configurer.on_scope_enter(move |scope| enter_events.lock().push(("enter", scope)));
+                let exit_events = Arc::clone(&events);
+                configurer.on_scope_exit(move |scope| exit_events.lock().push(("exit", scope)));
+                Ok(())
+            }
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+        drop(session);
+        assert_eq!(
+            *events.lock(),
+            vec![("enter", WebScope::Session), ("enter", WebScope::Request)]
+        );
+
+        drop(request);
+        drop(root);
+        assert_eq!(
+            *events.lock(),
+            vec![
+                ("enter", WebScope::Session),
+                ("enter", WebScope::Request),
+                ("exit", WebScope::Request),
+                ("exit", WebScope::Session),
+            ]
+        );
+    }
+
+    #[test]
+    fn container_has_any_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::named::<i32>("name"), InstanceProvider::new(1));
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::init(module).unwrap();
+        assert!(container.has_any::<i32>());
+        assert!(!container.has_any::<i64>());
+    }
+
+    #[test]
+    fn container_get_timed_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<i32>>(),
+                RawClosureProvider::new(|_| {
+                    thread::sleep(Duration::from_millis(20));
+                    Ok(Ok::<_, Infallible>(Arc::new(42)))
+                }),
+                WebScope::Singleton,
+            );
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::init(module).unwrap();
+        let (object, first) = container.get_timed(key::of::<Arc<i32>>()).unwrap();
+        assert_eq!(*object, 42);
+        assert!(first >= Duration::from_millis(20));
+
+        let (_, second) = container.get_timed(key::of::<Arc<i32>>()).unwrap();
+        assert!(second < first);
+    }
+
+    #[test]
+    fn container_get_with_context_succeeds() {
+        #[derive(Debug)]
+        struct RequestIdProvider;
+
+        impl TypedProvider for RequestIdProvider {
+            type Output = u64;
+
+            fn provide<I>(
+                &self,
+                _injector: &I,
+                context: &CallContext<'_>,
+            ) -> Result<Self::Output, InjectorError>
+            where
+                I: TypedInjector + ?Sized,
+            {
+                Ok(context.user_data::<u64>().copied().unwrap_or(0))
+            }
+
+            fn clone_provider(&self) -> Option<Self> {
+                Some(Self)
+            }
+        }
+
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::of::<u64>(), RequestIdProvider);
+            configurer.register(
+                key::of::<String>(),
+                RawClosureProvider::new(|injector| {
+                    let request_id = injector.get(key::of::<u64>())?;
+                    Ok(Ok::<_, Infallible>(format!("request-{request_id}")))
+                }),
+            );
+            Ok(())
+        });
+
+        let container = Container::<SingletonScope>::init(module).unwrap();
+        let request_id = container.get_with_context(key::of::<u64>(), &42u64);
+        assert_eq!(request_id.unwrap(), 42);
+        assert_eq!(container.get(key::of::<u64>()).unwrap(), 0);
+        let request_id = container.get_with_context(key::of::<u64>(), &"42");
+        assert_eq!(request_id.unwrap(), 0);
+
+        let name = container.get_with_context(key::of::<String>(), &7u64);
+        assert_eq!(name.unwrap(), "request-7");
+    }
+
+    #[test]
+    fn container_with_qualifier_override_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::named::<i32>("a"), InstanceProvider::new(1));
+            configurer.register(key::named::<i32>("b"), InstanceProvider::new(2));
+            configurer.register(key::named::<u32>("a"), InstanceProvider::new(3));
+            configurer.register(
+                key::of::<i64>(),
+                RawClosureProvider::new(|injector| {
+                    let value = injector.get(key::named::<i32>("a"))?;
+                    Ok(Ok::<_, Infallible>(value as i64))
+                }),
+            );
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                RawClosureProvider::new(|injector| {
+                    let value = injector.get(key::named::<i32>("a"))?;
+                    Ok(Ok::<_, Infallible>(Arc::new(value as i64)))
+                }),
+                WebScope::Singleton,
+            );
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::init(module).unwrap();
+        let injector = container.with_qualifier_override::<i32>("a", "b");
+        assert_eq!(injector.get(key::named::<i32>("a")).unwrap(), 2);
+        assert_eq!(injector.get(key::named::<u32>("a")).unwrap(), 3);
+        assert_eq!(injector.get(key::of::<i64>()).unwrap(), 2);
+        assert_eq!(*injector.get(key::of::<Arc<i64>>()).unwrap(), 1);
+        assert_eq!(container.get(key::of::<i64>()).unwrap(), 1);
+    }
+
+    #[test]
+    fn container_snapshot_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::named::<i32>("name"), InstanceProvider::new(1));
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                InstanceProvider::new(Arc::new(2i64)),
+                WebScope::Session,
+            );
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::init(module).unwrap();
+        let snapshot = container.snapshot();
+        assert_eq!(snapshot.bindings.len(), 2);
+
+        let binding = &snapshot.bindings[0];
+        assert_eq!(binding.key, any::type_name::<Arc<i64>>());
+        assert_eq!(binding.target_type, any::type_name::<Arc<i64>>());
+        assert_eq!(binding.qualifier_type, "()");
+        assert_eq!(
+            binding.lifetime,
+            BindingLifetime::Shared { scope: "Session" }
+        );
+        assert_eq!(
+            binding.provider_type,
+            any::type_name::<InstanceProvider<Arc<i64>>>()
+        );
+
+        let binding = &snapshot.bindings[1];
+        assert_eq!(binding.key, "i32@\"name\"");
+        assert_eq!(binding.qualifier_type, "&str");
+        assert_eq!(binding.lifetime, BindingLifetime::Transient);
+    }
+
+    #[test]
+    fn container_resolve_path_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register(key::of::<i32>(), InstanceProvider::new(1));
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                InstanceProvider::new(Arc::new(2i64)),
+                WebScope::Session,
+            );
+            configurer.register_shared(
+                key::of::<Arc<u64>>(),
+                InstanceProvider::new(Arc::new(3u64)),
+                WebScope::Request,
+            );
+            configurer.register_shared(
+                key::of::<Arc<TestObject>>(),
+                ComponentProvider::<TestObject>::new(),
+                WebScope::Singleton,
+            );
+            Ok(())
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+        session.get(key::of::<Arc<i64>>()).unwrap();
+
+        let plan = request.resolve_path(&key::of::<Arc<TestObject>>()).unwrap();
+        assert_eq!(plan.scope, WebScope::Singleton);
+        assert_eq!(
+            plan.dependencies,
+            [DependencyDescriptor::of::<Arc<String>>()]
+        );
+
+        let plan = request.resolve_path(&key::of::<Arc<i64>>()).unwrap();
+        assert_eq!(plan.scope, WebScope::Session);
+        assert_eq!(plan.lifetime, BindingLifetime::Shared { scope: "Session" });
+        assert!(plan.cached);
+        assert!(plan.constructed);
+        assert!(plan.dependencies.is_empty());
+
+        let plan = request.resolve_path(&key::of::<i32>()).unwrap();
+        assert_eq!(plan.scope, WebScope::Request);
+        assert_eq!(plan.lifetime, BindingLifetime::Transient);
+        assert!(!plan.cached);
+
+        let plan = session.resolve_path(&key::of::<Arc<u64>>()).unwrap();
+        assert_eq!(plan.scope, WebScope::Session);
+        assert!(!plan.cached);
+        assert!(!plan.constructed);
+    }
+
+    #[test]
+    fn container_resolve_path_fails_when_key_is_unbound() {
+        let root = Container::<WebScope>::init(Configuration::new()).unwrap();
+        let res = root.resolve_path(&key::of::<i32>());
+        assert!(matches!(res, Err(InjectorError::NotFound { .. })));
+    }
+
+    #[test]
+    fn container_actor_scope_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<Mutex<i32>>>(),
+                RawClosureProvider::new(|_| Ok(Ok::<_, Infallible>(Arc::new(Mutex::new(0))))),
+                ActorScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<String>>(),
+                RawClosureProvider::new(|injector| {
+                    let counter: Arc<Mutex<i32>> = injector.get(key::of())?;
+                    let mut counter = counter.lock();
+                    *counter += 1;
+                    Ok(Ok::<_, Infallible>(Arc::new(format!("actor-{counter}"))))
+                }),
+                ActorScope::Actor,
+            );
+            Ok(())
+        });
+
+        let root = Container::<ActorScope>::init(module).unwrap();
+        assert!(root.sub_container().unwrap().sub_container().is_none());
+        let actor1 = root.sub_container().unwrap();
+        let actor2 = root.sub_container().unwrap();
+
+        let name1 = actor1.get(key::of::<Arc<String>>()).unwrap();
+        let name2 = actor2.get(key::of::<Arc<String>>()).unwrap();
+        assert_eq!(*name1, "actor-1");
+        assert_eq!(*name2, "actor-2");
+        assert!(Arc::ptr_eq(
+            &name1,
+            &actor1.get(key::of::<Arc<String>>()).unwrap()
+        ));
+        assert!(root.is_constructed(key::of::<Arc<Mutex<i32>>>()));
+        assert!(!root.is_constructed(key::of::<Arc<String>>()));
+    }
+
+    #[test]
+    fn container_scope_specific_providers_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<&'static str>>(),
+                InstanceProvider::new(Arc::new("session")),
+                WebScope::Session,
+            );
+            configurer.register_shared(
+                key::of::<Arc<&'static str>>(),
+                InstanceProvider::new(Arc::new("request")),
+                WebScope::Request,
+            );
+            Ok(())
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+
+        assert_eq!(*root.get(key::of::<Arc<&str>>()).unwrap(), "session");
+        assert_eq!(*session.get(key::of::<Arc<&str>>()).unwrap(), "session");
+        assert_eq!(*request.get(key::of::<Arc<&str>>()).unwrap(), "request");
+        assert!(!root.is_constructed(key::of::<Arc<&str>>()));
+        assert!(session.is_constructed(key::of::<Arc<&str>>()));
+    }
+
+    #[test]
+    fn container_scope_specific_providers_fallback_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<&'static str>>(),
+                InstanceProvider::new(Arc::new("singleton")),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<&'static str>>(),
+                InstanceProvider::new(Arc::new("request")),
+                WebScope::Request,
+            );
+            Ok(())
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+
+        assert_eq!(*session.get(key::of::<Arc<&str>>()).unwrap(), "singleton");
+        assert_eq!(*request.get(key::of::<Arc<&str>>()).unwrap(), "request");
+        assert!(root.is_constructed(key::of::<Arc<&str>>()));
+    }
+
+    #[test]
+    fn container_inject_scope_context_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            bind::<Arc<String>>()
+                .to_closure(|context: ScopeContext| {
+                    Ok::<_, Infallible>(Arc::new(context.chain().join("/")))
+                })
+                .within(WebScope::Singleton)
+                .set_on(configurer);
+            Ok(())
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+
+        let context = request.get(key::of::<ScopeContext>()).unwrap();
+        assert_eq!(context.current(), "Request");
+        assert_eq!(context.chain(), ["Singleton", "Session", "Request"]);
+        assert_eq!(*request.get(key::of::<Arc<String>>()).unwrap(), "Singleton");
+    }
+
+    #[test]
+    fn container_get_arc_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<i32>>(),
+                InstanceProvider::new(Arc::new(42)),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                InstanceProvider::new(Arc::new(42)),
+                WebScope::Session,
+            );
+            Ok(())
+        });
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let request = session.sub_container().unwrap();
+
+        let object = request.get_arc(key::of::<Arc<i32>>()).unwrap();
+        assert!(root.is_constructed(key::of::<Arc<i32>>()));
+        assert!(Arc::ptr_eq(
+            &object,
+            &request.get_arc(key::of::<Arc<i32>>()).unwrap()
+        ));
+
+        let object = request.get_arc(key::of::<Arc<i64>>()).unwrap();
+        assert!(Arc::ptr_eq(
+            &object,
+            &session.get_arc(key::of::<Arc<i64>>()).unwrap()
+        ));
+        assert!(!root.is_constructed(key::of::<Arc<i64>>()));
+    }
+
+    #[test]
+    fn container_into_injector_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<i32>>(),
+                InstanceProvider::new(Arc::new(42)),
+                SingletonScope,
+            );
+            Ok(())
+        });
+        let container = Container::<SingletonScope>::init(module).unwrap();
+        let object = container.get(key::of::<Arc<i32>>()).unwrap();
+
+        let injector = container.into_injector();
+        assert!(Arc::ptr_eq(
+            &object,
+            &injector.get(key::of::<Arc<i32>>()).unwrap()
+        ));
+        assert_eq!(injector.keys(TypeId::of::<Arc<i32>>()).len(), 1);
+    }
+
+    #[test]
+    fn container_health_check_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<TestObject>>(),
+                ComponentProvider::<TestObject>::new(),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                InstanceProvider::new(Arc::new(1i64)),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<u64>>(),
+                InstanceProvider::new(Arc::new(2u64)),
+                WebScope::Session,
+            );
+            configurer.register(key::of::<i32>(), InstanceProvider::new(3));
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::init(module).unwrap();
+        let report = container.health_check();
+        assert_eq!(report.len(), 2);
+        assert!(matches!(
+            report.get(&key::of::<Arc<TestObject>>() as &dyn Key),
+            Some(Err(InjectorError::NotFound { .. }))
+        ));
+        assert!(matches!(
+            report.get(&key::of::<Arc<i64>>() as &dyn Key),
+            Some(Ok(()))
+        ));
+        assert!(container.is_constructed(key::of::<Arc<i64>>()));
+    }
+
+    #[test]
+    fn container_health_check_succeeds_when_tracking_usage_in_sub_container() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<i64>>(),
+                InstanceProvider::new(Arc::new(1i64)),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<u64>>(),
+                InstanceProvider::new(Arc::new(2u64)),
+                WebScope::Singleton,
+            );
+            Ok(())
+        });
+
+        let container = Container::<WebScope>::builder()
+            .module(module)
+            .track_usage(true)
+            .build()
+            .unwrap();
+        let session = container.sub_container().unwrap();
+        let report = session.health_check();
+        assert_eq!(report.len(), 2);
+        assert!(container.is_constructed(key::of::<Arc<i64>>()));
+        assert_eq!(container.unused_keys().len(), 2);
+
+        session.get(key::of::<Arc<i64>>()).unwrap();
+        let keys = container.unused_keys();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].as_ref(), &key::of::<Arc<u64>>() as &dyn Key);
+    }
+
+    #[test]
+    fn container_clear_scope_cache_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            configurer.register_shared(
+                key::of::<Arc<String>>(),
+                InstanceProvider::new(Arc::new(String::from("test-object"))),
+                WebScope::Singleton,
+            );
+            configurer.register_shared(
+                key::of::<Arc<TestObject>>(),
+                ComponentProvider::<TestObject>::new(),
+                WebScope::Session,
+            );
+            Ok(())
+        });
+
+        let root = Container::<WebScope>::init(module).unwrap();
+        let session = root.sub_container().unwrap();
+        let object: Arc<TestObject> = session.get(key::of()).unwrap();
+        object.set(42);
+
+        assert_eq!(session.clear_scope_cache(), 1);
+        assert!(!session.is_constructed(key::of::<Arc<TestObject>>()));
+        assert!(session.is_constructed(key::of::<Arc<String>>()));
+
+        let object: Arc<TestObject> = session.get(key::of()).unwrap();
+        assert_eq!(object.get(), 0);
+    }
 }
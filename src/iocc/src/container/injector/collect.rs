@@ -1,6 +1,7 @@
 use std::any::{self, TypeId};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
 use std::hash::Hash;
+use std::sync::Arc;
 
 use crate::container::injector::{InjectorError, TypedInjector};
 use crate::container::Managed;
@@ -106,6 +107,68 @@ macro_rules! impl_collect_for_maps {
 impl_collect_for_maps!(HashMap, [TypedQualifier]);
 impl_collect_for_maps!(BTreeMap, [TypedQualifier, Ord]);
 
+/// Collects objects into a [`HashMap`] keyed by their qualifiers, which is
+/// shared behind an [`Arc`], so that many readers can hold the same immutable
+/// map without cloning it.
+impl<T, Q, P> Collect<P> for Arc<HashMap<Q, T>>
+where
+    T: Managed,
+    Q: TypedQualifier,
+    P: Pattern<Target = T, Qualifier = Q>,
+{
+    fn collect<'a, I, KI>(injector: &I, keys: KI, pattern: P) -> Result<Self, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+        KI: Iterator<Item = &'a dyn Key>,
+    {
+        match HashMap::<Q, T>::collect(injector, keys, pattern) {
+            Ok(collection) => Ok(Arc::new(collection)),
+            Err(InjectorError::EmptyCollection { pattern, .. }) => {
+                Err(InjectorError::EmptyCollection {
+                    collection: any::type_name::<Self>(),
+                    pattern,
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Collects the only object matching the pattern, if any. Unlike other
+/// collections, matching nothing isn't an error, while matching more than one
+/// object is.
+impl<T, P> Collect<P> for Option<T>
+where
+    T: Managed,
+    P: Pattern<Target = T>,
+{
+    fn collect<'a, I, KI>(injector: &I, keys: KI, pattern: P) -> Result<Self, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+        KI: Iterator<Item = &'a dyn Key>,
+    {
+        let mut keys = keys
+            .filter(|key| key.target_type() == TypeId::of::<T>())
+            .filter(move |key| pattern.matches(*key));
+
+        let Some(key) = keys.next() else {
+            return Ok(None);
+        };
+        if keys.next().is_some() {
+            return Err(InjectorError::AmbiguousCollection {
+                collection: any::type_name::<Self>(),
+                pattern: any::type_name::<P>(),
+            });
+        }
+
+        injector.dyn_get(key).map(|object| {
+            Some(*object.downcast::<T>().unwrap_or_else(|_| {
+                unreachable!("in impl `Collect<P>` for `Option`, `object` should be `Box<T>`")
+            }))
+        })
+    }
+}
+
 impl<T, P> Collect<P> for HashMap<Box<dyn Qualifier>, T>
 where
     T: Managed,
@@ -141,6 +204,40 @@ where
     }
 }
 
+/// Gets the objects of `keys` matching `pattern` one by one, partitioning
+/// successfully retrieved objects and failures instead of stopping at the
+/// first error.
+#[allow(clippy::type_complexity)]
+pub(super) fn try_collect<T, P, I>(
+    injector: &I,
+    keys: Vec<Box<dyn Key>>,
+    pattern: P,
+) -> (Vec<T>, Vec<(Box<dyn Key>, InjectorError)>)
+where
+    T: Managed,
+    P: Pattern<Target = T>,
+    I: TypedInjector + ?Sized,
+{
+    let mut objects = Vec::new();
+    let mut errors = Vec::new();
+
+    for key in keys {
+        if key.target_type() != TypeId::of::<T>() || !pattern.matches(key.as_ref()) {
+            continue;
+        }
+        match injector.dyn_get(key.as_ref()) {
+            Ok(object) => {
+                objects.push(*object.downcast::<T>().unwrap_or_else(|_| {
+                    unreachable!("in `try_collect`, `object` should be `Box<T>`")
+                }))
+            }
+            Err(err) => errors.push((key, err)),
+        }
+    }
+
+    (objects, errors)
+}
+
 #[cfg(test)]
 mod tests {
     use std::iter;
@@ -167,6 +264,26 @@ mod tests {
         assert!(objects.contains(&42i32));
     }
 
+    #[test]
+    fn vec_collect_filters_qualifier_type_only_with_key_type_pattern() {
+        let injector = make_injector();
+        let keys = make_keys();
+        let keys = keys.iter().map(AsRef::as_ref);
+
+        let pattern = AnyPattern::new();
+        let objects: Vec<i32> = Collect::collect(&injector, keys.clone(), pattern).unwrap();
+        assert_eq!(objects.len(), 3);
+
+        let pattern: KeyTypePattern<_, &'static str> = KeyTypePattern::new();
+        let mut objects: Vec<i32> = Collect::collect(&injector, keys.clone(), pattern).unwrap();
+        objects.sort();
+        assert_eq!(objects, vec![1i32, 2i32]);
+
+        let pattern: KeyTypePattern<_, u32> = KeyTypePattern::new();
+        let res: Result<Vec<i32>, _> = Collect::collect(&injector, keys, pattern);
+        assert!(matches!(res, Err(InjectorError::EmptyCollection { .. })));
+    }
+
     #[test]
     fn hash_map_collect_succeeds() {
         let injector = make_injector();
@@ -185,6 +302,28 @@ mod tests {
         assert_eq!(objects.get("2"), Some(&2i32));
     }
 
+    #[test]
+    fn arc_hash_map_collect_succeeds() {
+        let injector = make_injector();
+        let keys = make_keys();
+        let keys = keys.iter().map(AsRef::as_ref);
+
+        let pattern: KeyTypePattern<_, &'static str> = KeyTypePattern::new();
+        let objects: Arc<HashMap<_, i32>> =
+            Collect::collect(&injector, keys.clone(), pattern).unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects.get("1"), Some(&1i32));
+        assert_eq!(objects.get("2"), Some(&2i32));
+
+        let pattern: KeyTypePattern<_, u32> = KeyTypePattern::new();
+        let res: Result<Arc<HashMap<u32, i32>>, _> = Collect::collect(&injector, keys, pattern);
+        assert!(matches!(
+            res,
+            Err(InjectorError::EmptyCollection { collection, .. })
+                if collection == any::type_name::<Arc<HashMap<u32, i32>>>()
+        ));
+    }
+
     #[test]
     fn vec_collect_fails_when_no_matching_key_exists() {
         let injector = make_injector();
@@ -194,6 +333,35 @@ mod tests {
         assert!(matches!(res, Err(InjectorError::EmptyCollection { .. })));
     }
 
+    #[test]
+    fn option_collect_succeeds() {
+        let injector = make_injector();
+        let keys = make_keys();
+        let keys = keys.iter().map(AsRef::as_ref);
+
+        let pattern: KeyTypePattern<_, ()> = KeyTypePattern::new();
+        let object: Option<i32> = Collect::collect(&injector, keys.clone(), pattern).unwrap();
+        assert_eq!(object, Some(42i32));
+
+        let pattern: KeyTypePattern<_, u32> = KeyTypePattern::new();
+        let object: Option<i32> = Collect::collect(&injector, keys, pattern).unwrap();
+        assert_eq!(object, None);
+    }
+
+    #[test]
+    fn option_collect_fails_when_several_keys_match() {
+        let injector = make_injector();
+        let keys = make_keys();
+        let keys = keys.iter().map(AsRef::as_ref);
+
+        let pattern: KeyTypePattern<_, &'static str> = KeyTypePattern::new();
+        let res: Result<Option<i32>, _> = Collect::collect(&injector, keys, pattern);
+        assert!(matches!(
+            res,
+            Err(InjectorError::AmbiguousCollection { .. })
+        ));
+    }
+
     fn make_keys() -> Vec<Box<dyn Key>> {
         vec![
             Box::new(key::of::<i32>()),
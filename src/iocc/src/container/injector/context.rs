@@ -1,23 +1,68 @@
+use std::any::Any;
+use std::iter;
+
 use crate::key::Key;
 
 #[derive(Clone)]
 pub struct CallContext<'a> {
     trace: InjectionTrace<'a>,
+    scope: Option<&'static str>,
+    user_data: Option<&'a (dyn Any + Sync)>,
 }
 
 impl<'a> CallContext<'a> {
     pub fn new(key: &'a dyn Key) -> Self {
         Self {
             trace: InjectionTrace::new(key),
+            scope: None,
+            user_data: None,
         }
     }
 
     pub fn append<'b>(&'b self, key: &'b dyn Key) -> CallContext<'b> {
         CallContext {
             trace: self.trace.append(key),
+            scope: self.scope,
+            user_data: self.user_data,
+        }
+    }
+
+    /// Returns a copy of `self` whose current scope is set to `scope`.
+    pub fn with_scope(&self, scope: &'static str) -> Self {
+        Self {
+            trace: self.trace.clone(),
+            scope: Some(scope),
+            user_data: self.user_data,
         }
     }
 
+    /// Returns a copy of `self` carrying `user_data`, which is passed on to
+    /// contexts of dependencies.
+    pub fn with_user_data(&self, user_data: &'a (dyn Any + Sync)) -> Self {
+        Self {
+            trace: self.trace.clone(),
+            scope: self.scope,
+            user_data: Some(user_data),
+        }
+    }
+
+    /// Returns the data passed by the caller to
+    /// [`TypedInjector::get_with_context`], or [`None`] if there is no such
+    /// data or it isn't of type `T`.
+    ///
+    /// [`TypedInjector::get_with_context`]: crate::container::injector::TypedInjector::get_with_context
+    pub fn user_data<T: Any>(&self) -> Option<&'a T> {
+        self.user_data
+            .and_then(|user_data| (user_data as &dyn Any).downcast_ref::<T>())
+    }
+
+    /// Returns the name of the scope of the container which is constructing
+    /// the requested object, or [`None`] if the context isn't created by a
+    /// container.
+    pub fn current_scope(&self) -> Option<&'static str> {
+        self.scope
+    }
+
     pub fn key(&self) -> &dyn Key {
         self.trace.key()
     }
@@ -25,12 +70,32 @@ impl<'a> CallContext<'a> {
     pub fn trace(&self) -> &InjectionTrace<'_> {
         &self.trace
     }
+
+    /// Returns the key of the object which depends on the requested object, or
+    /// [`None`] if the requested object is requested directly.
+    pub fn requester(&self) -> Option<&dyn Key> {
+        self.trace.previous().map(|trace| trace.key())
+    }
+
+    /// Returns the number of dependencies between the object initially
+    /// requested and the requested object, which is `0` for the initially
+    /// requested object itself.
+    pub fn depth(&self) -> usize {
+        self.trace.depth()
+    }
+
+    /// Returns keys of the requested object and all objects depending on it,
+    /// from the requested object back to the initially requested one.
+    pub fn chain(&self) -> impl Iterator<Item = &dyn Key> {
+        self.trace.chain()
+    }
 }
 
 #[derive(Clone)]
 pub struct InjectionTrace<'a> {
     key: &'a dyn Key,
     previous: Option<&'a InjectionTrace<'a>>,
+    depth: usize,
 }
 
 impl<'a> InjectionTrace<'a> {
@@ -38,6 +103,7 @@ impl<'a> InjectionTrace<'a> {
         Self {
             key,
             previous: None,
+            depth: 0,
         }
     }
 
@@ -45,6 +111,7 @@ impl<'a> InjectionTrace<'a> {
         InjectionTrace {
             key,
             previous: Some(self),
+            depth: self.depth + 1,
         }
     }
 
@@ -56,6 +123,14 @@ impl<'a> InjectionTrace<'a> {
         self.previous
     }
 
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn chain(&self) -> impl Iterator<Item = &dyn Key> {
+        iter::successors(Some(self), |trace| trace.previous()).map(|trace| trace.key())
+    }
+
     pub fn previous_exist_key(&self, key: &dyn Key) -> bool {
         let mut this = self;
         while let Some(previous) = this.previous() {
@@ -67,3 +142,63 @@ impl<'a> InjectionTrace<'a> {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn call_context_current_scope_succeeds() {
+        let key = key::of::<i32>();
+        let context = CallContext::new(&key);
+        assert_eq!(context.current_scope(), None);
+
+        let context = context.with_scope("Request");
+        assert_eq!(context.current_scope(), Some("Request"));
+
+        let dependency = key::of::<i64>();
+        let context = context.append(&dependency);
+        assert_eq!(context.current_scope(), Some("Request"));
+        assert!(context.trace().previous_exist_key(&key));
+    }
+
+    #[test]
+    fn call_context_user_data_succeeds() {
+        let key = key::of::<i32>();
+        let context = CallContext::new(&key);
+        assert_eq!(context.user_data::<u64>(), None);
+
+        let request_id = 42u64;
+        let context = context.with_user_data(&request_id);
+        assert_eq!(context.user_data::<u64>(), Some(&42));
+        assert_eq!(context.user_data::<u32>(), None);
+
+        let dependency = key::of::<i64>();
+        let context = context.append(&dependency).with_scope("Request");
+        assert_eq!(context.user_data::<u64>(), Some(&42));
+    }
+
+    #[test]
+    fn call_context_depth_succeeds() {
+        let key = key::of::<i32>();
+        let context = CallContext::new(&key);
+        assert_eq!(context.depth(), 0);
+        assert!(context.requester().is_none());
+        assert_eq!(context.chain().count(), 1);
+
+        let dependency = key::of::<i64>();
+        let context = context.append(&dependency);
+        assert_eq!(context.depth(), 1);
+        assert_eq!(context.requester().unwrap().to_string(), "i32");
+
+        let dependency = key::named::<i64>("name");
+        let context = context.with_scope("Request");
+        let context = context.append(&dependency);
+        assert_eq!(context.depth(), 2);
+
+        let chain: Vec<_> = context.chain().map(|key| key.to_string()).collect();
+        assert_eq!(chain, vec!["i64@\"name\"", "i64", "i32"]);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::container::injector::{InjectorError, TypedInjector};
+
+/// A set of dependencies which can be resolved together from an injector.
+///
+/// This trait is usually derived with `#[derive(Inject)]` on a struct, each
+/// of whose fields is resolved like a parameter of a `#[component]`
+/// constructor. Fields support the `#[named]`, `#[qualified]` and
+/// `#[collect]` attributes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// #[derive(Inject)]
+/// struct Deps {
+///     port: u16,
+///     #[named("primary")]
+///     url: Arc<str>,
+/// }
+///
+/// let module = module::from_fn(|configurer| {
+///     bind::<u16>().to_instance(8080).set_on(configurer);
+///     bind::<Arc<str>>()
+///         .qualified_by("primary")
+///         .to_instance(Arc::from("db://primary"))
+///         .set_on(configurer);
+///     Ok(())
+/// });
+///
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+/// let deps: Deps = container.inject().unwrap();
+/// assert_eq!(deps.port, 8080);
+/// assert_eq!(&*deps.url, "db://primary");
+/// ```
+pub trait Inject: Sized {
+    /// Resolves all dependencies from `injector`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the dependencies fails to be retrieved.
+    fn inject_from<I>(injector: &I) -> Result<Self, InjectorError>
+    where
+        I: TypedInjector + ?Sized;
+}
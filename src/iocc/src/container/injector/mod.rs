@@ -1,22 +1,28 @@
 mod collect;
 mod context;
+mod inject;
 mod object_map;
 mod proxy;
+mod report;
 
-use std::any::TypeId;
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
 use std::error::Error;
 use std::sync::Arc;
 
 use snafu::prelude::*;
 
 use crate::container::Managed;
-use crate::key::{Key, Pattern, TypedKey};
+use crate::key::{ErasedPattern, Key, Pattern, TypedKey};
 use crate::util::any::Downcast;
 
 pub use collect::Collect;
 pub use context::CallContext;
-pub(super) use object_map::ObjectMap;
+pub use inject::Inject;
+pub use iocc_derive::Inject;
+pub(super) use object_map::{downgrade_arc, Downgrade, ObjectEntry, ObjectMap};
 pub(crate) use proxy::ContextForwardingInjectorProxy;
+pub use report::{ErrorReport, InjectorErrorKind};
 
 #[cfg_attr(test, mockall::automock)]
 pub trait Injector: Send + Sync {
@@ -28,7 +34,63 @@ pub trait Injector: Send + Sync {
         context: &'a CallContext<'a>,
     ) -> Result<Box<dyn Managed>, InjectorError>;
 
+    /// Gets the object of `key` like [`Injector::dyn_get`], passing
+    /// `user_data` to providers through [`CallContext::user_data`].
+    ///
+    /// The default implementation ignores `user_data` and calls
+    /// [`Injector::dyn_get`].
+    fn dyn_get_with_context(
+        &self,
+        key: &dyn Key,
+        user_data: &(dyn Any + Sync),
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        let _ = user_data;
+        self.dyn_get(key)
+    }
+
+    /// Returns keys of all objects of the given target type.
+    ///
+    /// When several containers contribute the same key, the key may occur
+    /// more than once, and keys from the closest container should come first.
+    /// Collecting methods of [`TypedInjector`] only keep the first occurrence,
+    /// so the closest binding takes precedence over its parents.
     fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>>;
+
+    /// Returns true if there is any object of the given target type,
+    /// regardless of its qualifier.
+    ///
+    /// The default implementation checks whether [`Injector::keys`] is
+    /// non-empty, which implementors are encouraged to override without
+    /// enumerating all keys.
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        !self.keys(type_id).is_empty()
+    }
+
+    /// Passes a reference to the cached shared object of `key` to `visitor`,
+    /// and returns true if such an object exists.
+    ///
+    /// This allows reading cached objects without boxing a copy of them. The
+    /// default implementation returns false without visiting anything, as if
+    /// no object were cached.
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        let _ = (key, visitor);
+        false
+    }
+}
+
+/// A callback receiving a reference to a cached shared object, see
+/// [`Injector::dyn_with_constructed`].
+pub trait ConstructedVisitor {
+    fn visit(&mut self, object: &dyn Any);
+}
+
+impl<F> ConstructedVisitor for F
+where
+    F: FnMut(&dyn Any),
+{
+    fn visit(&mut self, object: &dyn Any) {
+        self(object);
+    }
 }
 
 pub trait TypedInjector: Injector {
@@ -45,18 +107,174 @@ pub trait TypedInjector: Injector {
         }
     }
 
+    /// Gets the object of `key` like [`TypedInjector::get`], passing `ctx` to
+    /// providers, which read it by [`CallContext::user_data`].
+    ///
+    /// This bridges data owned by the caller, e.g. the ID of the current
+    /// request, into construction without binding it. `ctx` is passed on to
+    /// dependencies requested with the [`CallContext`] of their dependents,
+    /// and only takes part in constructing objects, so shared objects already
+    /// cached are returned as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object fails to be retrieved.
+    fn get_with_context<K>(
+        &self,
+        key: K,
+        ctx: &(dyn Any + Sync),
+    ) -> Result<K::Target, InjectorError>
+    where
+        K: TypedKey,
+    {
+        let boxed = self.dyn_get_with_context(&key, ctx)?;
+        let Ok(object) = boxed.downcast::<K::Target>() else {
+            unreachable!("the object's type should be `K::Target`");
+        };
+        Ok(*object)
+    }
+
+    /// Gets the object of `key` like [`TypedInjector::get`], but writes it
+    /// into `slot` rather than returning it.
+    ///
+    /// The object is moved out of its box directly into `slot`, which saves
+    /// a move of large objects compared to [`TypedInjector::get`]. `slot` is
+    /// left untouched if the object fails to be retrieved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object fails to be retrieved.
+    fn resolve_into<K>(&self, key: K, slot: &mut Option<K::Target>) -> Result<(), InjectorError>
+    where
+        K: TypedKey,
+    {
+        let boxed = self.dyn_get(&key)?;
+        let Ok(object) = boxed.downcast::<K::Target>() else {
+            unreachable!("the object's type should be `K::Target`");
+        };
+        *slot = Some(*object);
+        Ok(())
+    }
+
     fn collect<C, P>(&self, pattern: P) -> Result<C, InjectorError>
     where
         C: Collect<P>,
         P: Pattern,
     {
-        let keys = self.keys(TypeId::of::<P::Target>());
+        let keys = dedup_keys(self.keys(TypeId::of::<P::Target>()));
         C::collect(self, keys.iter().map(AsRef::as_ref), pattern)
     }
 
+    /// Gets all objects whose keys match `pattern`, tolerating failures of
+    /// individual objects.
+    ///
+    /// Unlike [`TypedInjector::collect`], which fails entirely if any object
+    /// fails to be retrieved, each matching key is attempted independently.
+    /// Successfully retrieved objects are returned along with the keys that
+    /// failed and their errors. No error is reported if nothing matches.
+    #[allow(clippy::type_complexity)]
+    fn try_collect<T, P>(&self, pattern: P) -> (Vec<T>, Vec<(Box<dyn Key>, InjectorError)>)
+    where
+        T: Managed,
+        P: Pattern<Target = T>,
+    {
+        let keys = dedup_keys(self.keys(TypeId::of::<T>()));
+        collect::try_collect(self, keys, pattern)
+    }
+
+    /// Gets the shared object of `key`, like [`TypedInjector::get`].
+    ///
+    /// If the object is already constructed, the cached [`Arc`] is cloned
+    /// directly rather than through a boxed copy, which saves an allocation
+    /// on the most common path. Otherwise, it falls back to
+    /// [`TypedInjector::get`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object fails to be retrieved.
+    fn get_arc<T, K>(&self, key: K) -> Result<Arc<T>, InjectorError>
+    where
+        T: Send + Sync + ?Sized + 'static,
+        K: TypedKey<Target = Arc<T>>,
+    {
+        let mut object = None;
+        self.dyn_with_constructed(&key, &mut |cached: &dyn Any| {
+            object = cached.downcast_ref::<Arc<T>>().map(Arc::clone);
+        });
+        match object {
+            Some(object) => Ok(object),
+            None => self.get(key),
+        }
+    }
+
+    /// Resolves the dependencies described by `D` at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the dependencies fails to be retrieved.
+    fn inject<D>(&self) -> Result<D, InjectorError>
+    where
+        D: Inject,
+    {
+        D::inject_from(self)
+    }
+
+    /// Gets all objects of target type `T`, each paired with the full key
+    /// identifying it.
+    ///
+    /// Unlike [`TypedInjector::collect`], the qualifier type doesn't need to
+    /// be known statically, and an empty [`Vec`] is returned rather than an
+    /// error if no object of type `T` exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the objects fails to be retrieved.
+    #[allow(clippy::type_complexity)]
+    fn get_all_keyed<T>(&self) -> Result<Vec<(Box<dyn Key>, T)>, InjectorError>
+    where
+        T: Managed,
+    {
+        dedup_keys(self.keys(TypeId::of::<T>()))
+            .into_iter()
+            .map(|key| match self.dyn_get(key.as_ref())?.downcast::<T>() {
+                Ok(object) => Ok((key, *object)),
+                Err(_) => unreachable!("the object's type should be `T`"),
+            })
+            .collect()
+    }
+
+    /// Gets all objects of the given target type whose keys match `pattern`,
+    /// without downcasting them.
+    ///
+    /// This is the type-erased counterpart of [`TypedInjector::collect`], for
+    /// consumers which only know the target type at runtime. An empty [`Vec`]
+    /// is returned rather than an error if no object matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the matched objects fails to be retrieved.
+    fn collect_erased(
+        &self,
+        type_id: TypeId,
+        pattern: &dyn ErasedPattern,
+    ) -> Result<Vec<Box<dyn Managed>>, InjectorError> {
+        dedup_keys(self.keys(type_id))
+            .into_iter()
+            .filter(|key| pattern.dyn_matches(key.as_ref()))
+            .map(|key| self.dyn_get(key.as_ref()))
+            .collect()
+    }
+
     fn upcast_dyn(&self) -> &dyn Injector;
 }
 
+fn dedup_keys(keys: Vec<Box<dyn Key>>) -> Vec<Box<dyn Key>> {
+    let mut seen = HashSet::with_capacity(keys.len());
+    keys.into_iter()
+        .filter(|key| seen.insert(key.dyn_clone()))
+        .collect()
+}
+
 impl<T> TypedInjector for T
 where
     T: Injector,
@@ -84,6 +302,12 @@ pub enum InjectorError {
         collection: &'static str,
         pattern: &'static str,
     },
+    #[snafu(display("found more than one object matching {pattern} to a {collection}"))]
+    #[non_exhaustive]
+    AmbiguousCollection {
+        collection: &'static str,
+        pattern: &'static str,
+    },
     #[snafu(display("could not construct the object {key} which depends on itself somehow"))]
     #[non_exhaustive]
     CyclicDependency { key: Box<dyn Key> },
@@ -93,6 +317,43 @@ pub enum InjectorError {
         key: Box<dyn Key>,
         source: Arc<dyn Error + Send + Sync>,
     },
+    #[snafu(display("could not wait for another thread to construct the object {key} in time"))]
+    #[non_exhaustive]
+    ConstructionTimeout { key: Box<dyn Key> },
+}
+
+impl InjectorError {
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> InjectorErrorKind {
+        match self {
+            Self::NotFound { .. } => InjectorErrorKind::NotFound,
+            Self::EmptyCollection { .. } => InjectorErrorKind::EmptyCollection,
+            Self::AmbiguousCollection { .. } => InjectorErrorKind::AmbiguousCollection,
+            Self::CyclicDependency { .. } => InjectorErrorKind::CyclicDependency,
+            Self::ObjectConstruction { .. } => InjectorErrorKind::ObjectConstruction,
+            Self::ConstructionTimeout { .. } => InjectorErrorKind::ConstructionTimeout,
+        }
+    }
+
+    /// Converts `self` to an [`ErrorReport`].
+    pub fn into_report(self) -> ErrorReport {
+        self.into()
+    }
+
+    /// Returns the source of an [`InjectorError::ObjectConstruction`] if it's
+    /// an error of type `E`, e.g. the [`Component::Error`] of the component
+    /// failing to be constructed.
+    ///
+    /// [`Component::Error`]: crate::provider::component::Component::Error
+    pub fn construction_source<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        match self {
+            Self::ObjectConstruction { source, .. } => source.downcast_ref(),
+            _ => None,
+        }
+    }
 }
 
 impl Clone for InjectorError {
@@ -108,6 +369,13 @@ impl Clone for InjectorError {
                 collection,
                 pattern,
             },
+            Self::AmbiguousCollection {
+                collection,
+                pattern,
+            } => Self::AmbiguousCollection {
+                collection,
+                pattern,
+            },
             Self::CyclicDependency { key } => Self::CyclicDependency {
                 key: key.dyn_clone(),
             },
@@ -115,6 +383,157 @@ impl Clone for InjectorError {
                 key: key.dyn_clone(),
                 source: Arc::clone(source),
             },
+            Self::ConstructionTimeout { key } => Self::ConstructionTimeout {
+                key: key.dyn_clone(),
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::key::{self, AnyPattern, KeyTypePattern};
+
+    use super::*;
+
+    #[test]
+    fn resolve_into_succeeds() {
+        let mut injector = MockInjector::new();
+        injector
+            .expect_dyn_get()
+            .returning(|_| Ok(Box::new([7u8; 1024])));
+
+        let mut slot = None;
+        injector
+            .resolve_into(key::of::<[u8; 1024]>(), &mut slot)
+            .unwrap();
+        assert_eq!(slot, Some([7u8; 1024]));
+    }
+
+    #[test]
+    fn get_all_keyed_succeeds() {
+        let mut injector = MockInjector::new();
+        injector.expect_keys().returning(|type_id| {
+            if type_id == TypeId::of::<i32>() {
+                vec![Box::new(key::of::<i32>()), Box::new(key::named::<i32>("1"))]
+            } else {
+                Vec::new()
+            }
+        });
+        injector.expect_dyn_get().returning(|key| {
+            if key == &key::of::<i32>() as &dyn Key {
+                Ok(Box::new(42i32))
+            } else {
+                Ok(Box::new(1i32))
+            }
+        });
+
+        let mut objects = injector.get_all_keyed::<i32>().unwrap();
+        objects.sort_by_key(|(_, object)| *object);
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].0.as_ref(), &key::named::<i32>("1") as &dyn Key);
+        assert_eq!(objects[0].1, 1);
+        assert_eq!(objects[1].0.as_ref(), &key::of::<i32>() as &dyn Key);
+        assert_eq!(objects[1].1, 42);
+
+        assert!(injector.get_all_keyed::<i64>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn collect_keeps_first_occurrence_of_duplicated_keys() {
+        let mut injector = MockInjector::new();
+        injector.expect_keys().returning(|_| {
+            vec![
+                Box::new(key::named::<i32>("1")),
+                Box::new(key::of::<i32>()),
+                Box::new(key::named::<i32>("1")),
+            ]
+        });
+        injector.expect_dyn_get().times(4).returning(|key| {
+            if key == &key::of::<i32>() as &dyn Key {
+                Ok(Box::new(42i32))
+            } else {
+                Ok(Box::new(1i32))
+            }
+        });
+
+        let mut objects: Vec<i32> = injector.collect(AnyPattern::new()).unwrap();
+        objects.sort();
+        assert_eq!(objects, vec![1, 42]);
+
+        let objects = injector.get_all_keyed::<i32>().unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].0.as_ref(), &key::named::<i32>("1") as &dyn Key);
+    }
+
+    #[test]
+    fn get_all_keyed_fails_when_object_fails_to_be_retrieved() {
+        let mut injector = MockInjector::new();
+        injector
+            .expect_keys()
+            .returning(|_| vec![Box::new(key::of::<i32>())]);
+        injector.expect_dyn_get().returning(|key| {
+            Err(InjectorError::NotFound {
+                key: key.dyn_clone(),
+            })
+        });
+
+        let res = injector.get_all_keyed::<i32>();
+        assert!(matches!(res, Err(InjectorError::NotFound { .. })));
+    }
+
+    #[test]
+    fn try_collect_succeeds() {
+        let mut injector = MockInjector::new();
+        injector.expect_keys().returning(|_| {
+            vec![
+                Box::new(key::of::<i32>()),
+                Box::new(key::named::<i32>("1")),
+                Box::new(key::named::<i32>("2")),
+            ]
+        });
+        injector.expect_dyn_get().returning(|key| {
+            if key == &key::named::<i32>("2") as &dyn Key {
+                Err(InjectorError::NotFound {
+                    key: key.dyn_clone(),
+                })
+            } else {
+                Ok(Box::new(1i32))
+            }
+        });
+
+        let (objects, errors) = injector.try_collect(AnyPattern::<i32>::new());
+        assert_eq!(objects, vec![1, 1]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0.as_ref(), &key::named::<i32>("2") as &dyn Key);
+        assert!(matches!(errors[0].1, InjectorError::NotFound { .. }));
+
+        let (objects, errors) = injector.try_collect(KeyTypePattern::<i32, ()>::new());
+        assert_eq!(objects, vec![1]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn collect_erased_succeeds() {
+        let mut injector = MockInjector::new();
+        injector.expect_keys().returning(|type_id| {
+            if type_id == TypeId::of::<i32>() {
+                vec![Box::new(key::of::<i32>()), Box::new(key::named::<i32>("1"))]
+            } else {
+                Vec::new()
+            }
+        });
+        injector.expect_dyn_get().returning(|_| Ok(Box::new(1i32)));
+
+        let objects = injector
+            .collect_erased(TypeId::of::<i32>(), &KeyTypePattern::<i32, &str>::new())
+            .unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].as_any().downcast_ref::<i32>(), Some(&1));
+
+        let objects = injector
+            .collect_erased(TypeId::of::<i64>(), &AnyPattern::<i64>::new())
+            .unwrap();
+        assert!(objects.is_empty());
+    }
+}
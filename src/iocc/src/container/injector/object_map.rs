@@ -1,32 +1,31 @@
 use std::any::TypeId;
-use std::collections::HashMap;
 use std::mem;
+use std::sync::{Arc, Weak};
 
+use crate::container::injector::ConstructedVisitor;
 use crate::container::{Managed, SharedManaged};
 use crate::key::Key;
+use crate::util::hash::{HashMap, KeyBuildHasher, KeyMap};
 
 pub struct ObjectMap {
     objects: HashMap<TypeId, Slot>,
+    hasher: KeyBuildHasher,
 }
 
 impl ObjectMap {
-    pub fn new() -> Self {
+    pub fn new(hasher: KeyBuildHasher) -> Self {
         Self {
-            objects: HashMap::new(),
+            objects: HashMap::default(),
+            hasher,
         }
     }
 
-    pub fn insert(
-        &mut self,
-        key: Box<dyn Key>,
-        object: Box<dyn SharedManaged>,
-    ) -> Option<ObjectEntry> {
+    pub fn insert(&mut self, key: Box<dyn Key>, entry: ObjectEntry) -> Option<ObjectEntry> {
         let target = key.target_type();
         if let Some(slot) = self.objects.get_mut(&target) {
-            slot.insert(key, ObjectEntry(object))
+            slot.insert(key, entry, &self.hasher)
         } else {
-            self.objects
-                .insert(target, Slot::new(key, ObjectEntry(object)));
+            self.objects.insert(target, Slot::new(key, entry));
             None
         }
     }
@@ -36,11 +35,15 @@ impl ObjectMap {
             .get(&key.target_type())
             .and_then(|slot| slot.get(key))
     }
+
+    pub fn len(&self) -> usize {
+        self.objects.values().map(Slot::len).sum()
+    }
 }
 
 enum Slot {
     Singleton(Box<dyn Key>, ObjectEntry),
-    Map(HashMap<Box<dyn Key>, ObjectEntry>),
+    Map(KeyMap<Box<dyn Key>, ObjectEntry>),
 }
 
 impl Slot {
@@ -48,16 +51,20 @@ impl Slot {
         Self::Singleton(key, entry)
     }
 
-    fn insert(&mut self, key: Box<dyn Key>, entry: ObjectEntry) -> Option<ObjectEntry> {
+    fn insert(
+        &mut self,
+        key: Box<dyn Key>,
+        entry: ObjectEntry,
+        hasher: &KeyBuildHasher,
+    ) -> Option<ObjectEntry> {
         match self {
             Self::Singleton(k, e) if k == &key => {
                 let original = mem::replace(e, entry);
                 Some(original)
             }
             Self::Singleton(_, _) => {
-                let Self::Singleton(k, e) =
-                    mem::replace(self, Self::Map(HashMap::with_capacity(2)))
-                else {
+                let entries = KeyMap::with_capacity_and_hasher(2, hasher.clone());
+                let Self::Singleton(k, e) = mem::replace(self, Self::Map(entries)) else {
                     unreachable!("`self` should match `Self::Singleton(_, _)`")
                 };
                 let Self::Map(entries) = self else {
@@ -78,34 +85,124 @@ impl Slot {
             Self::Map(entries) => entries.get(key),
         }
     }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Singleton(_, _) => 1,
+            Self::Map(entries) => entries.len(),
+        }
+    }
 }
 
-pub struct ObjectEntry(Box<dyn SharedManaged>);
+/// A cached shared object, which is either kept alive by the cache, or only
+/// referenced weakly for weak bindings.
+pub enum ObjectEntry {
+    Strong(Box<dyn SharedManaged>),
+    Weak(Box<dyn WeakShared>),
+}
 
 impl ObjectEntry {
-    pub fn clone_managed(&self) -> Box<dyn Managed> {
-        self.0.dyn_clone().upcast_managed()
+    /// Creates an entry of `object`, which only keeps a weak reference if
+    /// `downgrade` is given and supports the object.
+    pub fn new(object: &dyn SharedManaged, downgrade: Option<Downgrade>) -> Self {
+        match downgrade.and_then(|downgrade| downgrade(object)) {
+            Some(weak) => Self::Weak(weak),
+            None => Self::Strong(object.dyn_clone()),
+        }
     }
+
+    /// Returns a copy of the object, or [`None`] if it's referenced weakly
+    /// and already dropped.
+    pub fn clone_managed(&self) -> Option<Box<dyn Managed>> {
+        match self {
+            Self::Strong(object) => Some((**object).dyn_clone().upcast_managed()),
+            Self::Weak(weak) => weak.upgrade().map(|object| object.upcast_managed()),
+        }
+    }
+
+    /// Passes a reference to the object to `visitor`, and returns false
+    /// without visiting anything if it's already dropped.
+    pub fn visit(&self, visitor: &mut dyn ConstructedVisitor) -> bool {
+        match self {
+            Self::Strong(object) => {
+                visitor.visit((**object).as_any());
+                true
+            }
+            Self::Weak(weak) => match weak.upgrade() {
+                Some(object) => {
+                    visitor.visit((*object).as_any());
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// A weak reference to a shared object, see [`ObjectEntry::Weak`].
+pub trait WeakShared: Send + Sync {
+    fn upgrade(&self) -> Option<Box<dyn SharedManaged>>;
+}
+
+impl<T> WeakShared for Weak<T>
+where
+    T: Send + Sync + ?Sized + 'static,
+{
+    fn upgrade(&self) -> Option<Box<dyn SharedManaged>> {
+        Weak::upgrade(self).map(|object| Box::new(object) as _)
+    }
+}
+
+/// Makes a weak reference to a shared object, or returns [`None`] if the
+/// object isn't of the expected type.
+pub type Downgrade = fn(&dyn SharedManaged) -> Option<Box<dyn WeakShared>>;
+
+/// The [`Downgrade`] of objects of type `Arc<T>`.
+pub fn downgrade_arc<T>(object: &dyn SharedManaged) -> Option<Box<dyn WeakShared>>
+where
+    T: Send + Sync + ?Sized + 'static,
+{
+    let object = object.as_any().downcast_ref::<Arc<T>>()?;
+    Some(Box::new(Arc::downgrade(object)))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::any::Any;
 
     use crate::key;
-    use crate::util::any::DowncastRef;
+    use crate::util::any::Downcast;
 
     use super::*;
 
     #[test]
     fn object_map_insert_succeeds() {
-        let mut map = ObjectMap::new();
+        let mut map = ObjectMap::new(KeyBuildHasher::default());
+
+        let entry = ObjectEntry::new(&Arc::new(42i32), None);
+        assert!(map.insert(Box::new(key::of::<Arc<i32>>()), entry).is_none());
 
-        assert!(map
-            .insert(Box::new(key::of::<Arc<i32>>()), Box::new(Arc::new(42i32)))
-            .is_none());
+        let obj = map.get(&key::of::<Arc<i32>>()).unwrap().clone_managed();
+        let Ok(obj) = obj.unwrap().downcast::<Arc<i32>>() else {
+            panic!("the object's type should be `Arc<i32>`");
+        };
+        assert_eq!(**obj, 42);
+    }
 
-        let obj = &map.get(&key::of::<Arc<i32>>()).unwrap().0;
-        assert_eq!(**obj.downcast_ref::<Arc<i32>>().unwrap(), 42);
+    #[test]
+    fn object_entry_weak_succeeds() {
+        let object = Arc::new(42i32);
+        let entry = ObjectEntry::new(&object, Some(downgrade_arc::<i32>));
+        assert!(matches!(entry, ObjectEntry::Weak(_)));
+
+        let Ok(cloned) = entry.clone_managed().unwrap().downcast::<Arc<i32>>() else {
+            panic!("the object's type should be `Arc<i32>`");
+        };
+        assert!(Arc::ptr_eq(&object, &cloned));
+
+        drop(object);
+        drop(cloned);
+        assert!(entry.clone_managed().is_none());
+        assert!(!entry.visit(&mut |_: &dyn Any| {}));
     }
 }
@@ -1,6 +1,8 @@
 use std::any::TypeId;
 
-use crate::container::injector::{CallContext, Injector, InjectorError, TypedInjector};
+use crate::container::injector::{
+    CallContext, ConstructedVisitor, Injector, InjectorError, TypedInjector,
+};
 use crate::container::Managed;
 use crate::key::Key;
 
@@ -40,4 +42,12 @@ where
     fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
         self.inner.keys(type_id)
     }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.inner.contains_target(type_id)
+    }
+
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        self.inner.dyn_with_constructed(key, visitor)
+    }
 }
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use crate::container::injector::InjectorError;
+use crate::key::Key;
+
+/// The kind of an [`InjectorError`], without any payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InjectorErrorKind {
+    NotFound,
+    EmptyCollection,
+    AmbiguousCollection,
+    CyclicDependency,
+    ObjectConstruction,
+    ConstructionTimeout,
+}
+
+impl Display for InjectorErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotFound => write!(f, "NotFound"),
+            Self::EmptyCollection => write!(f, "EmptyCollection"),
+            Self::AmbiguousCollection => write!(f, "AmbiguousCollection"),
+            Self::CyclicDependency => write!(f, "CyclicDependency"),
+            Self::ObjectConstruction => write!(f, "ObjectConstruction"),
+            Self::ConstructionTimeout => write!(f, "ConstructionTimeout"),
+        }
+    }
+}
+
+/// A flattened representation of an [`InjectorError`].
+///
+/// Unlike [`InjectorError`], whose variants may grow in the future, an
+/// [`ErrorReport`] has a stable shape, which makes it suitable for mapping
+/// into application-specific errors while preserving the structured key.
+#[derive(Debug)]
+pub struct ErrorReport {
+    /// The kind of the original error.
+    pub kind: InjectorErrorKind,
+    /// The key of the object failing to be retrieved, if any.
+    pub key: Option<Box<dyn Key>>,
+    /// The underlying error causing the failure, if any.
+    pub source: Option<Arc<dyn Error + Send + Sync>>,
+    message: String,
+}
+
+impl Clone for ErrorReport {
+    fn clone(&self) -> Self {
+        Self {
+            kind: self.kind,
+            key: self.key.as_ref().map(|key| key.dyn_clone()),
+            source: self.source.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ErrorReport {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| -> &(dyn Error + 'static) { source.as_ref() })
+    }
+}
+
+impl From<InjectorError> for ErrorReport {
+    fn from(err: InjectorError) -> Self {
+        let kind = err.kind();
+        let message = err.to_string();
+        let (key, source) = match err {
+            InjectorError::NotFound { key } => (Some(key), None),
+            InjectorError::EmptyCollection { .. } => (None, None),
+            InjectorError::AmbiguousCollection { .. } => (None, None),
+            InjectorError::CyclicDependency { key } => (Some(key), None),
+            InjectorError::ObjectConstruction { key, source } => (Some(key), Some(source)),
+            InjectorError::ConstructionTimeout { key } => (Some(key), None),
+        };
+
+        Self {
+            kind,
+            key,
+            source,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn into_report_succeeds() {
+        let err = InjectorError::NotFound {
+            key: Box::new(key::of::<i32>()),
+        };
+        let message = err.to_string();
+        let report = err.into_report();
+        assert_eq!(report.kind, InjectorErrorKind::NotFound);
+        assert_eq!(report.key.as_deref(), Some(&key::of::<i32>() as &dyn Key));
+        assert!(report.source.is_none());
+        assert_eq!(report.to_string(), message);
+
+        let err = InjectorError::ObjectConstruction {
+            key: Box::new(key::named::<i32>("name")),
+            source: Arc::from(Box::<dyn Error + Send + Sync>::from("whatever")),
+        };
+        let report = ErrorReport::from(err);
+        assert_eq!(report.kind, InjectorErrorKind::ObjectConstruction);
+        assert_eq!(
+            report.key.as_deref(),
+            Some(&key::named::<i32>("name") as &dyn Key)
+        );
+        assert_eq!(report.source().unwrap().to_string(), "whatever");
+
+        let err = InjectorError::EmptyCollection {
+            collection: "Vec<i32>",
+            pattern: "AnyPattern<i32>",
+        };
+        let report = err.into_report();
+        assert_eq!(report.kind, InjectorErrorKind::EmptyCollection);
+        assert!(report.key.is_none());
+    }
+}
@@ -1,20 +1,43 @@
 pub mod injector;
 pub mod registry;
 
+mod builder;
+mod catalog;
 mod core;
 mod handle;
+mod plan;
+mod scoped;
+mod snapshot;
+mod stats;
 
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 use crate::util::any::AsAny;
 
+pub use crate::util::any::{Downcast, DowncastMut, DowncastRef};
+pub use builder::ContainerBuilder;
+pub use catalog::Catalog;
 pub use handle::Container;
+pub use plan::ResolutionPlan;
+pub use scoped::ScopedInjector;
+pub use snapshot::{BindingLifetime, BindingSnapshot, ContainerSnapshot};
+pub use stats::KeyStats;
 
 pub trait Managed: AsAny + Send + Sync + 'static {}
 
 impl<T> Managed for T where T: AsAny + Send + Sync + 'static {}
 
+/// An object which can be shared by all its users, such as an [`Arc`].
+///
+/// Containers cache one copy of each shared object, and hand out copies
+/// obtained from [`SharedManaged::dyn_clone`], so implementations must
+/// preserve identity: a copy refers to the same underlying object as the
+/// original rather than a duplicate of it. Otherwise, mutation through
+/// interior mutability would be invisible to other users, which silently
+/// breaks the sharing semantics.
 pub trait SharedManaged: Managed {
+    /// Returns a handle to the same underlying object as `self`.
     fn dyn_clone(&self) -> Box<dyn SharedManaged>;
 
     fn upcast_managed(self: Box<Self>) -> Box<dyn Managed>;
@@ -32,3 +55,58 @@ where
         self
     }
 }
+
+impl<T> SharedManaged for &'static T
+where
+    T: Sync + ?Sized + 'static,
+{
+    fn dyn_clone(&self) -> Box<dyn SharedManaged> {
+        Box::new(*self)
+    }
+
+    fn upcast_managed(self: Box<Self>) -> Box<dyn Managed> {
+        self
+    }
+}
+
+/// An object which can be sent to another thread but not shared between
+/// threads, such as one holding a [`Cell`] or a [`RefCell`].
+///
+/// [`Cell`]: std::cell::Cell
+/// [`RefCell`]: std::cell::RefCell
+pub trait LocalManaged: AsAny + Send + 'static {}
+
+impl<T> LocalManaged for T where T: AsAny + Send + 'static {}
+
+/// A marker type used as the target of keys identifying local objects of type
+/// `T`.
+///
+/// A local object is constructed at most once in the container whose scope
+/// matches its own, and is owned by that container instead of being cloned
+/// on each request. Since `T` may not be [`Sync`], local objects can only be
+/// accessed by one thread at a time through [`Container::with_local`].
+pub struct Local<T: LocalManaged> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    #[test]
+    fn shared_managed_dyn_clone_preserves_identity_succeeds() {
+        fn assert_identity<T: Send + Sync + ?Sized + 'static>(object: Arc<T>) {
+            let shared: Box<dyn SharedManaged> = Box::new(Arc::clone(&object));
+            let cloned = shared.dyn_clone();
+            let Ok(cloned) = cloned.upcast_managed().downcast::<Arc<T>>() else {
+                panic!("the object's type should be `Arc<T>`");
+            };
+            assert!(Arc::ptr_eq(&object, &cloned));
+        }
+
+        assert_identity(Arc::new(42));
+        assert_identity::<dyn Debug + Send + Sync>(Arc::new("str"));
+    }
+}
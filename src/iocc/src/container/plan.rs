@@ -0,0 +1,26 @@
+use crate::container::snapshot::BindingLifetime;
+use crate::provider::DependencyDescriptor;
+use crate::scope::Scope;
+
+/// An explanation of how a container would resolve a key, obtained without
+/// constructing anything.
+///
+/// Use [`Container::resolve_path`] to create a [`ResolutionPlan`].
+///
+/// [`Container::resolve_path`]: crate::container::Container::resolve_path
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResolutionPlan<S: Scope> {
+    /// The scope of the container which would construct the object.
+    pub scope: S,
+    /// The lifetime declared by the binding.
+    pub lifetime: BindingLifetime,
+    /// Whether the object is cached by the container constructing it, rather
+    /// than constructed on each request.
+    pub cached: bool,
+    /// Whether the object has already been constructed and cached.
+    pub constructed: bool,
+    /// The dependencies reported by the provider of the binding, which is
+    /// empty if they are unknown.
+    pub dependencies: Vec<DependencyDescriptor>,
+}
@@ -1,14 +1,27 @@
+use std::any::TypeId;
+use std::collections::HashSet;
 use std::error::Error;
+use std::mem;
 
+use crate::container::injector::Downgrade;
+use crate::container::registry::contribution::Contribution;
 use crate::container::registry::provider_map::ProviderMap;
-use crate::container::registry::{Configurer, ConfigurerPrivate, RegistryError};
+use crate::container::registry::{
+    Configurer, ConfigurerPrivate, DuplicatePolicy, EmptyErrorListError, RegistryError,
+    ScopeListener, StartupHook,
+};
 use crate::key::Key;
-use crate::provider::{Provider, SharedProvider};
-use crate::scope::Scope;
+use crate::provider::{LocalProvider, Provider, SharedProvider};
+use crate::scope::{Scope, ScopeContext};
+use crate::util::hash::KeyBuildHasher;
 
 pub struct ConfigurerImpl<S: Scope> {
     providers: ProviderMap<S>,
     errors: Vec<RegistryError>,
+    contributions: Vec<(TypeId, Box<dyn Contribution<S>>)>,
+    startup_hooks: Vec<StartupHook<S>>,
+    duplicate_policy: DuplicatePolicy,
+    disabled_groups: HashSet<String>,
 }
 
 impl<S: Scope> ConfigurerImpl<S> {
@@ -16,18 +29,77 @@ impl<S: Scope> ConfigurerImpl<S> {
         Self {
             providers: ProviderMap::new(),
             errors: Vec::new(),
+            contributions: Vec::new(),
+            startup_hooks: Vec::new(),
+            duplicate_policy: DuplicatePolicy::default(),
+            disabled_groups: HashSet::new(),
         }
     }
 
-    pub fn finish(mut self) -> Result<ProviderMap<S>, RegistryError> {
-        if self.errors.len() > 1 {
-            Err(RegistryError::Aggregated {
-                errors: self.errors,
-            })
-        } else if let Some(error) = self.errors.pop() {
-            Err(error)
+    /// Sets how bindings registered afterwards are handled if their keys are
+    /// already bound.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Enables or disables the binding group `group` for modules configured
+    /// afterwards. All groups are enabled by default.
+    pub fn set_group_enabled(&mut self, group: impl Into<String>, enabled: bool) {
+        let group = group.into();
+        if enabled {
+            self.disabled_groups.remove(&group);
         } else {
-            Ok(self.providers)
+            self.disabled_groups.insert(group);
+        }
+    }
+
+    /// Sets the hasher of maps keyed by [`Key`]s, which also applies to
+    /// bindings already registered.
+    pub fn set_key_hasher(&mut self, hasher: KeyBuildHasher) {
+        self.providers.set_hasher(hasher);
+    }
+
+    /// Takes the startup hooks in registration order, which aren't part of
+    /// the bindings returned by [`ConfigurerImpl::finish`].
+    pub fn take_startup_hooks(&mut self) -> Vec<StartupHook<S>> {
+        mem::take(&mut self.startup_hooks)
+    }
+
+    /// Returns true if a binding of `key` within `scope` should be inserted,
+    /// resolving any clash with existing bindings by the duplicate policy.
+    fn accept(&mut self, key: &dyn Key, scope: Option<S>) -> bool {
+        if ScopeContext::is_key(key) {
+            self.errors.push(RegistryError::ReservedKey {
+                key: key.dyn_clone(),
+            });
+            return false;
+        }
+        if !self.providers.conflicts(key, scope) {
+            return true;
+        }
+        match self.duplicate_policy {
+            DuplicatePolicy::Error => {
+                self.errors.push(RegistryError::KeyDuplicated {
+                    key: key.dyn_clone(),
+                });
+                false
+            }
+            DuplicatePolicy::FirstWins => false,
+            DuplicatePolicy::LastWins => {
+                self.providers.remove_conflicts(key, scope);
+                true
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> Result<ProviderMap<S>, RegistryError> {
+        for (_, contribution) in mem::take(&mut self.contributions) {
+            contribution.finish(&mut self);
+        }
+
+        match RegistryError::try_from(self.errors) {
+            Ok(error) => Err(error),
+            Err(EmptyErrorListError) => Ok(self.providers),
         }
     }
 }
@@ -40,6 +112,11 @@ impl<S: Scope> Configurer for ConfigurerImpl<S> {
         self
     }
 
+    #[allow(private_interfaces)]
+    fn as_private_ref(&self) -> &dyn ConfigurerPrivate<Scope = Self::Scope> {
+        self
+    }
+
     fn report_module_error(&mut self, module: &'static str, err: Box<dyn Error + Send + Sync>) {
         self.errors.push(RegistryError::ModuleInner {
             module,
@@ -49,13 +126,13 @@ impl<S: Scope> Configurer for ConfigurerImpl<S> {
 }
 
 impl<S: Scope> ConfigurerPrivate for ConfigurerImpl<S> {
+    fn upcast_configurer(&mut self) -> &mut dyn Configurer<Scope = Self::Scope> {
+        self
+    }
+
     fn dyn_register(&mut self, key: Box<dyn Key>, provider: Box<dyn Provider>) {
-        if self.providers.get(key.as_ref()).is_none() {
+        if self.accept(key.as_ref(), None) {
             self.providers.insert(key, provider);
-        } else {
-            self.errors.push(RegistryError::KeyDuplicated {
-                key: key.dyn_clone(),
-            });
         }
     }
 
@@ -65,14 +142,72 @@ impl<S: Scope> ConfigurerPrivate for ConfigurerImpl<S> {
         provider: Box<dyn SharedProvider>,
         scope: S,
     ) {
-        if self.providers.get(key.as_ref()).is_none() {
+        if self.accept(key.as_ref(), Some(scope)) {
             self.providers.insert_shared(key, provider, scope);
-        } else {
-            self.errors.push(RegistryError::KeyDuplicated {
-                key: key.dyn_clone(),
-            });
         }
     }
+
+    fn dyn_register_weak(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn SharedProvider>,
+        scope: S,
+        downgrade: Downgrade,
+    ) {
+        if self.accept(key.as_ref(), Some(scope)) {
+            self.providers.insert_weak(key, provider, scope, downgrade);
+        }
+    }
+
+    fn dyn_on_scope_enter(&mut self, listener: ScopeListener<S>) {
+        self.providers.add_scope_enter_listener(listener);
+    }
+
+    fn dyn_on_scope_exit(&mut self, listener: ScopeListener<S>) {
+        self.providers.add_scope_exit_listener(listener);
+    }
+
+    fn dyn_on_startup(&mut self, hook: StartupHook<S>) {
+        self.startup_hooks.push(hook);
+    }
+
+    fn dyn_report_error(&mut self, err: RegistryError) {
+        self.errors.push(err);
+    }
+
+    fn dyn_is_registered(&self, key: &dyn Key) -> bool {
+        self.providers.get(key).is_some()
+    }
+
+    fn dyn_is_group_enabled(&self, group: &str) -> bool {
+        !self.disabled_groups.contains(group)
+    }
+
+    fn dyn_register_local(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn LocalProvider>,
+        scope: S,
+    ) {
+        if self.accept(key.as_ref(), Some(scope)) {
+            self.providers.insert_local(key, provider, scope);
+        }
+    }
+
+    fn dyn_contribution(
+        &mut self,
+        type_id: TypeId,
+        init: fn() -> Box<dyn Contribution<S>>,
+    ) -> &mut dyn Contribution<S> {
+        let index = match self.contributions.iter().position(|(id, _)| *id == type_id) {
+            Some(index) => index,
+            None => {
+                self.contributions.push((type_id, init()));
+                self.contributions.len() - 1
+            }
+        };
+        self.contributions[index].1.as_mut()
+    }
 }
 
 #[cfg(test)]
@@ -80,7 +215,10 @@ mod tests {
     use std::{fmt::Debug, sync::Arc};
 
     use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+    use crate::container::registry::provider_map::ProviderEntry;
+    use crate::container::registry::{Contributable, TypedConfigurer};
     use crate::key;
+    use crate::module::Module;
     use crate::provider::{TypedProvider, TypedSharedProvider};
     use crate::scope::SingletonScope;
 
@@ -120,6 +258,18 @@ mod tests {
         assert!(matches!(errs, RegistryError::KeyDuplicated { .. }));
     }
 
+    #[test]
+    fn configurer_impl_finish_fails_when_key_is_reserved() {
+        let mut configurer: ConfigurerImpl<SingletonScope> = ConfigurerImpl::new();
+        configurer.dyn_register(
+            Box::new(key::of::<ScopeContext>()),
+            Box::new(TestProvider::new(ScopeContext::new(&[SingletonScope]))),
+        );
+
+        let errs = configurer.finish().unwrap_err();
+        assert!(matches!(errs, RegistryError::ReservedKey { .. }));
+    }
+
     #[test]
     fn configurer_impl_finish_fails_when_other_error_reported() {
         let mut configurer = ConfigurerImpl::new();
@@ -138,6 +288,125 @@ mod tests {
         assert!(matches!(errs, RegistryError::ModuleInner { .. }));
     }
 
+    #[test]
+    fn configurer_impl_install_succeeds() {
+        let mut configurer = ConfigurerImpl::new();
+        configurer.install(OuterModule {
+            install_failing: false,
+        });
+
+        let map = configurer.finish().unwrap();
+        assert!(map.get(&key::of::<i32>()).is_some());
+        assert!(map.get(&key::of::<Arc<&str>>()).is_some());
+    }
+
+    #[test]
+    fn configurer_impl_install_fails_with_installed_module_name() {
+        let mut configurer = ConfigurerImpl::new();
+        configurer.install(OuterModule {
+            install_failing: true,
+        });
+
+        let errs = configurer.finish().unwrap_err();
+        assert!(matches!(
+            errs,
+            RegistryError::ModuleInner { module, .. }
+                if module == std::any::type_name::<FailingModule>()
+        ));
+    }
+
+    #[test]
+    fn configurer_impl_is_registered_succeeds() {
+        let mut configurer = ConfigurerImpl::new();
+        configurer.dyn_register_shared(
+            Box::new(key::of::<Arc<&'static str>>()),
+            Box::new(TestProvider::new(Arc::new("str"))),
+            SingletonScope,
+        );
+
+        assert!(configurer.is_registered(&key::of::<Arc<&str>>()));
+        assert!(!configurer.is_registered(&key::of::<i32>()));
+        assert!(!configurer.is_registered(&key::named::<Arc<&str>>("name")));
+    }
+
+    #[test]
+    fn configurer_impl_contribute_succeeds() {
+        let mut configurer: ConfigurerImpl<SingletonScope> = ConfigurerImpl::new();
+        configurer.contribute(|builder: &mut TestBuilder| builder.parts.push("first"));
+        configurer.contribute(|builder: &mut TestBuilder| builder.parts.push("second"));
+
+        let map = configurer.finish().unwrap();
+        let entry = map.get(&key::of::<Arc<Vec<&str>>>()).unwrap();
+        assert!(matches!(
+            entry,
+            ProviderEntry::Shared { scope, .. } if *scope == SingletonScope
+        ));
+    }
+
+    #[derive(Default)]
+    struct TestBuilder {
+        parts: Vec<&'static str>,
+    }
+
+    impl Contributable for TestBuilder {
+        type Output = Arc<Vec<&'static str>>;
+
+        fn build(self) -> Self::Output {
+            Arc::new(self.parts)
+        }
+    }
+
+    struct OuterModule {
+        install_failing: bool,
+    }
+
+    impl Module for OuterModule {
+        type Scope = SingletonScope;
+
+        fn configure(
+            &self,
+            configurer: &mut dyn Configurer<Scope = Self::Scope>,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            configurer.register(key::of::<i32>(), TestProvider::new(42i32));
+            configurer.install(InnerModule);
+            if self.install_failing {
+                configurer.install(FailingModule);
+            }
+            Ok(())
+        }
+    }
+
+    struct InnerModule;
+
+    impl Module for InnerModule {
+        type Scope = SingletonScope;
+
+        fn configure(
+            &self,
+            configurer: &mut dyn Configurer<Scope = Self::Scope>,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            configurer.register_shared(
+                key::of::<Arc<&'static str>>(),
+                TestProvider::new(Arc::new("str")),
+                SingletonScope,
+            );
+            Ok(())
+        }
+    }
+
+    struct FailingModule;
+
+    impl Module for FailingModule {
+        type Scope = SingletonScope;
+
+        fn configure(
+            &self,
+            _configurer: &mut dyn Configurer<Scope = Self::Scope>,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            Err("whatever".into())
+        }
+    }
+
     #[derive(Debug)]
     struct TestProvider<T>
     where
@@ -0,0 +1,91 @@
+use std::any::Any;
+
+use crate::container::registry::ConfigurerPrivate;
+use crate::container::SharedManaged;
+use crate::key;
+use crate::provider::instance::InstanceProvider;
+use crate::scope::Scope;
+use crate::util::any::AsAny;
+
+/// A builder of a singleton object assembled incrementally by multiple
+/// modules.
+///
+/// Modules add their parts to the builder with
+/// [`TypedConfigurer::contribute`], which creates the builder with
+/// [`Default`] on first use. Once all modules are configured, the builder is
+/// built and the result is registered as a singleton with an unqualified key
+/// of type [`Contributable::Output`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use iocc::prelude::*;
+/// # use iocc::container::registry::{Contributable, TypedConfigurer};
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// #[derive(Default)]
+/// struct RoutesBuilder {
+///     routes: Vec<&'static str>,
+/// }
+///
+/// impl Contributable for RoutesBuilder {
+///     type Output = Arc<Vec<&'static str>>;
+///
+///     fn build(self) -> Self::Output {
+///         Arc::new(self.routes)
+///     }
+/// }
+///
+/// let users = module::from_fn(|configurer| {
+///     configurer.contribute(|builder: &mut RoutesBuilder| builder.routes.push("/users"));
+///     Ok(())
+/// });
+/// let posts = module::from_fn(|configurer| {
+///     configurer.contribute(|builder: &mut RoutesBuilder| builder.routes.push("/posts"));
+///     Ok(())
+/// });
+///
+/// let configuration = Configuration::new().with(users).with(posts);
+/// let container = Container::<SingletonScope>::init(configuration).unwrap();
+/// let routes = container.get(key::of::<Arc<Vec<&str>>>()).unwrap();
+/// assert_eq!(*routes, ["/users", "/posts"]);
+/// ```
+///
+/// [`TypedConfigurer::contribute`]: crate::container::registry::TypedConfigurer::contribute
+pub trait Contributable: Default + Send + Sync + 'static {
+    /// The type of the built object.
+    type Output: SharedManaged + Clone;
+
+    /// Builds the object from all contributions.
+    fn build(self) -> Self::Output;
+}
+
+pub(super) trait Contribution<S: Scope>: AsAny + Send + Sync {
+    fn finish(self: Box<Self>, configurer: &mut dyn ConfigurerPrivate<Scope = S>);
+}
+
+impl<B, S> Contribution<S> for B
+where
+    B: Contributable,
+    S: Scope,
+{
+    fn finish(self: Box<Self>, configurer: &mut dyn ConfigurerPrivate<Scope = S>) {
+        configurer.dyn_register_shared(
+            Box::new(key::of::<B::Output>()),
+            Box::new(InstanceProvider::new(self.build())),
+            S::SINGLETON,
+        );
+    }
+}
+
+pub(super) fn downcast_builder<B, S>(contribution: &mut dyn Contribution<S>) -> &mut B
+where
+    B: Contributable,
+    S: Scope,
+{
+    let builder: &mut dyn Any = contribution.as_any_mut();
+    builder
+        .downcast_mut()
+        .unwrap_or_else(|| unreachable!("contributions should be indexed by their types"))
+}
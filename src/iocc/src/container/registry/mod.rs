@@ -1,18 +1,26 @@
 mod configurer;
+mod contribution;
 mod provider_map;
 
+use std::any::TypeId;
 use std::error::Error;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
 
 use snafu::prelude::*;
 
-use crate::container::SharedManaged;
+use crate::container::injector::{downgrade_arc, Downgrade, InjectorError};
+use crate::container::registry::contribution::Contribution;
+use crate::container::{Container, Local, LocalManaged, SharedManaged};
 use crate::key::{Key, TypedKey};
 use crate::module::Module;
-use crate::provider::{Provider, SharedProvider, TypedProvider, TypedSharedProvider};
+use crate::provider::{
+    LocalProvider, Provider, SharedProvider, TypedLocalProvider, TypedProvider, TypedSharedProvider,
+};
 use crate::scope::Scope;
 
 pub(super) use configurer::ConfigurerImpl;
+pub use contribution::Contributable;
 pub(super) use provider_map::{ProviderEntry, ProviderMap};
 
 pub trait Registry: Sized + Send + Sync + 'static {
@@ -30,10 +38,40 @@ pub trait Configurer: Send + Sync + 'static {
     #[allow(private_interfaces)]
     fn as_private(&mut self) -> &mut dyn ConfigurerPrivate<Scope = Self::Scope>;
 
+    #[doc(hidden)]
+    #[allow(private_interfaces)]
+    fn as_private_ref(&self) -> &dyn ConfigurerPrivate<Scope = Self::Scope>;
+
     fn report_module_error(&mut self, module: &'static str, err: Box<dyn Error + Send + Sync>);
+
+    /// Returns true if `key` has already been bound within any scope by the
+    /// modules configured so far, which allows registering objects
+    /// conditionally.
+    fn is_registered(&self, key: &dyn Key) -> bool {
+        self.as_private_ref().dyn_is_registered(key)
+    }
+
+    /// Returns true if the binding group `group` is enabled, which is the
+    /// default unless the container is built with it disabled. See
+    /// [`GroupedModule`].
+    ///
+    /// [`GroupedModule`]: crate::module::GroupedModule
+    fn is_group_enabled(&self, group: &str) -> bool {
+        self.as_private_ref().dyn_is_group_enabled(group)
+    }
 }
 
+/// A callback receiving the scope of a sub-container when it's created or
+/// dropped.
+pub type ScopeListener<S> = Box<dyn Fn(S) + Send + Sync>;
+
+/// A callback run once against a root container right after it's built.
+pub type StartupHook<S> =
+    Box<dyn FnOnce(&Container<S>) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync>;
+
 trait ConfigurerPrivate: Configurer {
+    fn upcast_configurer(&mut self) -> &mut dyn Configurer<Scope = Self::Scope>;
+
     fn dyn_register(&mut self, key: Box<dyn Key>, provider: Box<dyn Provider>);
 
     fn dyn_register_shared(
@@ -42,9 +80,53 @@ trait ConfigurerPrivate: Configurer {
         provider: Box<dyn SharedProvider>,
         scope: Self::Scope,
     );
+
+    fn dyn_register_weak(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn SharedProvider>,
+        scope: Self::Scope,
+        downgrade: Downgrade,
+    );
+
+    fn dyn_register_local(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn LocalProvider>,
+        scope: Self::Scope,
+    );
+
+    fn dyn_on_scope_enter(&mut self, listener: ScopeListener<Self::Scope>);
+
+    fn dyn_on_scope_exit(&mut self, listener: ScopeListener<Self::Scope>);
+
+    fn dyn_on_startup(&mut self, hook: StartupHook<Self::Scope>);
+
+    fn dyn_report_error(&mut self, err: RegistryError);
+
+    fn dyn_is_registered(&self, key: &dyn Key) -> bool;
+
+    fn dyn_is_group_enabled(&self, group: &str) -> bool;
+
+    fn dyn_contribution(
+        &mut self,
+        type_id: TypeId,
+        init: fn() -> Box<dyn Contribution<Self::Scope>>,
+    ) -> &mut dyn Contribution<Self::Scope>;
 }
 
 pub trait TypedConfigurer: Configurer {
+    /// Installs `module` by running its [`Module::setup`] against this
+    /// configurer, so that a module can include other modules from its
+    /// [`Module::configure`]. Errors of `module` are reported under its own
+    /// name.
+    fn install<M>(&mut self, module: M)
+    where
+        M: Module<Scope = Self::Scope>,
+    {
+        module.setup(self.as_private().upcast_configurer());
+    }
+
     fn register<K, P>(&mut self, key: K, provider: P)
     where
         K: TypedKey,
@@ -62,26 +144,173 @@ pub trait TypedConfigurer: Configurer {
         self.as_private()
             .dyn_register_shared(Box::new(key), Box::new(provider), scope);
     }
+
+    /// Registers a shared binding like [`TypedConfigurer::register_shared`],
+    /// except that containers only keep weak references to the objects.
+    ///
+    /// A cached object is handed out as long as it's still referenced
+    /// elsewhere. Once the last strong reference outside the container is
+    /// dropped, the object is dropped as well, and the next request
+    /// constructs a new one within the same scope. Upgrading a weak
+    /// reference either succeeds or fails atomically, so a request racing
+    /// with the drop either gets the old object or constructs a new one,
+    /// and concurrent requests for a dropped object wait for a single
+    /// construction like they do for a shared object never constructed.
+    fn register_weak<K, P, T>(&mut self, key: K, provider: P, scope: Self::Scope)
+    where
+        K: TypedKey<Target = Arc<T>>,
+        P: TypedSharedProvider<Output = Arc<T>>,
+        T: Send + Sync + ?Sized + 'static,
+    {
+        self.as_private().dyn_register_weak(
+            Box::new(key),
+            Box::new(provider),
+            scope,
+            downgrade_arc::<T>,
+        );
+    }
+
+    fn register_local<K, P, T>(&mut self, key: K, provider: P, scope: Self::Scope)
+    where
+        K: TypedKey<Target = Local<T>>,
+        P: TypedLocalProvider<Output = T>,
+        T: LocalManaged,
+    {
+        self.as_private()
+            .dyn_register_local(Box::new(key), Box::new(provider), scope);
+    }
+
+    /// Registers a callback invoked with the scope of each sub-container right
+    /// after it's created. Root containers never trigger the callback.
+    fn on_scope_enter<F>(&mut self, f: F)
+    where
+        F: Fn(Self::Scope) + Send + Sync + 'static,
+    {
+        self.as_private().dyn_on_scope_enter(Box::new(f));
+    }
+
+    /// Registers a callback invoked with the scope of each sub-container when
+    /// it's dropped, i.e. when all handles to it and its own sub-containers
+    /// are gone. Root containers never trigger the callback.
+    fn on_scope_exit<F>(&mut self, f: F)
+    where
+        F: Fn(Self::Scope) + Send + Sync + 'static,
+    {
+        self.as_private().dyn_on_scope_exit(Box::new(f));
+    }
+
+    /// Registers a hook run once against the root container after it's built
+    /// by [`ContainerBuilder::build`] or [`Registry::init`], e.g. to warm a
+    /// cache.
+    ///
+    /// Hooks run in registration order after eager construction, if enabled.
+    /// The first failing hook aborts building the container with
+    /// [`RegistryError::Startup`], and the remaining hooks are skipped.
+    ///
+    /// [`ContainerBuilder::build`]: crate::container::ContainerBuilder::build
+    fn on_startup<F>(&mut self, f: F)
+    where
+        F: FnOnce(&Container<Self::Scope>) -> Result<(), Box<dyn Error + Send + Sync>>,
+        F: Send + Sync + 'static,
+    {
+        self.as_private().dyn_on_startup(Box::new(f));
+    }
+
+    /// Contributes to the builder of type `B` shared by all modules, which is
+    /// created with [`Default`] on first use. After all modules are
+    /// configured, the builder is built and registered as a singleton. See
+    /// [`Contributable`] for details.
+    fn contribute<B, F>(&mut self, f: F)
+    where
+        B: Contributable,
+        F: FnOnce(&mut B),
+    {
+        let contribution = self
+            .as_private()
+            .dyn_contribution(TypeId::of::<B>(), || Box::new(B::default()));
+        f(contribution::downcast_builder(contribution));
+    }
 }
 
 impl<T: Configurer + ?Sized> TypedConfigurer for T {}
 
+/// Reports `err` detected by a binding, which makes the configuration fail.
+pub(crate) fn report_error<C>(configurer: &mut C, err: RegistryError)
+where
+    C: Configurer + ?Sized,
+{
+    configurer.as_private().dyn_report_error(err);
+}
+
+/// The way a configurer handles a binding whose key is already bound, e.g.
+/// registered twice in the same scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DuplicatePolicy {
+    /// Reports [`RegistryError::KeyDuplicated`], which is the default.
+    #[default]
+    Error,
+    /// Keeps the binding registered first and ignores the later ones.
+    FirstWins,
+    /// Replaces the existing binding with the one registered last.
+    LastWins,
+}
+
 #[derive(Debug, Snafu)]
 #[non_exhaustive]
 pub enum RegistryError {
     #[snafu(display("the key {key} already exists in the registry"))]
     #[non_exhaustive]
     KeyDuplicated { key: Box<dyn Key> },
+    #[snafu(display("the key {key} is reserved by containers and can't be bound"))]
+    #[non_exhaustive]
+    ReservedKey { key: Box<dyn Key> },
+    #[snafu(display("the object {key} depends on itself"))]
+    #[non_exhaustive]
+    SelfDependency { key: Box<dyn Key> },
     #[snafu(display("module {module} fails to setup the configuration"))]
     #[non_exhaustive]
     ModuleInner {
         module: &'static str,
         source: Box<dyn Error + Send + Sync>,
     },
+    #[snafu(display("fails to eagerly construct the singleton objects"))]
+    #[non_exhaustive]
+    EagerConstruction { source: InjectorError },
+    #[snafu(display("a startup hook fails"))]
+    #[non_exhaustive]
+    Startup {
+        source: Box<dyn Error + Send + Sync>,
+    },
     #[snafu(display("aggregated registry errors:\n{}", AggregatedDisplayer::new(errors)))]
     Aggregated { errors: Vec<RegistryError> },
 }
 
+impl TryFrom<Vec<RegistryError>> for RegistryError {
+    type Error = EmptyErrorListError;
+
+    /// Normalizes a list of errors into a single error. A list with a single
+    /// error is unwrapped into that error, while any longer list is wrapped
+    /// into [`RegistryError::Aggregated`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EmptyErrorListError`] if the list is empty, since it
+    /// doesn't describe any error.
+    fn try_from(mut errors: Vec<RegistryError>) -> Result<Self, Self::Error> {
+        match errors.len() {
+            0 => Err(EmptyErrorListError),
+            1 => Ok(errors.pop().unwrap()),
+            _ => Ok(Self::Aggregated { errors }),
+        }
+    }
+}
+
+/// The error returned when converting an empty list into a [`RegistryError`].
+#[derive(Debug, Snafu)]
+#[snafu(display("the list of registry errors is empty"))]
+pub struct EmptyErrorListError;
+
 struct AggregatedDisplayer<'a> {
     errors: &'a [RegistryError],
 }
@@ -100,3 +329,41 @@ impl Display for AggregatedDisplayer<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn registry_error_try_from_vec_succeeds() {
+        let error = RegistryError::try_from(vec![RegistryError::KeyDuplicated {
+            key: Box::new(key::of::<i32>()),
+        }])
+        .unwrap();
+        assert!(matches!(error, RegistryError::KeyDuplicated { .. }));
+
+        let error = RegistryError::try_from(vec![
+            RegistryError::KeyDuplicated {
+                key: Box::new(key::of::<i32>()),
+            },
+            RegistryError::KeyDuplicated {
+                key: Box::new(key::of::<i64>()),
+            },
+        ])
+        .unwrap();
+        assert!(matches!(
+            error,
+            RegistryError::Aggregated { errors } if errors.len() == 2
+        ));
+    }
+
+    #[test]
+    fn registry_error_try_from_vec_fails_when_empty() {
+        assert!(matches!(
+            RegistryError::try_from(Vec::new()),
+            Err(EmptyErrorListError)
+        ));
+    }
+}
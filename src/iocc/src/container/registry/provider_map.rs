@@ -1,23 +1,47 @@
 use std::any::TypeId;
-use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
 use std::mem;
+use std::sync::Arc;
 
+use crate::container::injector::Downgrade;
+use crate::container::registry::ScopeListener;
 use crate::key::Key;
-use crate::provider::{Provider, SharedProvider};
-use crate::scope::Scope;
+use crate::provider::{DependencyDescriptor, LocalProvider, Provider, SharedProvider};
+use crate::scope::{Lifetime, Scope};
+use crate::util::hash::{HashMap, KeyBuildHasher, KeyMap};
 
 #[derive(Debug)]
 pub struct ProviderMap<S: Scope> {
     providers: HashMap<TypeId, ProviderSlot<S>>,
+    listeners: ScopeListeners<S>,
+    hasher: KeyBuildHasher,
 }
 
 impl<S: Scope> ProviderMap<S> {
     pub fn new() -> Self {
         Self {
-            providers: HashMap::new(),
+            providers: HashMap::default(),
+            listeners: ScopeListeners::new(),
+            hasher: KeyBuildHasher::default(),
         }
     }
 
+    /// Returns the hasher of maps keyed by [`Key`]s, which containers using
+    /// this map are expected to use as well.
+    pub fn hasher(&self) -> &KeyBuildHasher {
+        &self.hasher
+    }
+
+    /// Replaces the hasher of maps keyed by [`Key`]s, rehashing all existing
+    /// entries.
+    pub fn set_hasher(&mut self, hasher: KeyBuildHasher) {
+        for slot in self.providers.values_mut() {
+            slot.rehash(&hasher);
+        }
+        self.hasher = hasher;
+    }
+
     pub fn insert(
         &mut self,
         key: Box<dyn Key>,
@@ -35,16 +59,71 @@ impl<S: Scope> ProviderMap<S> {
         self.insert_impl(ProviderEntry::new_shared(key, provider, scope))
     }
 
+    /// Inserts a shared entry whose objects are cached by weak references
+    /// made by `downgrade`.
+    pub fn insert_weak(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn SharedProvider>,
+        scope: S,
+        downgrade: Downgrade,
+    ) -> Option<ProviderEntry<S>> {
+        self.insert_impl(ProviderEntry::new_weak(key, provider, scope, downgrade))
+    }
+
+    pub fn insert_local(
+        &mut self,
+        key: Box<dyn Key>,
+        provider: Box<dyn LocalProvider>,
+        scope: S,
+    ) -> Option<ProviderEntry<S>> {
+        self.insert_impl(ProviderEntry::new_local(key, provider, scope))
+    }
+
+    /// Gets the entry of `key` as seen from the root container, i.e. like
+    /// [`ProviderMap::get_in`] with [`Scope::SINGLETON`].
     pub fn get(&self, key: &dyn Key) -> Option<&ProviderEntry<S>> {
+        self.get_in(key, S::SINGLETON)
+    }
+
+    /// Gets the entry of `key` for a container of `scope`.
+    ///
+    /// A key may be bound within several scopes with different providers. The
+    /// entry bound within `scope` itself is preferred. Otherwise, the entry of
+    /// the nearest enclosing scope is used, and if no enclosing scope has one
+    /// either, the entry of the nearest narrower scope is used, which keeps
+    /// the behavior of keys bound within a single scope.
+    pub fn get_in(&self, key: &dyn Key, scope: S) -> Option<&ProviderEntry<S>> {
         self.providers
             .get(&key.target_type())
-            .and_then(|slot| slot.get(key))
+            .and_then(|slot| slot.get(key, scope))
+    }
+
+    /// Returns true if an entry of `key` within `scope` would clash with an
+    /// existing one. Entries without a scope clash with any entry of the same
+    /// key, while scoped entries only clash within the same scope.
+    pub fn conflicts(&self, key: &dyn Key, scope: Option<S>) -> bool {
+        self.providers
+            .get(&key.target_type())
+            .is_some_and(|slot| slot.conflicts(key, scope))
+    }
+
+    /// Removes all entries which an entry of `key` within `scope` would clash
+    /// with, see [`ProviderMap::conflicts`].
+    pub fn remove_conflicts(&mut self, key: &dyn Key, scope: Option<S>) {
+        let target = key.target_type();
+        let Some(slot) = self.providers.get_mut(&target) else {
+            return;
+        };
+        if slot.remove_conflicts(key, scope) {
+            self.providers.remove(&target);
+        }
     }
 
     fn insert_impl(&mut self, provider: ProviderEntry<S>) -> Option<ProviderEntry<S>> {
         let target = provider.dyn_key().target_type();
         if let Some(slot) = self.providers.get_mut(&target) {
-            slot.insert(provider)
+            slot.insert(provider, &self.hasher)
         } else {
             self.providers.insert(target, provider.into());
             None
@@ -56,43 +135,198 @@ impl<S: Scope> ProviderMap<S> {
             .get(&type_id)
             .map_or(Vec::new(), |slot| slot.keys())
     }
+
+    pub fn contains_target(&self, type_id: TypeId) -> bool {
+        self.providers.contains_key(&type_id)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ProviderEntry<S>> {
+        self.providers.values().flat_map(|slot| slot.entries())
+    }
+
+    pub fn add_scope_enter_listener(&mut self, listener: ScopeListener<S>) {
+        self.listeners.on_enter.push(Arc::from(listener));
+    }
+
+    pub fn add_scope_exit_listener(&mut self, listener: ScopeListener<S>) {
+        self.listeners.on_exit.push(Arc::from(listener));
+    }
+
+    pub fn notify_scope_enter(&self, scope: S) {
+        self.listeners.on_enter.iter().for_each(|f| f(scope));
+    }
+
+    pub fn notify_scope_exit(&self, scope: S) {
+        self.listeners.on_exit.iter().for_each(|f| f(scope));
+    }
+
+    /// Returns a deep copy of all entries, or [`None`] if any provider can't
+    /// be cloned. Scope listeners are shared with the copy.
+    pub fn try_clone(&self) -> Option<Self> {
+        let providers = self
+            .providers
+            .iter()
+            .map(|(type_id, slot)| Some((*type_id, slot.try_clone(&self.hasher)?)))
+            .collect::<Option<_>>()?;
+        Some(Self {
+            providers,
+            listeners: self.listeners.clone(),
+            hasher: self.hasher.clone(),
+        })
+    }
+}
+
+#[derive(Clone)]
+struct ScopeListeners<S: Scope> {
+    on_enter: Vec<Arc<dyn Fn(S) + Send + Sync>>,
+    on_exit: Vec<Arc<dyn Fn(S) + Send + Sync>>,
+}
+
+impl<S: Scope> ScopeListeners<S> {
+    fn new() -> Self {
+        Self {
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+}
+
+impl<S: Scope> Debug for ScopeListeners<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ScopeListeners<S>").finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
 enum ProviderSlot<S: Scope> {
     Singleton(ProviderEntry<S>),
-    Map(HashMap<Box<dyn Key>, ProviderEntry<S>>),
+    Map(KeyMap<Box<dyn Key>, Vec<ProviderEntry<S>>>),
 }
 
 impl<S: Scope> ProviderSlot<S> {
-    fn insert(&mut self, provider: ProviderEntry<S>) -> Option<ProviderEntry<S>> {
+    fn insert(
+        &mut self,
+        provider: ProviderEntry<S>,
+        hasher: &KeyBuildHasher,
+    ) -> Option<ProviderEntry<S>> {
         match self {
-            Self::Singleton(entry) if entry.dyn_key() == provider.dyn_key() => {
+            Self::Singleton(entry) if entry.conflicts(provider.dyn_key(), provider.scope()) => {
                 let original = mem::replace(entry, provider);
                 Some(original)
             }
             Self::Singleton(_) => {
-                let Self::Singleton(entry) =
-                    mem::replace(self, Self::Map(HashMap::with_capacity(2)))
-                else {
+                let entries = KeyMap::with_capacity_and_hasher(2, hasher.clone());
+                let Self::Singleton(entry) = mem::replace(self, Self::Map(entries)) else {
                     unreachable!("`self` should match `Self::Singleton(_)``")
                 };
-                let Self::Map(entries) = self else {
-                    unreachable!("`self` should already be assigned to `Self::Map(_)``")
-                };
-                entries.insert(entry.dyn_key().dyn_clone(), entry);
-                entries.insert(provider.dyn_key().dyn_clone(), provider);
-                None
+                self.insert(entry, hasher);
+                self.insert(provider, hasher)
+            }
+            Self::Map(entries) => {
+                let scoped = entries.entry(provider.dyn_key().dyn_clone()).or_default();
+                let conflicting = scoped
+                    .iter_mut()
+                    .find(|entry| entry.conflicts(provider.dyn_key(), provider.scope()));
+                if let Some(entry) = conflicting {
+                    Some(mem::replace(entry, provider))
+                } else {
+                    scoped.push(provider);
+                    None
+                }
+            }
+        }
+    }
+
+    fn try_clone(&self, hasher: &KeyBuildHasher) -> Option<Self> {
+        match self {
+            Self::Singleton(entry) => entry.try_clone().map(Self::Singleton),
+            Self::Map(entries) => {
+                let mut cloned = KeyMap::with_capacity_and_hasher(entries.len(), hasher.clone());
+                for (key, scoped) in entries {
+                    let scoped = scoped
+                        .iter()
+                        .map(ProviderEntry::try_clone)
+                        .collect::<Option<_>>()?;
+                    cloned.insert(key.dyn_clone(), scoped);
+                }
+                Some(Self::Map(cloned))
             }
-            Self::Map(entries) => entries.insert(provider.dyn_key().dyn_clone(), provider),
         }
     }
 
-    fn get(&self, key: &dyn Key) -> Option<&ProviderEntry<S>> {
+    fn rehash(&mut self, hasher: &KeyBuildHasher) {
+        if let Self::Map(entries) = self {
+            let mut rehashed = KeyMap::with_capacity_and_hasher(entries.len(), hasher.clone());
+            rehashed.extend(entries.drain());
+            *entries = rehashed;
+        }
+    }
+
+    fn get(&self, key: &dyn Key, scope: S) -> Option<&ProviderEntry<S>> {
         match self {
             Self::Singleton(entry) if entry.dyn_key() != key => None,
             Self::Singleton(entry) => Some(entry),
-            Self::Map(entries) => entries.get(key),
+            Self::Map(entries) => entries
+                .get(key)
+                .and_then(|scoped| Self::select(scoped, scope)),
+        }
+    }
+
+    fn select(scoped: &[ProviderEntry<S>], scope: S) -> Option<&ProviderEntry<S>> {
+        if let [entry] = scoped {
+            return Some(entry);
+        }
+
+        // Entries without a scope never share their key with other entries,
+        // so all entries here are bound within distinct scopes.
+        let find = |scope| scoped.iter().find(|entry| entry.scope() == Some(scope));
+        let mut enclosing = Some(scope);
+        while let Some(current) = enclosing {
+            if let Some(entry) = find(current) {
+                return Some(entry);
+            }
+            enclosing = current.super_scope();
+        }
+        let mut narrower = VecDeque::from(scope.sub_scopes());
+        while let Some(current) = narrower.pop_front() {
+            if let Some(entry) = find(current) {
+                return Some(entry);
+            }
+            narrower.extend(current.sub_scopes());
+        }
+        None
+    }
+
+    fn conflicts(&self, key: &dyn Key, scope: Option<S>) -> bool {
+        match self {
+            Self::Singleton(entry) => entry.conflicts(key, scope),
+            Self::Map(entries) => entries
+                .get(key)
+                .is_some_and(|scoped| scoped.iter().any(|entry| entry.conflicts(key, scope))),
+        }
+    }
+
+    /// Removes the conflicting entries and returns true if the slot becomes
+    /// empty.
+    fn remove_conflicts(&mut self, key: &dyn Key, scope: Option<S>) -> bool {
+        match self {
+            Self::Singleton(entry) => entry.conflicts(key, scope),
+            Self::Map(entries) => {
+                if let Some(scoped) = entries.get_mut(key) {
+                    scoped.retain(|entry| !entry.conflicts(key, scope));
+                    if scoped.is_empty() {
+                        entries.remove(key);
+                    }
+                }
+                entries.is_empty()
+            }
+        }
+    }
+
+    fn entries(&self) -> Vec<&ProviderEntry<S>> {
+        match self {
+            Self::Singleton(entry) => vec![entry],
+            Self::Map(entries) => entries.values().flatten().collect(),
         }
     }
 
@@ -116,11 +350,19 @@ pub enum ProviderEntry<S: Scope> {
         key: Box<dyn Key>,
         provider: Box<dyn SharedProvider>,
         scope: S,
+        /// Makes the weak references to cache objects by, or [`None`] if
+        /// objects are cached by strong references.
+        weak: Option<Downgrade>,
     },
     Owned {
         key: Box<dyn Key>,
         provider: Box<dyn Provider>,
     },
+    Local {
+        key: Box<dyn Key>,
+        provider: Box<dyn LocalProvider>,
+        scope: S,
+    },
 }
 
 impl<S: Scope> ProviderEntry<S> {
@@ -129,6 +371,21 @@ impl<S: Scope> ProviderEntry<S> {
             key,
             provider,
             scope,
+            weak: None,
+        }
+    }
+
+    pub fn new_weak(
+        key: Box<dyn Key>,
+        provider: Box<dyn SharedProvider>,
+        scope: S,
+        downgrade: Downgrade,
+    ) -> Self {
+        Self::Shared {
+            key,
+            provider,
+            scope,
+            weak: Some(downgrade),
         }
     }
 
@@ -136,23 +393,104 @@ impl<S: Scope> ProviderEntry<S> {
         Self::Owned { key, provider }
     }
 
+    pub fn new_local(key: Box<dyn Key>, provider: Box<dyn LocalProvider>, scope: S) -> Self {
+        Self::Local {
+            key,
+            provider,
+            scope,
+        }
+    }
+
     pub fn dyn_key(&self) -> &dyn Key {
         match self {
             Self::Shared { key, .. } => key.as_ref(),
             Self::Owned { key, .. } => key.as_ref(),
+            Self::Local { key, .. } => key.as_ref(),
         }
     }
+
+    /// Returns the lifetime of objects supplied by the entry, which is scoped
+    /// for both shared and local objects.
+    pub fn lifetime(&self) -> Lifetime<S> {
+        match self {
+            Self::Shared { scope, .. } | Self::Local { scope, .. } => Lifetime::scoped(*scope),
+            Self::Owned { .. } => Lifetime::transient(),
+        }
+    }
+
+    /// Returns the scope the entry is bound within, or [`None`] for transient
+    /// objects.
+    pub fn scope(&self) -> Option<S> {
+        match self.lifetime() {
+            Lifetime::Scoped(scope) => Some(scope),
+            Lifetime::Transient(_) => None,
+        }
+    }
+
+    /// Returns the dependencies reported by the provider, which is empty for
+    /// local objects since [`LocalProvider`]s don't report them.
+    pub fn dependencies(&self) -> Vec<DependencyDescriptor> {
+        match self {
+            Self::Shared { provider, .. } => provider.upcast_provider().dyn_dependencies(),
+            Self::Owned { provider, .. } => provider.dyn_dependencies(),
+            Self::Local { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns a copy of the entry, or [`None`] if its provider can't be
+    /// cloned.
+    pub fn try_clone(&self) -> Option<Self> {
+        match self {
+            Self::Shared {
+                key,
+                provider,
+                scope,
+                weak,
+            } => Some(Self::Shared {
+                key: key.dyn_clone(),
+                provider: provider.clone_shared_box()?,
+                scope: *scope,
+                weak: *weak,
+            }),
+            Self::Owned { key, provider } => Some(Self::Owned {
+                key: key.dyn_clone(),
+                provider: provider.clone_box()?,
+            }),
+            Self::Local {
+                key,
+                provider,
+                scope,
+            } => Some(Self::Local {
+                key: key.dyn_clone(),
+                provider: provider.clone_local_box()?,
+                scope: *scope,
+            }),
+        }
+    }
+
+    fn conflicts(&self, key: &dyn Key, scope: Option<S>) -> bool {
+        self.dyn_key() == key
+            && match (self.scope(), scope) {
+                (Some(this), Some(other)) => this == other,
+                _ => true,
+            }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::convert::Infallible;
     use std::fmt::Debug;
     use std::sync::Arc;
 
     use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+    use crate::container::Local;
     use crate::key;
+    use crate::provider::instance::InstanceProvider;
+    use crate::provider::local::LocalClosureProvider;
     use crate::provider::{TypedProvider, TypedSharedProvider};
-    use crate::scope::SingletonScope;
+    use crate::scope::{SingletonScope, WebScope};
 
     use super::*;
 
@@ -212,6 +550,109 @@ mod tests {
         assert_eq!(provider.dyn_key(), &key::of::<Arc<i32>>() as &dyn Key);
     }
 
+    #[test]
+    fn type_slot_registry_get_in_succeeds_when_key_is_bound_within_several_scopes() {
+        let mut registry = ProviderMap::new();
+
+        let key = key::of::<Arc<i32>>();
+        let provider = Box::new(TestProvider::new(Arc::new(1i32)));
+        assert!(registry
+            .insert_shared(Box::new(key), provider, WebScope::Session)
+            .is_none());
+        let provider = Box::new(TestProvider::new(Arc::new(2i32)));
+        assert!(registry
+            .insert_shared(Box::new(key), provider, WebScope::Request)
+            .is_none());
+
+        assert!(registry.conflicts(&key, Some(WebScope::Session)));
+        assert!(!registry.conflicts(&key, Some(WebScope::Singleton)));
+        assert!(registry.conflicts(&key, None));
+        assert_eq!(registry.keys(TypeId::of::<Arc<i32>>()).len(), 1);
+        assert_eq!(registry.entries().count(), 2);
+
+        let scope_of = |scope| registry.get_in(&key, scope).unwrap().scope();
+        assert_eq!(scope_of(WebScope::Singleton), Some(WebScope::Session));
+        assert_eq!(scope_of(WebScope::Session), Some(WebScope::Session));
+        assert_eq!(scope_of(WebScope::Request), Some(WebScope::Request));
+
+        let provider = Box::new(TestProvider::new(Arc::new(3i32)));
+        assert!(registry
+            .insert_shared(Box::new(key), provider, WebScope::Request)
+            .is_some());
+    }
+
+    #[test]
+    fn provider_entry_lifetime_succeeds() {
+        let entry: ProviderEntry<WebScope> = ProviderEntry::new_owned(
+            Box::new(key::of::<i32>()),
+            Box::new(TestProvider::new(42i32)),
+        );
+        assert_eq!(entry.lifetime(), Lifetime::transient());
+        assert_eq!(entry.scope(), None);
+
+        let entry = ProviderEntry::new_shared(
+            Box::new(key::of::<Arc<i32>>()),
+            Box::new(TestProvider::new(Arc::new(42i32))),
+            WebScope::Session,
+        );
+        assert_eq!(entry.lifetime(), Lifetime::scoped(WebScope::Session));
+        assert_eq!(entry.scope(), Some(WebScope::Session));
+
+        let entry = ProviderEntry::new_local(
+            Box::new(key::of::<Local<Cell<i32>>>()),
+            Box::new(LocalClosureProvider::new(|_| {
+                Ok(Ok::<_, Infallible>(Cell::new(42)))
+            })),
+            WebScope::Request,
+        );
+        assert_eq!(entry.lifetime(), Lifetime::scoped(WebScope::Request));
+        assert_eq!(entry.scope(), Some(WebScope::Request));
+    }
+
+    #[test]
+    fn provider_map_try_clone_succeeds() {
+        let mut registry = ProviderMap::new();
+        registry.insert(
+            Box::new(key::of::<i32>()),
+            Box::new(InstanceProvider::new(1i32)),
+        );
+        registry.insert_shared(
+            Box::new(key::of::<Arc<i32>>()),
+            Box::new(InstanceProvider::new(Arc::new(2i32))),
+            WebScope::Session,
+        );
+        registry.insert_shared(
+            Box::new(key::of::<Arc<i32>>()),
+            Box::new(InstanceProvider::new(Arc::new(3i32))),
+            WebScope::Request,
+        );
+        registry.insert_local(
+            Box::new(key::of::<Local<Cell<i32>>>()),
+            Box::new(LocalClosureProvider::new(|_| {
+                Ok(Ok::<_, Infallible>(Cell::new(4)))
+            })),
+            WebScope::Request,
+        );
+
+        let cloned = registry.try_clone().unwrap();
+        assert_eq!(cloned.entries().count(), 4);
+        let scope_of = |scope| {
+            cloned
+                .get_in(&key::of::<Arc<i32>>(), scope)
+                .unwrap()
+                .scope()
+        };
+        assert_eq!(scope_of(WebScope::Session), Some(WebScope::Session));
+        assert_eq!(scope_of(WebScope::Request), Some(WebScope::Request));
+        assert!(cloned.get(&key::of::<Local<Cell<i32>>>()).is_some());
+
+        registry.insert(
+            Box::new(key::of::<i64>()),
+            Box::new(TestProvider::new(5i64)),
+        );
+        assert!(registry.try_clone().is_none());
+    }
+
     #[derive(Debug)]
     struct TestProvider<T>
     where
@@ -0,0 +1,131 @@
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use crate::container::core::ContainerCore;
+use crate::container::injector::{CallContext, ConstructedVisitor, Injector, InjectorError};
+use crate::container::Managed;
+use crate::key::{self, Key, TypedQualifier};
+use crate::scope::Scope;
+
+/// An [`Injector`] which resolves objects from a container as if some
+/// qualifiers were replaced by others.
+///
+/// Each override is a pair of keys with the same target type, and a request
+/// for exactly the first key is answered with the object of the second key.
+/// Only the explicitly listed (target, qualifier) pairs are rewritten, so
+/// keys of other target types with the same qualifier are left untouched.
+///
+/// The overrides apply to all dependencies of objects constructed on each
+/// request, so resolution inside a component transparently picks the
+/// remapped bindings. Shared objects cached by containers are always
+/// constructed without any override, since they're visible to all users of
+/// the containers.
+///
+/// Use [`Container::with_qualifier_override`] to create a [`ScopedInjector`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let module = module::from_fn(|configurer| {
+///     bind::<String>()
+///         .qualified_by("tenant-a")
+///         .to_instance(String::from("a"))
+///         .set_on(configurer);
+///     bind::<String>()
+///         .qualified_by("tenant-b")
+///         .to_instance(String::from("b"))
+///         .set_on(configurer);
+///     Ok(())
+/// });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+///
+/// let injector = container.with_qualifier_override::<String>("tenant-a", "tenant-b");
+/// assert_eq!(injector.get(key::named::<String>("tenant-a")).unwrap(), "b");
+/// ```
+///
+/// [`Container::with_qualifier_override`]: crate::container::Container::with_qualifier_override
+pub struct ScopedInjector<S: Scope> {
+    core: Arc<ContainerCore<S>>,
+    overrides: Vec<(Box<dyn Key>, Box<dyn Key>)>,
+}
+
+impl<S: Scope> ScopedInjector<S> {
+    pub(super) fn new(core: Arc<ContainerCore<S>>) -> Self {
+        Self {
+            core,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Adds an override, so that requests for objects of type `T` qualified
+    /// by `from` are answered with objects qualified by `to`.
+    pub fn with_qualifier_override<T: Managed>(
+        mut self,
+        from: impl TypedQualifier,
+        to: impl TypedQualifier,
+    ) -> Self {
+        let target = TypeId::of::<T>();
+        self.overrides.push((
+            Box::new(key::erased(target, from)),
+            Box::new(key::erased(target, to)),
+        ));
+        self
+    }
+
+    fn rewrite<'a>(&'a self, key: &'a dyn Key) -> &'a dyn Key {
+        self.overrides
+            .iter()
+            .find(|(from, _)| from.as_ref() == key)
+            .map_or(key, |(_, to)| to.as_ref())
+    }
+}
+
+impl<S: Scope> Debug for ScopedInjector<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ScopedInjector<S>")
+            .field("overrides", &self.overrides)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Scope> Injector for ScopedInjector<S> {
+    fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+        let context = CallContext::new(self.rewrite(key));
+        self.core.get_object(&context, self)
+    }
+
+    fn dyn_get_dependency<'a>(
+        &self,
+        key: &dyn Key,
+        context: &'a CallContext<'a>,
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        let context = context.append(self.rewrite(key));
+        self.core.get_object(&context, self)
+    }
+
+    fn dyn_get_with_context(
+        &self,
+        key: &dyn Key,
+        user_data: &(dyn Any + Sync),
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        let context = CallContext::new(self.rewrite(key)).with_user_data(user_data);
+        self.core.get_object(&context, self)
+    }
+
+    fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+        self.core.keys(type_id)
+    }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.core.contains_target(type_id)
+    }
+
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        self.core
+            .with_constructed_object(self.rewrite(key), visitor)
+    }
+}
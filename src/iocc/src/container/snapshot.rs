@@ -0,0 +1,131 @@
+use crate::container::registry::ProviderEntry;
+use crate::provider::{LocalProvider, Provider};
+use crate::scope::Scope;
+
+/// A description of all bindings in a container, without any object.
+///
+/// Bindings are sorted by their keys, so that snapshots of the same
+/// configuration are always identical. With the `serde` feature enabled, a
+/// [`ContainerSnapshot`] can be serialized to compare configurations across
+/// deployments or to generate documentation.
+///
+/// Use [`Container::snapshot`] to create a [`ContainerSnapshot`].
+///
+/// [`Container::snapshot`]: crate::container::Container::snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct ContainerSnapshot {
+    /// Descriptions of all bindings.
+    pub bindings: Vec<BindingSnapshot>,
+}
+
+impl ContainerSnapshot {
+    pub(super) fn new<'a, S, I>(entries: I) -> Self
+    where
+        S: Scope,
+        I: IntoIterator<Item = &'a ProviderEntry<S>>,
+    {
+        let mut bindings: Vec<_> = entries.into_iter().map(BindingSnapshot::new).collect();
+        bindings.sort_by(|a, b| a.key.cmp(&b.key));
+        Self { bindings }
+    }
+}
+
+/// A description of a single binding in a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct BindingSnapshot {
+    /// The displayed key of the binding.
+    pub key: String,
+    /// The name of the target type of the key.
+    pub target_type: &'static str,
+    /// The name of the qualifier type of the key.
+    pub qualifier_type: &'static str,
+    /// The lifetime of objects of the binding.
+    pub lifetime: BindingLifetime,
+    /// The name of the provider type of the binding.
+    pub provider_type: &'static str,
+}
+
+impl BindingSnapshot {
+    pub(super) fn new<S: Scope>(entry: &ProviderEntry<S>) -> Self {
+        let key = entry.dyn_key();
+        let provider_type = match entry {
+            ProviderEntry::Shared { provider, .. } => {
+                Provider::type_name(provider.upcast_provider())
+            }
+            ProviderEntry::Owned { provider, .. } => Provider::type_name(provider.as_ref()),
+            ProviderEntry::Local { provider, .. } => LocalProvider::type_name(provider.as_ref()),
+        };
+
+        Self {
+            key: key.to_string(),
+            target_type: key.target_type_name(),
+            qualifier_type: key.qualifier_type_name(),
+            lifetime: BindingLifetime::new(entry),
+            provider_type,
+        }
+    }
+}
+
+/// The lifetime of objects of a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum BindingLifetime {
+    /// A new object is constructed on each request.
+    Transient,
+    /// An object is shared within the named scope.
+    Shared { scope: &'static str },
+    /// An object is owned by the container of the named scope.
+    Local { scope: &'static str },
+}
+
+impl BindingLifetime {
+    pub(super) fn new<S: Scope>(entry: &ProviderEntry<S>) -> Self {
+        match entry {
+            ProviderEntry::Shared { scope, .. } => Self::Shared {
+                scope: scope.to_str(),
+            },
+            ProviderEntry::Owned { .. } => Self::Transient,
+            ProviderEntry::Local { scope, .. } => Self::Local {
+                scope: scope.to_str(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+    use crate::key;
+    use crate::provider::instance::InstanceProvider;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    #[test]
+    fn container_snapshot_serialize_succeeds() {
+        let entry = ProviderEntry::<SingletonScope>::new_owned(
+            Box::new(key::of::<i32>()),
+            Box::new(InstanceProvider::new(42)),
+        );
+        let snapshot = ContainerSnapshot::new([&entry]);
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "bindings": [{
+                    "key": "i32",
+                    "target_type": "i32",
+                    "qualifier_type": "()",
+                    "lifetime": "Transient",
+                    "provider_type": "iocc::provider::instance::InstanceProvider<i32>",
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use crate::container::injector::InjectorError;
+
+/// Construction statistics of a single key, see [`Container::stats`].
+///
+/// [`Container::stats`]: crate::container::Container::stats
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct KeyStats {
+    /// The number of times the object has been constructed, including failed
+    /// attempts.
+    pub construct_count: u64,
+    /// The total time spent constructing the object, which includes
+    /// constructing its dependencies on the way.
+    pub total_duration: Duration,
+    /// The error of the latest failed construction, if any.
+    pub last_error: Option<InjectorError>,
+}
+
+impl KeyStats {
+    pub(super) fn record(&mut self, duration: Duration, error: Option<&InjectorError>) {
+        self.construct_count += 1;
+        self.total_duration += duration;
+        if let Some(error) = error {
+            self.last_error = Some(error.clone());
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Process-wide access to containers.
+//!
+//! This module stores at most one [`Container`] for each [`Scope`] type, so
+//! that a container can be retrieved anywhere without passing it through
+//! every function.
+//!
+//! Note that accessing a global container is a form of the service locator
+//! pattern, which hides dependencies of the callers and makes them harder to
+//! test. Prefer injecting dependencies explicitly, and only reach for global
+//! access at the boundary of the application where no container is available,
+//! such as callbacks registered to foreign libraries.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use iocc::prelude::*;
+//! # use iocc::global;
+//! # use iocc::module;
+//! # use iocc::scope::SingletonScope;
+//! let module = module::from_fn(|configurer| {
+//!     bind::<i32>().to_instance(42).set_on(configurer);
+//!     Ok(())
+//! });
+//! let container = Container::<SingletonScope>::init(module).unwrap();
+//! assert!(global::set(container).is_ok());
+//!
+//! let container = global::expect::<SingletonScope>();
+//! assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use parking_lot::RwLock;
+
+use crate::container::Container;
+use crate::scope::Scope;
+
+type ContainerMap = HashMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+static CONTAINERS: OnceLock<RwLock<ContainerMap>> = OnceLock::new();
+
+fn containers() -> &'static RwLock<ContainerMap> {
+    CONTAINERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Sets the global container of scope type `S`.
+///
+/// # Errors
+///
+/// Returns the given container back if a global container of scope type `S`
+/// has already been set.
+pub fn set<S: Scope>(container: Container<S>) -> Result<(), Container<S>> {
+    match containers().write().entry(TypeId::of::<S>()) {
+        Entry::Occupied(_) => Err(container),
+        Entry::Vacant(entry) => {
+            entry.insert(Box::new(container));
+            Ok(())
+        }
+    }
+}
+
+/// Gets the global container of scope type `S`, or returns [`None`] if it
+/// hasn't been set yet.
+pub fn get<S: Scope>() -> Option<Container<S>> {
+    containers()
+        .read()
+        .get(&TypeId::of::<S>())
+        .map(|container| {
+            container
+                .downcast_ref::<Container<S>>()
+                .unwrap_or_else(|| unreachable!("`container` should be `Container<S>`"))
+                .clone()
+        })
+}
+
+/// Gets the global container of scope type `S`.
+///
+/// # Panics
+///
+/// Panics if the global container of scope type `S` hasn't been set yet.
+pub fn expect<S: Scope>() -> Container<S> {
+    get::<S>().unwrap_or_else(|| {
+        panic!(
+            "the global container of scope {} should be set before being accessed",
+            std::any::type_name::<S>()
+        )
+    })
+}
+
+/// Returns true if the global container of scope type `S` has been set.
+pub fn is_set<S: Scope>() -> bool {
+    containers().read().contains_key(&TypeId::of::<S>())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::Registry;
+    use crate::key;
+    use crate::module::{bind, from_fn};
+    use crate::scope::WebScope;
+
+    use super::*;
+
+    #[test]
+    fn global_set_and_get_succeeds() {
+        assert!(!is_set::<WebScope>());
+        assert!(get::<WebScope>().is_none());
+
+        let module = from_fn(|configurer| {
+            bind::<i32>().to_instance(42).set_on(configurer);
+            Ok(())
+        });
+        let container = Container::<WebScope>::init(module).unwrap();
+        assert!(set(container.clone()).is_ok());
+        assert!(set(container).is_err());
+
+        assert!(is_set::<WebScope>());
+        assert_eq!(
+            get::<WebScope>().unwrap().get(key::of::<i32>()).unwrap(),
+            42
+        );
+        assert_eq!(expect::<WebScope>().get(key::of::<i32>()).unwrap(), 42);
+    }
+}
@@ -0,0 +1,112 @@
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+
+use crate::key::{Key, Qualifier};
+
+/// A lookup-only [`Key`] whose target type is only known at runtime.
+///
+/// Use [`key::erased`] to create an [`ErasedKey`].
+///
+/// [`key::erased`]: crate::key::erased()
+pub struct ErasedKey {
+    target: TypeId,
+    qualifier: Box<dyn Qualifier>,
+}
+
+impl ErasedKey {
+    pub(super) fn new(target: TypeId, qualifier: Box<dyn Qualifier>) -> Self {
+        Self { target, qualifier }
+    }
+}
+
+impl Clone for ErasedKey {
+    fn clone(&self) -> Self {
+        Self::new(self.target, self.qualifier.dyn_clone())
+    }
+}
+
+impl Debug for ErasedKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for ErasedKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.qualifier_type() == TypeId::of::<()>() {
+            write!(f, "{:?}", self.target)
+        } else {
+            write!(f, "{:?}@{:?}", self.target, self.qualifier)
+        }
+    }
+}
+
+impl PartialEq for ErasedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target && *self.qualifier == *other.qualifier
+    }
+}
+
+impl Eq for ErasedKey {}
+
+impl Hash for ErasedKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.target.hash(state);
+        self.qualifier.hash(state);
+    }
+}
+
+impl Key for ErasedKey {
+    fn target_type(&self) -> TypeId {
+        self.target
+    }
+
+    fn qualifier_type(&self) -> TypeId {
+        Any::type_id(self.qualifier.as_ref().as_any())
+    }
+
+    /// Returns a placeholder, since only the [`TypeId`] of the target is
+    /// known.
+    fn target_type_name(&self) -> &'static str {
+        "{erased}"
+    }
+
+    fn dyn_qualifier(&self) -> &dyn Qualifier {
+        self.qualifier.as_ref()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Key> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn erased_key_eq_succeeds() {
+        let erased: Box<dyn Key> = Box::new(key::erased(TypeId::of::<i32>(), "name"));
+        let typed: Box<dyn Key> = Box::new(key::named::<i32>("name"));
+        assert_eq!(&erased, &typed);
+        assert_eq!(erased.qualifier_type(), TypeId::of::<&'static str>());
+
+        let erased: Box<dyn Key> = Box::new(key::erased(TypeId::of::<i32>(), ()));
+        assert_ne!(&erased, &typed);
+        assert_eq!(&erased, &(Box::new(key::of::<i32>()) as Box<dyn Key>));
+    }
+
+    #[test]
+    fn erased_key_hash_succeeds() {
+        let mut map: HashMap<Box<dyn Key>, i32> = HashMap::new();
+        map.insert(Box::new(key::named::<i32>("name")), 42);
+
+        let erased = key::erased(TypeId::of::<i32>(), "name");
+        assert_eq!(map.get(&erased as &dyn Key), Some(&42));
+    }
+}
@@ -174,4 +174,37 @@ mod tests {
         assert_ne!(&i32_key, &i32_name2_key);
         assert_ne!(&i32_name1_key, &i32_name2_key);
     }
+
+    #[test]
+    fn key_impl_info_succeeds() {
+        let key: Box<dyn Key> = Box::new(KeyImpl::<i32, _>::new("name"));
+
+        let info = key.info();
+        assert_eq!(info.target_type, TypeId::of::<i32>());
+        assert_eq!(info.qualifier_type, TypeId::of::<&str>());
+        assert_eq!(info.display, key.to_string());
+        assert_eq!(info, KeyImpl::<i32, _>::new("name").info());
+    }
+
+    #[test]
+    fn key_impl_stable_hash_succeeds() {
+        let key: Box<dyn Key> = Box::new(KeyImpl::<i32, _>::new("name"));
+
+        assert_eq!(
+            key.stable_hash(),
+            KeyImpl::<i32, _>::new("name").stable_hash()
+        );
+        assert_ne!(
+            key.stable_hash(),
+            KeyImpl::<i32, _>::new("other").stable_hash()
+        );
+        assert_ne!(
+            key.stable_hash(),
+            KeyImpl::<i64, _>::new("name").stable_hash()
+        );
+        assert_ne!(
+            KeyImpl::<i32, _>::new(1u32).stable_hash(),
+            KeyImpl::<i32, _>::new(1u64).stable_hash()
+        );
+    }
 }
@@ -1,16 +1,19 @@
+mod erased;
 mod implementation;
 mod pattern;
 
-use std::any::TypeId;
+use std::any::{self, TypeId};
 use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 
 use crate::container::Managed;
 use crate::util::any::AsAny;
-use crate::util::hash::DynHash;
+use crate::util::hash::{DynHash, StableHasher};
 
+pub use crate::key::erased::ErasedKey;
 pub(crate) use crate::key::implementation::KeyImpl;
-pub use crate::key::pattern::{AnyPattern, KeyTypePattern, Pattern};
+pub use crate::key::pattern::{AnyPattern, ErasedPattern, KeyTypePattern, Pattern};
+pub use iocc_derive::Qualifier;
 
 /// An abstract identifier for each object managed by a container.
 ///
@@ -33,16 +36,77 @@ where
     /// Returns a [`TypeId`] of the qualifier.
     fn qualifier_type(&self) -> TypeId;
 
+    /// Returns the name of the target type, which is only intended for
+    /// diagnostics.
+    ///
+    /// The default implementation returns a placeholder, since the target
+    /// type isn't always known statically.
+    fn target_type_name(&self) -> &'static str {
+        "{unknown}"
+    }
+
+    /// Returns the name of the qualifier type, which is only intended for
+    /// diagnostics.
+    fn qualifier_type_name(&self) -> &'static str {
+        AsAny::type_name(self.dyn_qualifier())
+    }
+
     /// Gets a type-erased qualifier.
     fn dyn_qualifier(&self) -> &dyn Qualifier;
 
     /// Clones a new [`Key`] from `self`.
     fn dyn_clone(&self) -> Box<dyn Key>;
+
+    /// Returns a hash of `self` which is consistent across process runs, so
+    /// that it can be persisted, such as in an external cache keyed by
+    /// container keys.
+    ///
+    /// Since [`TypeId`]s aren't stable across builds, the hash is computed
+    /// from the names of the target and qualifier types and the [`Debug`]
+    /// representation of the qualifier with a fixed-seed hasher. Type names
+    /// may still change with the compiler version, and distinct types can
+    /// share a name, so the hash is meant for indexing rather than as the
+    /// sole proof of identity.
+    fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_len_prefixed(self.target_type_name());
+        hasher.write_len_prefixed(self.qualifier_type_name());
+        hasher.write_len_prefixed(&format!("{:?}", self.dyn_qualifier()));
+        hasher.finish()
+    }
+
+    /// Takes a snapshot of the identity of `self`, which can be kept without
+    /// holding the key.
+    fn info(&self) -> KeyInfo {
+        KeyInfo {
+            target_type: self.target_type(),
+            qualifier_type: self.qualifier_type(),
+            display: self.to_string(),
+        }
+    }
+}
+
+/// A snapshot of the identity of a [`Key`], returned by [`Key::info`].
+///
+/// The pair of [`KeyInfo::target_type`] and [`KeyInfo::qualifier_type`] is
+/// convenient for building indices of keys, while [`KeyInfo::display`] keeps
+/// the qualifier value in a readable form for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct KeyInfo {
+    /// The [`TypeId`] of the target.
+    pub target_type: TypeId,
+    /// The [`TypeId`] of the qualifier.
+    pub qualifier_type: TypeId,
+    /// The key formatted with [`Display`].
+    pub display: String,
 }
 
+// Keys are compared by their target types and qualifiers rather than their
+// concrete types, so that an `ErasedKey` matches the typed key it describes.
 impl PartialEq for dyn Key {
     fn eq(&self, other: &Self) -> bool {
-        self.dyn_eq(other.as_any())
+        self.target_type() == other.target_type() && self.dyn_qualifier() == other.dyn_qualifier()
     }
 }
 
@@ -50,7 +114,8 @@ impl Eq for dyn Key {}
 
 impl Hash for dyn Key {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.dyn_hash(state);
+        self.target_type().hash(state);
+        self.dyn_qualifier().hash(state);
     }
 }
 
@@ -63,6 +128,10 @@ impl<T: TypedKey> Key for T {
         TypeId::of::<T::Qualifier>()
     }
 
+    fn target_type_name(&self) -> &'static str {
+        any::type_name::<T::Target>()
+    }
+
     fn dyn_qualifier(&self) -> &dyn Qualifier {
         self.qualifier_ref()
     }
@@ -93,6 +162,37 @@ pub trait TypedKey: Key + Copy + Eq + Hash {
 
 /// An abstract value helps distinguish multiple managed objects of the same
 /// type.
+///
+/// Any `Debug + Copy + Eq + Hash + Send + Sync + 'static` type is a qualifier.
+/// Domain-specific qualifier types can be marked with `#[derive(Qualifier)]`,
+/// which confirms these bounds at compile time. Keys render their qualifiers
+/// with [`Debug`], so adding `#[qualifier(display)]` makes the derive
+/// implement [`Debug`] in terms of [`Display`] instead of deriving it,
+/// rendering keys in diagnostics readably.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::fmt::{Display, Formatter, Result as FmtResult};
+/// # use iocc::key::{self, Qualifier};
+/// #[derive(Qualifier, Clone, Copy, PartialEq, Eq, Hash)]
+/// #[qualifier(display)]
+/// enum Region {
+///     Europe,
+///     America,
+/// }
+///
+/// impl Display for Region {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+///         match self {
+///             Region::Europe => write!(f, "eu"),
+///             Region::America => write!(f, "us"),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(key::qualified::<i32>(Region::Europe).to_string(), "i32@eu");
+/// ```
 pub trait Qualifier
 where
     Self: Debug + AsAny + DynHash + Send + Sync + 'static,
@@ -159,3 +259,36 @@ where
 {
     KeyImpl::new(qualifier)
 }
+
+/// Creates a lookup-only key from the [`TypeId`] of the target and a
+/// qualifier, for code which only knows the target type at runtime.
+///
+/// The returned key is equal to the typed key with the same target type and
+/// qualifier, so it can be passed to [`Injector::dyn_get`], whose result has
+/// to be downcast by the caller. Since the target type is unknown at compile
+/// time, it can't be used with [`TypedInjector::get`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::any::TypeId;
+/// # use iocc::prelude::*;
+/// # use iocc::container::injector::Injector;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let module = module::from_fn(|configurer| {
+///     bind::<i32>().to_instance(42).qualified_by("answer").set_on(configurer);
+///     Ok(())
+/// });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+///
+/// let key = key::erased(TypeId::of::<i32>(), "answer");
+/// let object = container.dyn_get(&key).unwrap();
+/// assert_eq!(object.downcast_ref::<i32>(), Some(&42));
+/// ```
+///
+/// [`Injector::dyn_get`]: crate::container::injector::Injector::dyn_get
+/// [`TypedInjector::get`]: crate::container::injector::TypedInjector::get
+pub fn erased(target: TypeId, qualifier: impl TypedQualifier) -> ErasedKey {
+    ErasedKey::new(target, Box::new(qualifier))
+}
@@ -16,9 +16,27 @@ pub trait Pattern {
     fn matches(&self, key: &dyn Key) -> bool;
 }
 
+/// An object-safe counterpart of [`Pattern`], which is used to match keys
+/// whose target type is only known at runtime.
+///
+/// It's implemented for all [`Pattern`]s.
+pub trait ErasedPattern {
+    /// Tests whether the key matches the pattern.
+    fn dyn_matches(&self, key: &dyn Key) -> bool;
+}
+
+impl<P: Pattern> ErasedPattern for P {
+    fn dyn_matches(&self, key: &dyn Key) -> bool {
+        self.matches(key)
+    }
+}
+
 /// A [`Pattern`] which matches all keys of target type `T` and any qualifier
 /// type.
 ///
+/// Use [`KeyTypePattern`] instead to only match keys whose qualifier is of a
+/// specific type.
+///
 /// # Examples
 ///
 /// ```rust
@@ -64,6 +82,12 @@ where
 /// A [`Pattern`] which matches all keys of target type `T` and qualifier type
 /// `Q`.
 ///
+/// Unlike [`AnyPattern`], keys with a qualifier of any other type are filtered
+/// out, regardless of the collection that matched objects are gathered into.
+/// Collecting with a [`KeyTypePattern`] into a map keeps the qualifier values
+/// as the map's keys, while collecting into a non-map collection like [`Vec`]
+/// discards them.
+///
 /// # Examples
 ///
 /// ```rust
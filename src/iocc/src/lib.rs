@@ -1,19 +1,23 @@
 #![allow(clippy::new_without_default)]
 
 pub mod container;
+pub mod global;
 pub mod key;
+mod macros;
 pub mod module;
 pub mod provider;
 pub mod scope;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod util;
 
 pub use iocc_derive::component;
 
 pub mod prelude {
     pub use crate::component;
-    pub use crate::container::injector::{InjectorError, TypedInjector};
+    pub use crate::container::injector::{Collect, Inject, InjectorError, TypedInjector};
     pub use crate::container::registry::{Configurer, Registry, RegistryError};
-    pub use crate::container::Container;
-    pub use crate::key;
+    pub use crate::container::{Container, Downcast, DowncastRef};
+    pub use crate::key::{self, AnyPattern, Key, KeyTypePattern, Pattern};
     pub use crate::module::{bind, Configuration, Module};
 }
@@ -0,0 +1,173 @@
+/// Declares a transparent newtype wrapper, which adapts a type from another
+/// crate to be managed by containers.
+///
+/// The generated wrapper is `#[repr(transparent)]` and implements [`Deref`],
+/// [`DerefMut`] and [`From`] for the inner type, so that it can be bound with
+/// the inner value directly and used as if it were the inner type. It also
+/// gets an associated `key()` function returning `key::of::<Wrapper>()`, and
+/// an `into_inner()` method.
+///
+/// Note that the wrapper is still required to be [`Send`] and [`Sync`] like
+/// any other managed object, which is checked at compile time by `key()`.
+/// Wrapping only helps with implementing local traits and giving the type a
+/// distinct identity in containers, and it can't make a non-thread-safe type
+/// shareable. Wrap such a type in a [`Mutex`] before wrapping it in a newtype.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::BTreeMap;
+/// # use iocc::prelude::*;
+/// # use iocc::managed_newtype;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// managed_newtype! {
+///     /// Settings read from the environment.
+///     #[derive(Clone)]
+///     pub struct Settings(BTreeMap<String, String>);
+/// }
+///
+/// let module = module::from_fn(|configurer| {
+///     bind::<Settings>()
+///         .to_instance(Settings::from(BTreeMap::new()))
+///         .set_on(configurer);
+///     Ok(())
+/// });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+/// let settings = container.get(Settings::key()).unwrap();
+/// assert!(settings.is_empty());
+/// ```
+///
+/// [`Deref`]: std::ops::Deref
+/// [`DerefMut`]: std::ops::DerefMut
+/// [`Mutex`]: std::sync::Mutex
+#[macro_export]
+macro_rules! managed_newtype {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($inner_vis:vis $inner:ty);) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        $vis struct $name($inner_vis $inner);
+
+        impl $name {
+            /// Returns the key of this type without a qualifier.
+            #[allow(dead_code)]
+            $vis fn key() -> impl $crate::key::TypedKey<Target = Self, Qualifier = ()> {
+                $crate::key::of::<Self>()
+            }
+
+            /// Unwraps the inner value.
+            #[allow(dead_code)]
+            $vis fn into_inner(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl ::std::ops::Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::std::ops::DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl ::std::convert::From<$inner> for $name {
+            fn from(inner: $inner) -> Self {
+                Self(inner)
+            }
+        }
+    };
+}
+
+/// Declares a [`Module`] whose bindings are listed as statements, sparing the
+/// boilerplate of implementing [`Module::configure`] by hand.
+///
+/// The block starts with an optional `struct` declaration naming the module,
+/// followed by a `scope:` header setting [`Module::Scope`], and then the
+/// bindings, each terminated by a semicolon. Every binding is set on the
+/// configurer, so `.set_on(configurer)` must be left out. [`bind`] and
+/// [`bind_key`] are in scope within the block.
+///
+/// With a `struct` declaration, the macro expands to a unit struct
+/// implementing [`Module`]. Without one, it expands to an expression
+/// evaluating to a module of an anonymous type.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::WebScope;
+/// module! {
+///     /// Bindings of the configuration values.
+///     pub struct ConfigModule;
+///     scope: WebScope;
+///     bind::<u16>().qualified_by("port").to_instance(8080);
+///     bind::<Arc<String>>()
+///         .to_instance(Arc::new("localhost".to_owned()))
+///         .within(WebScope::Singleton);
+/// }
+///
+/// let container = Container::init(ConfigModule).unwrap();
+/// assert_eq!(container.get(key::named::<u16>("port")).unwrap(), 8080);
+///
+/// let module = module! {
+///     scope: WebScope;
+///     bind::<i32>().to_instance(42);
+/// };
+/// let container = Container::init(module).unwrap();
+/// assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+/// ```
+///
+/// [`Module`]: crate::module::Module
+/// [`Module::configure`]: crate::module::Module::configure
+/// [`Module::Scope`]: crate::module::Module::Scope
+/// [`bind`]: crate::module::bind
+/// [`bind_key`]: crate::module::bind_key
+#[macro_export]
+macro_rules! module {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident;
+        scope: $scope:ty;
+        $($binding:expr;)*
+    ) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        impl $crate::module::Module for $name {
+            type Scope = $scope;
+
+            fn configure(
+                &self,
+                configurer: &mut dyn $crate::container::registry::Configurer<Scope = Self::Scope>,
+            ) -> ::std::result::Result<
+                (),
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+            > {
+                #[allow(unused_imports)]
+                use $crate::module::{bind, bind_key};
+
+                $($binding.set_on(configurer);)*
+                ::std::result::Result::Ok(())
+            }
+        }
+    };
+    (
+        scope: $scope:ty;
+        $($binding:expr;)*
+    ) => {{
+        $crate::module! {
+            struct AnonymousModule;
+            scope: $scope;
+            $($binding;)*
+        }
+        AnonymousModule
+    }};
+}
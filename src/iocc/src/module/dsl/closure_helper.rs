@@ -1,6 +1,7 @@
+use std::any::TypeId;
 use std::marker::PhantomData;
 
-use crate::container::registry::{Configurer, TypedConfigurer};
+use crate::container::registry::{self, Configurer, RegistryError, TypedConfigurer};
 use crate::container::{Managed, SharedManaged};
 use crate::key::{self, TypedQualifier};
 use crate::module::dsl::ToLifetime;
@@ -57,6 +58,16 @@ where
     pub fn as_transient(self) -> ClosureBinding<KT, KQ, Transient, C, D> {
         ClosureBinding::new(self.closure, self.qualifier, Transient)
     }
+
+    /// Returns true if the closure fetches the bound key itself, which would
+    /// only fail as a cyclic dependency when the object is resolved.
+    fn depends_on_itself(&self) -> bool {
+        TypeId::of::<KQ>() == TypeId::of::<()>()
+            && self
+                .closure
+                .dependency_types()
+                .contains(&TypeId::of::<KT>())
+    }
 }
 
 impl<KT, KQ, S, C, D> ClosureBinding<KT, KQ, S, C, D>
@@ -68,7 +79,13 @@ where
     D: Send + Sync + 'static,
 {
     pub fn set_on(self, configurer: &mut dyn Configurer<Scope = S>) {
-        let key = key::qualified(self.qualifier);
+        let self_dependent = self.depends_on_itself();
+        let key = key::qualified::<KT>(self.qualifier);
+        if self_dependent {
+            let key = Box::new(key);
+            registry::report_error(configurer, RegistryError::SelfDependency { key });
+            return;
+        }
         let provider = ClosureProvider::new(self.closure);
         configurer.register_shared(key, provider, self.lifetime);
     }
@@ -85,7 +102,13 @@ where
     where
         S: Scope,
     {
-        let key = key::qualified(self.qualifier);
+        let self_dependent = self.depends_on_itself();
+        let key = key::qualified::<KT>(self.qualifier);
+        if self_dependent {
+            let key = Box::new(key);
+            registry::report_error(configurer, RegistryError::SelfDependency { key });
+            return;
+        }
         let provider = ClosureProvider::new(self.closure);
         configurer.register(key, provider);
     }
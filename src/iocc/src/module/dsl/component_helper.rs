@@ -1,10 +1,16 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
+use crate::container::injector::Injector;
 use crate::container::registry::{Configurer, TypedConfigurer};
-use crate::container::SharedManaged;
-use crate::key::{self, TypedQualifier};
-use crate::module::dsl::ToLifetime;
+use crate::container::{Managed, SharedManaged};
+use crate::key::{self, TypedKey, TypedQualifier};
+use crate::module::dsl::provider_helper::ProviderBinding;
+use crate::module::dsl::{ToLifetime, WeakScoped};
+use crate::provider::alias::AliasProvider;
 use crate::provider::component::{Component, ComponentProvider};
+use crate::provider::gated::{Gate, GatedProvider};
+use crate::provider::proxy::ProxiedProvider;
 use crate::scope::{Scope, Transient};
 
 #[allow(private_bounds)]
@@ -51,6 +57,63 @@ where
     pub fn as_transient(self) -> ComponentBinding<C, KQ, Transient> {
         ComponentBinding::new(self.qualifier, Transient)
     }
+
+    pub fn gated_by(
+        self,
+        gate: Gate,
+    ) -> ProviderBinding<C::Constructed, KQ, L, GatedProvider<ComponentProvider<C>>> {
+        let provider = GatedProvider::new(ComponentProvider::<C>::new(), gate);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn with_injector_proxy<F>(
+        self,
+        proxy: F,
+    ) -> ProviderBinding<C::Constructed, KQ, L, ProxiedProvider<ComponentProvider<C>, F>>
+    where
+        F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+        F: Send + Sync + 'static,
+    {
+        let provider = ProxiedProvider::new(ComponentProvider::<C>::new(), proxy);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn exposed_as<T, F>(self, convert: F) -> ExposedComponentBinding<C, KQ, L, T, F>
+    where
+        T: Managed,
+        F: Fn(C::Constructed) -> T + Send + Sync + 'static,
+    {
+        ExposedComponentBinding {
+            binding: self,
+            convert,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Also binds the component to an `Arc<U>` key with the same qualifier,
+    /// where `U` is usually a trait object, by coercing the constructed `Arc`
+    /// with `convert`, e.g. `|c| c as Arc<dyn Trait>`.
+    ///
+    /// Keys of a concrete type and of the trait objects it implements are
+    /// unrelated unless they are linked explicitly like this. Shared objects
+    /// are resolved through the component's own key, so both keys hand out
+    /// the same object.
+    #[allow(clippy::type_complexity)]
+    pub fn upcast_to<U>(
+        self,
+        convert: impl Fn(C::Constructed) -> Arc<U> + Send + Sync + 'static,
+    ) -> ExposedComponentBinding<
+        C,
+        KQ,
+        L,
+        Arc<U>,
+        impl Fn(C::Constructed) -> Arc<U> + Send + Sync + 'static,
+    >
+    where
+        U: Send + Sync + ?Sized + 'static,
+    {
+        self.exposed_as(convert)
+    }
 }
 
 impl<C, KQ, S> ComponentBinding<C, KQ, S>
@@ -66,6 +129,20 @@ where
     }
 }
 
+impl<C, KQ, S, T> ComponentBinding<C, KQ, WeakScoped<S>>
+where
+    C: Component<Constructed = Arc<T>>,
+    KQ: TypedQualifier,
+    S: Scope,
+    T: Send + Sync + ?Sized + 'static,
+{
+    pub fn set_on(self, configurer: &mut dyn Configurer<Scope = S>) {
+        let key = key::qualified(self.qualifier);
+        let provider = ComponentProvider::<C>::new();
+        configurer.register_weak(key, provider, self.lifetime.0);
+    }
+}
+
 impl<C, KQ> ComponentBinding<C, KQ, Transient>
 where
     C: Component,
@@ -80,3 +157,70 @@ where
         configurer.register(key, provider);
     }
 }
+
+#[allow(private_bounds)]
+pub struct ExposedComponentBinding<C, KQ, L, T, F>
+where
+    C: Component,
+    KQ: TypedQualifier,
+    L: ToLifetime,
+    T: Managed,
+    F: Fn(C::Constructed) -> T + Send + Sync + 'static,
+{
+    binding: ComponentBinding<C, KQ, L>,
+    convert: F,
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[allow(private_bounds)]
+impl<C, KQ, L, T, F> ExposedComponentBinding<C, KQ, L, T, F>
+where
+    C: Component,
+    KQ: TypedQualifier,
+    L: ToLifetime,
+    T: Managed,
+    F: Fn(C::Constructed) -> T + Send + Sync + 'static,
+{
+    fn set_alias_on<S>(
+        key: impl TypedKey<Target = C::Constructed>,
+        convert: F,
+        configurer: &mut dyn Configurer<Scope = S>,
+    ) where
+        S: Scope,
+    {
+        let alias = key::qualified::<T>(key.qualifier());
+        configurer.register(alias, AliasProvider::new(key, convert));
+    }
+}
+
+impl<C, KQ, S, T, F> ExposedComponentBinding<C, KQ, S, T, F>
+where
+    C: Component<Constructed: SharedManaged>,
+    KQ: TypedQualifier,
+    S: Scope,
+    T: Managed,
+    F: Fn(C::Constructed) -> T + Send + Sync + 'static,
+{
+    pub fn set_on(self, configurer: &mut dyn Configurer<Scope = S>) {
+        let key = key::qualified::<C::Constructed>(self.binding.qualifier);
+        self.binding.set_on(configurer);
+        Self::set_alias_on(key, self.convert, configurer);
+    }
+}
+
+impl<C, KQ, T, F> ExposedComponentBinding<C, KQ, Transient, T, F>
+where
+    C: Component,
+    KQ: TypedQualifier,
+    T: Managed,
+    F: Fn(C::Constructed) -> T + Send + Sync + 'static,
+{
+    pub fn set_on<S>(self, configurer: &mut dyn Configurer<Scope = S>)
+    where
+        S: Scope,
+    {
+        let key = key::qualified::<C::Constructed>(self.binding.qualifier);
+        self.binding.set_on(configurer);
+        Self::set_alias_on(key, self.convert, configurer);
+    }
+}
@@ -1,16 +1,27 @@
+use std::error::Error;
 use std::marker::PhantomData;
-use std::sync::Arc;
+#[cfg(feature = "env")]
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
+use crate::container::injector::{Injector, InjectorError};
 use crate::container::registry::{Configurer, TypedConfigurer};
 use crate::container::{Managed, SharedManaged};
-use crate::key::{self, TypedQualifier};
+use crate::key::{self, Key, TypedQualifier};
 use crate::module::dsl::component_helper::ComponentBinding;
 use crate::module::dsl::instance_helper::InstanceBinding;
 use crate::module::dsl::provider_helper::ProviderBinding;
 use crate::module::dsl::raw_closure_helper::RawClosureBinding;
-use crate::module::dsl::ToLifetime;
-use crate::provider::closure::{Closure, RawClosure};
-use crate::provider::component::{Component, ComponentProvider};
+use crate::module::dsl::{ConstructedAs, ToLifetime, WeakScoped};
+use crate::provider::closure::{Closure, DependencyGroups, RawClosure};
+use crate::provider::component::{
+    Component, ComponentProvider, ComponentWith, ComponentWithProvider, SharedMutableProvider,
+};
+use crate::provider::contextual::ContextualProvider;
+#[cfg(feature = "env")]
+use crate::provider::env::EnvProvider;
+use crate::provider::fallback::FallbackProvider;
+use crate::provider::instance::ThunkProvider;
 use crate::provider::TypedProvider;
 use crate::scope::{Scope, Transient};
 
@@ -63,7 +74,7 @@ where
 
     pub fn to_component<C>(self) -> ComponentBinding<C, KQ, L>
     where
-        C: Component<Constructed = KT>,
+        C: Component<Constructed: ConstructedAs<KT>>,
     {
         ComponentBinding::new(self.qualifier, self.lifetime)
     }
@@ -76,6 +87,19 @@ where
         ClosureBinding::new(closure, self.qualifier, self.lifetime)
     }
 
+    pub fn to_closure_tuple<C, D, E>(
+        self,
+        closure: C,
+    ) -> RawClosureBinding<KT, KQ, L, impl RawClosure<Constructed = KT>>
+    where
+        C: Fn(D) -> Result<KT, E> + Send + Sync + 'static,
+        D: DependencyGroups,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let closure = move |injector: &dyn Injector| Ok(closure(D::resolve(injector)?));
+        RawClosureBinding::new(closure, self.qualifier, self.lifetime)
+    }
+
     pub fn to_raw_closure<C>(self, closure: C) -> RawClosureBinding<KT, KQ, L, C>
     where
         C: RawClosure<Constructed = KT>,
@@ -90,12 +114,98 @@ where
         InstanceBinding::new(instance, self.qualifier, self.lifetime)
     }
 
+    pub fn to_thunk<F>(self, thunk: F) -> ProviderBinding<KT, KQ, L, ThunkProvider<KT, F>>
+    where
+        F: Fn() -> KT + Send + Sync + 'static,
+    {
+        ProviderBinding::new(ThunkProvider::new(thunk), self.qualifier, self.lifetime)
+    }
+
     pub fn to_provider<P>(self, provider: P) -> ProviderBinding<KT, KQ, L, P>
     where
         P: TypedProvider<Output = KT>,
     {
         ProviderBinding::new(provider, self.qualifier, self.lifetime)
     }
+
+    pub fn to_fallback_chain<I>(
+        self,
+        providers: I,
+    ) -> ProviderBinding<KT, KQ, L, FallbackProvider<KT>>
+    where
+        I: IntoIterator<Item: TypedProvider<Output = KT>>,
+    {
+        let provider = providers.into_iter().collect();
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T, KQ, L> MetadataBinding<Arc<T>, KQ, L>
+where
+    T: Send + Sync + 'static,
+    KQ: TypedQualifier,
+    L: ToLifetime,
+{
+    pub fn to_instance_shared(self, instance: T) -> InstanceBinding<Arc<T>, KQ, L> {
+        InstanceBinding::new(Arc::new(instance), self.qualifier, self.lifetime)
+    }
+}
+
+impl<T, KQ, S> MetadataBinding<Arc<T>, KQ, S>
+where
+    T: Send + Sync + ?Sized + 'static,
+    KQ: TypedQualifier,
+    S: Scope,
+{
+    /// Makes containers cache objects of the binding by weak references, so
+    /// that an object is dropped once it's no longer referenced outside the
+    /// container, and constructed again on the next request.
+    ///
+    /// Only component and provider bindings support weak caching.
+    pub fn weak(self) -> MetadataBinding<Arc<T>, KQ, WeakScoped<S>> {
+        MetadataBinding::new(self.qualifier, WeakScoped(self.lifetime))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T, KQ, L> MetadataBinding<&'static T, KQ, L>
+where
+    T: Sync + 'static,
+    KQ: TypedQualifier,
+    L: ToLifetime,
+{
+    /// Leaks `instance` and binds the `&'static` reference to it, so that
+    /// resolving the key costs no more than copying a reference.
+    ///
+    /// The instance is never dropped, even if the container is, so this is
+    /// only meant for objects living as long as the process, such as global
+    /// configuration. Every resolution returns the same reference.
+    pub fn to_leaked_instance(self, instance: T) -> InstanceBinding<&'static T, KQ, L> {
+        let instance: &'static T = Box::leak(Box::new(instance));
+        InstanceBinding::new(instance, self.qualifier, self.lifetime)
+    }
+}
+
+impl<T, KQ, S> MetadataBinding<Arc<Mutex<T>>, KQ, S>
+where
+    T: Send + 'static,
+    KQ: TypedQualifier,
+    S: Scope,
+{
+    /// Binds the key to a [`SharedMutableProvider`] constructing `C`.
+    ///
+    /// This is only available after [`MetadataBinding::within`], since a
+    /// transient binding would hand out a new `Arc<Mutex<_>>` on each request
+    /// and never share the object.
+    pub fn to_shared_mutable<C>(
+        self,
+    ) -> ProviderBinding<Arc<Mutex<T>>, KQ, S, SharedMutableProvider<C>>
+    where
+        C: Component<Constructed = T>,
+    {
+        ProviderBinding::new(SharedMutableProvider::new(), self.qualifier, self.lifetime)
+    }
 }
 
 impl<KT, KQ, S> MetadataBinding<KT, KQ, S>
@@ -124,6 +234,51 @@ where
     }
 }
 
+impl<KT, KQ> MetadataBinding<KT, KQ, Transient>
+where
+    KT: Managed,
+    KQ: TypedQualifier,
+{
+    pub fn to_component_with<C, X, F>(
+        self,
+        extra: F,
+    ) -> ProviderBinding<KT, KQ, Transient, ComponentWithProvider<C, X, F>>
+    where
+        C: ComponentWith<X, Constructed = KT>,
+        X: 'static,
+        F: Fn() -> X + Send + Sync + 'static,
+    {
+        let provider = ComponentWithProvider::new(extra);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn to_contextual<E, F>(
+        self,
+        closure: F,
+    ) -> ProviderBinding<KT, KQ, Transient, ContextualProvider<KT, E, F>>
+    where
+        E: Into<Box<dyn Error + Send + Sync>> + 'static,
+        F: Fn(&dyn Injector, Option<&dyn Key>) -> Result<Result<KT, E>, InjectorError>,
+        F: Send + Sync + 'static,
+    {
+        let provider = ContextualProvider::new(closure);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    #[cfg(feature = "env")]
+    pub fn to_env(
+        self,
+        var: &'static str,
+        default: KT,
+    ) -> ProviderBinding<KT, KQ, Transient, EnvProvider<KT>>
+    where
+        KT: FromStr<Err: Into<Box<dyn Error + Send + Sync>>> + Clone,
+    {
+        let provider = EnvProvider::new(var).with_default(default);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+}
+
 impl<KT, KQ> MetadataBinding<KT, KQ, Transient>
 where
     KT: Component<Constructed = KT>,
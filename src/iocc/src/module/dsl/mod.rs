@@ -18,12 +18,50 @@ impl<S: Scope> ToLifetime for S {}
 
 impl ToLifetime for Transient {}
 
+impl<S: Scope> ToLifetime for WeakScoped<S> {}
+
 trait Sealed {}
 
 impl<S: Scope> Sealed for S {}
 
 impl Sealed for Transient {}
 
+impl<S: Scope> Sealed for WeakScoped<S> {}
+
+/// The lifetime of bindings created by `weak()` [`dsl`]s, whose objects are
+/// shared within the scope `S` but only weakly cached by containers, see
+/// [`TypedConfigurer::register_weak`].
+///
+/// [`dsl`]: crate::module::dsl
+/// [`TypedConfigurer::register_weak`]: crate::container::registry::TypedConfigurer::register_weak
+#[derive(Debug, Clone, Copy)]
+pub struct WeakScoped<S: Scope>(S);
+
+/// A witness that a component constructs objects of the bound type `KT`,
+/// which is only implemented for `KT` itself.
+///
+/// `to_component()` [`dsl`]s require `Component::Constructed` of the
+/// component to implement this, so that mismatched bindings are explained,
+/// especially trait-object bindings such as
+/// `bind::<Arc<dyn Trait>>().to_component::<C>()`, where `C` has to
+/// implement `Trait` and construct an `Arc<dyn Trait>`.
+///
+/// [`dsl`]: crate::module::dsl
+#[diagnostic::on_unimplemented(
+    message = "the component constructs `{Self}` rather than objects of the bound type `{KT}`",
+    label = "the component is bound to `{KT}` here",
+    note = "`Component::Constructed` of the component should be `{KT}`",
+    note = "for a trait-object binding such as `Arc<dyn Trait>`, implement `Trait` for the component and coerce it in `Component::post_process`, e.g. with `#[component(Arc<dyn Trait>, Arc::new)]`"
+)]
+#[allow(private_bounds)]
+pub trait ConstructedAs<KT>: ConstructedAsSealed<KT> {}
+
+impl<T> ConstructedAs<T> for T {}
+
+trait ConstructedAsSealed<KT> {}
+
+impl<T> ConstructedAsSealed<T> for T {}
+
 pub fn bind<KT>() -> MetadataBinding<KT, (), Transient>
 where
     KT: Managed,
@@ -40,14 +78,22 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::any::TypeId;
     use std::convert::Infallible;
     use std::error::Error;
-    use std::sync::Arc;
-
-    use crate::container::injector::{InjectorError, TypedInjector};
-    use crate::container::registry::Configurer;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::container::injector::{CallContext, Injector, InjectorError, TypedInjector};
+    use crate::container::registry::{Configurer, Registry, RegistryError};
+    use crate::container::Container;
+    use crate::key::{self, Key};
     use crate::module::Module;
+    use crate::provider::closure::RawClosureProvider;
     use crate::provider::component::Component;
+    use crate::provider::fallback::FallbackProvider;
+    use crate::provider::gated::Gate;
     use crate::provider::instance::InstanceProvider;
     use crate::scope::WebScope;
 
@@ -73,10 +119,24 @@ mod tests {
                 .to_component::<TestDynObject>()
                 .set_on(configurer);
 
+            bind::<Arc<dyn TestTrait>>()
+                .qualified_by("weak")
+                .within(WebScope::Request)
+                .weak()
+                .to_component::<TestDynObject>()
+                .set_on(configurer);
+
             bind::<(TestObject, Arc<dyn TestTrait>)>()
                 .to_closure(|a: TestObject, b: Arc<dyn TestTrait>| Ok::<_, Infallible>((a, b)))
                 .as_transient();
 
+            bind::<Arc<(TestObject, i64)>>()
+                .to_closure_tuple(|((a,), (b,)): ((TestObject,), (i64,))| {
+                    Ok::<_, Infallible>(Arc::new((a, b)))
+                })
+                .within(WebScope::Singleton)
+                .set_on(configurer);
+
             bind::<i64>()
                 .to_raw_closure(|_| Ok(Ok::<_, Infallible>(42)))
                 .qualified_by("i64")
@@ -91,10 +151,281 @@ mod tests {
                 .to_provider(InstanceProvider::new("str"))
                 .set_on(configurer);
 
+            #[cfg(feature = "env")]
+            bind::<u16>()
+                .qualified_by("port")
+                .to_env("PORT", 8080)
+                .set_on(configurer);
+
+            bind::<Arc<u32>>()
+                .to_provider(InstanceProvider::new(Arc::new(42)))
+                .with_retry(3)
+                .within(WebScope::Session)
+                .set_on(configurer);
+
+            bind::<u64>()
+                .to_provider(InstanceProvider::new(42))
+                .with_retry_and_backoff(3, |attempt| Duration::from_millis(attempt as u64))
+                .set_on(configurer);
+
+            bind::<TestObject>()
+                .qualified_by(2)
+                .to_component::<TestObject>()
+                .with_injector_proxy(|inner| Box::new(TestInjectorProxy { inner }))
+                .set_on(configurer);
+
+            bind::<TestObject>()
+                .qualified_by(3)
+                .to_component::<TestObject>()
+                .gated_by(Gate::new())
+                .set_on(configurer);
+
+            bind::<Arc<u16>>()
+                .to_provider(InstanceProvider::new(Arc::new(42)))
+                .gated_by(Gate::new().with_timeout(Duration::from_secs(1)))
+                .within(WebScope::Singleton)
+                .set_on(configurer);
+
+            bind::<String>()
+                .to_contextual(|_, requester| {
+                    Ok(Ok::<_, Infallible>(
+                        requester.map_or(String::new(), |key| key.to_string()),
+                    ))
+                })
+                .set_on(configurer);
+
+            bind::<i16>()
+                .to_fallback_chain([InstanceProvider::new(1), InstanceProvider::new(2)])
+                .set_on(configurer);
+
+            bind::<Arc<i16>>()
+                .to_provider(
+                    FallbackProvider::new()
+                        .or(RawClosureProvider::new(|_| Ok(Err("unavailable"))))
+                        .or(InstanceProvider::new(Arc::new(42))),
+                )
+                .within(WebScope::Singleton)
+                .set_on(configurer);
+
+            bind::<i8>()
+                .to_provider(InstanceProvider::new(42))
+                .with_injector_proxy(|inner| Box::new(TestInjectorProxy { inner }))
+                .set_on(configurer);
+
+            bind::<Arc<TestSharedObject>>()
+                .qualified_by("exposed")
+                .within(WebScope::Singleton)
+                .to_component::<TestSharedObject>()
+                .exposed_as(|object| object as Arc<dyn TestTrait>)
+                .set_on(configurer);
+
+            bind::<Arc<Mutex<TestObject>>>()
+                .within(WebScope::Singleton)
+                .to_shared_mutable::<TestObject>()
+                .set_on(configurer);
+
             Ok(())
         }
     }
 
+    #[test]
+    fn exposed_as_shares_object_succeeds() {
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<Arc<TestSharedObject>>()
+                .within(WebScope::Singleton)
+                .to_component::<TestSharedObject>()
+                .exposed_as(|object| object as Arc<dyn TestTrait>)
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let object = container.get(key::of::<Arc<TestSharedObject>>()).unwrap();
+        let exposed = container.get(key::of::<Arc<dyn TestTrait>>()).unwrap();
+        assert!(std::ptr::addr_eq(
+            Arc::as_ptr(&object),
+            Arc::as_ptr(&exposed)
+        ));
+    }
+
+    #[test]
+    fn closure_binding_set_on_fails_when_closure_depends_on_itself() {
+        let res = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<i32>()
+                .to_closure(|object: i32| Ok::<_, Infallible>(object))
+                .set_on(configurer);
+            Ok(())
+        }));
+        assert!(matches!(res, Err(RegistryError::SelfDependency { .. })));
+
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<i64>().to_instance(42).set_on(configurer);
+            bind::<i64>()
+                .qualified_by("doubled")
+                .to_closure(|object: i64| Ok::<_, Infallible>(object * 2))
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+        assert_eq!(container.get(key::named::<i64>("doubled")).unwrap(), 84);
+    }
+
+    #[test]
+    fn upcast_to_shares_object_succeeds() {
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<Arc<TestSharedObject>>()
+                .qualified_by("upcast")
+                .to_component::<TestSharedObject>()
+                .within(WebScope::Singleton)
+                .upcast_to::<dyn TestTrait>(|object| object)
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let object = container
+            .get(key::named::<Arc<TestSharedObject>>("upcast"))
+            .unwrap();
+        let upcast = container
+            .get(key::named::<Arc<dyn TestTrait>>("upcast"))
+            .unwrap();
+        assert!(std::ptr::addr_eq(
+            Arc::as_ptr(&object),
+            Arc::as_ptr(&upcast)
+        ));
+        assert!(container.get(key::of::<Arc<dyn TestTrait>>()).is_err());
+    }
+
+    #[test]
+    fn to_shared_mutable_shares_object_succeeds() {
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<Arc<Mutex<TestObject>>>()
+                .within(WebScope::Singleton)
+                .to_shared_mutable::<TestObject>()
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let first = container.get(key::of::<Arc<Mutex<TestObject>>>()).unwrap();
+        let second = container.get(key::of::<Arc<Mutex<TestObject>>>()).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn to_instance_shared_keeps_identity_succeeds() {
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<Arc<Vec<i32>>>()
+                .within(WebScope::Singleton)
+                .to_instance_shared(vec![1, 2, 3])
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let first = container.get(key::of::<Arc<Vec<i32>>>()).unwrap();
+        let second = container.get(key::of::<Arc<Vec<i32>>>()).unwrap();
+        assert_eq!(*first, [1, 2, 3]);
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn weak_shared_binding_succeeds() {
+        let counter = Arc::new(AtomicI32::new(0));
+        let container = Container::<WebScope>::init(crate::module::from_fn({
+            let counter = Arc::clone(&counter);
+            move |configurer| {
+                let counter = Arc::clone(&counter);
+                bind::<Arc<i32>>()
+                    .within(WebScope::Singleton)
+                    .weak()
+                    .to_provider(RawClosureProvider::new(move |_| {
+                        let id = counter.fetch_add(1, Ordering::Relaxed);
+                        Ok(Ok::<_, Infallible>(Arc::new(id)))
+                    }))
+                    .set_on(configurer);
+                Ok(())
+            }
+        }))
+        .unwrap();
+
+        let first = container.get(key::of::<Arc<i32>>()).unwrap();
+        let second = container.get(key::of::<Arc<i32>>()).unwrap();
+        assert_eq!(*first, 0);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(container.is_constructed(key::of::<Arc<i32>>()));
+
+        drop(first);
+        drop(second);
+        assert!(!container.is_constructed(key::of::<Arc<i32>>()));
+        let third = container.get(key::of::<Arc<i32>>()).unwrap();
+        assert_eq!(*third, 1);
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn to_thunk_succeeds() {
+        let counter = Arc::new(AtomicI32::new(0));
+        let container = Container::<WebScope>::init(crate::module::from_fn({
+            let counter = Arc::clone(&counter);
+            move |configurer| {
+                let counter = Arc::clone(&counter);
+                bind::<i32>()
+                    .to_thunk(move || counter.fetch_add(1, Ordering::Relaxed))
+                    .set_on(configurer);
+                Ok(())
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 0);
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 1);
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn to_leaked_instance_returns_same_reference_succeeds() {
+        let container = Container::<WebScope>::init(crate::module::from_fn(|configurer| {
+            bind::<&'static Vec<i32>>()
+                .to_leaked_instance(vec![1, 2, 3])
+                .set_on(configurer);
+            Ok(())
+        }))
+        .unwrap();
+
+        let first = container.get(key::of::<&'static Vec<i32>>()).unwrap();
+        let second = std::thread::scope(|scope| {
+            scope
+                .spawn(|| container.get(key::of::<&'static Vec<i32>>()).unwrap())
+                .join()
+                .unwrap()
+        });
+        assert_eq!(*first, [1, 2, 3]);
+        assert!(std::ptr::eq(first, second));
+    }
+
+    struct TestInjectorProxy<'a> {
+        inner: &'a dyn Injector,
+    }
+
+    impl Injector for TestInjectorProxy<'_> {
+        fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+            self.inner.dyn_get(key)
+        }
+
+        fn dyn_get_dependency<'a>(
+            &self,
+            key: &dyn Key,
+            context: &'a CallContext<'a>,
+        ) -> Result<Box<dyn Managed>, InjectorError> {
+            self.inner.dyn_get_dependency(key, context)
+        }
+
+        fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+            self.inner.keys(type_id)
+        }
+    }
+
     trait TestTrait: Send + Sync + 'static {}
 
     struct TestDynObject;
@@ -118,6 +449,27 @@ mod tests {
         }
     }
 
+    struct TestSharedObject;
+
+    impl TestTrait for TestSharedObject {}
+
+    impl Component for TestSharedObject {
+        type Constructed = Arc<Self>;
+
+        type Error = Infallible;
+
+        fn construct<I>(_injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Ok(Self))
+        }
+
+        fn post_process(self) -> Self::Constructed {
+            Arc::new(self)
+        }
+    }
+
     struct TestObject;
 
     impl Component for TestObject {
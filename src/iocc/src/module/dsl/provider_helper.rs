@@ -1,7 +1,14 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::container::injector::Injector;
 use crate::container::registry::{Configurer, TypedConfigurer};
 use crate::container::{Managed, SharedManaged};
 use crate::key::{self, TypedQualifier};
-use crate::module::dsl::ToLifetime;
+use crate::module::dsl::{ToLifetime, WeakScoped};
+use crate::provider::gated::{Gate, GatedProvider};
+use crate::provider::proxy::ProxiedProvider;
+use crate::provider::retry::RetryingProvider;
 use crate::provider::{TypedProvider, TypedSharedProvider};
 use crate::scope::{Scope, Transient};
 
@@ -51,6 +58,40 @@ where
     pub fn as_transient(self) -> ProviderBinding<KT, KQ, Transient, P> {
         ProviderBinding::new(self.provider, self.qualifier, Transient)
     }
+
+    pub fn with_retry(self, retries: usize) -> ProviderBinding<KT, KQ, L, RetryingProvider<P>> {
+        let provider = RetryingProvider::new(self.provider, retries);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn with_retry_and_backoff<B>(
+        self,
+        retries: usize,
+        backoff: B,
+    ) -> ProviderBinding<KT, KQ, L, RetryingProvider<P, B>>
+    where
+        B: Fn(usize) -> Duration + Send + Sync + 'static,
+    {
+        let provider = RetryingProvider::new(self.provider, retries).with_backoff(backoff);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn gated_by(self, gate: Gate) -> ProviderBinding<KT, KQ, L, GatedProvider<P>> {
+        let provider = GatedProvider::new(self.provider, gate);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
+
+    pub fn with_injector_proxy<F>(
+        self,
+        proxy: F,
+    ) -> ProviderBinding<KT, KQ, L, ProxiedProvider<P, F>>
+    where
+        F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+        F: Send + Sync + 'static,
+    {
+        let provider = ProxiedProvider::new(self.provider, proxy);
+        ProviderBinding::new(provider, self.qualifier, self.lifetime)
+    }
 }
 
 impl<KT, KQ, S, P> ProviderBinding<KT, KQ, S, P>
@@ -66,6 +107,19 @@ where
     }
 }
 
+impl<T, KQ, S, P> ProviderBinding<Arc<T>, KQ, WeakScoped<S>, P>
+where
+    T: Send + Sync + ?Sized + 'static,
+    KQ: TypedQualifier,
+    S: Scope,
+    P: TypedSharedProvider<Output = Arc<T>>,
+{
+    pub fn set_on(self, configurer: &mut dyn Configurer<Scope = S>) {
+        let key = key::qualified(self.qualifier);
+        configurer.register_weak(key, self.provider, self.lifetime.0);
+    }
+}
+
 impl<KT, KQ, P> ProviderBinding<KT, KQ, Transient, P>
 where
     KT: Managed,
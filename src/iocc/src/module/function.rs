@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::marker::PhantomData;
+
+use crate::container::registry::Configurer;
+use crate::module::Module;
+use crate::scope::Scope;
+
+/// A [`Module`] whose configuration is done by a closure.
+///
+/// Use [`from_fn`] or [`from_fn_named`] to create a [`FnModule`]. The name
+/// of a [`FnModule`] is used in error reporting and defaults to `"FnModule"`.
+pub struct FnModule<F, S>
+where
+    F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+    F: 'static,
+    S: Scope,
+{
+    name: &'static str,
+    configure: F,
+    _marker: PhantomData<S>,
+}
+
+impl<F, S> FnModule<F, S>
+where
+    F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+    F: 'static,
+    S: Scope,
+{
+    /// Creates a new [`FnModule`] with the given name.
+    pub fn new(name: &'static str, configure: F) -> Self {
+        Self {
+            name,
+            configure,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the name used in error reporting.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<F, S> Module for FnModule<F, S>
+where
+    F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+    F: 'static,
+    S: Scope,
+{
+    type Scope = S;
+
+    fn setup(&self, configurer: &mut dyn Configurer<Scope = Self::Scope>) {
+        if let Err(err) = self.configure(configurer) {
+            configurer.report_module_error(self.name, err);
+        }
+    }
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        (self.configure)(configurer)
+    }
+}
+
+/// Creates a [`Module`] from a closure, which configures bindings in the same
+/// way as [`Module::configure`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let module = module::from_fn(|configurer| {
+///     bind::<i32>().to_instance(42).set_on(configurer);
+///     Ok(())
+/// });
+/// let configuration = Configuration::<SingletonScope>::new().with(module);
+/// let container = Container::init(configuration).unwrap();
+/// assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+/// ```
+pub fn from_fn<F, S>(configure: F) -> FnModule<F, S>
+where
+    F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+    F: 'static,
+    S: Scope,
+{
+    FnModule::new("FnModule", configure)
+}
+
+/// Creates a [`Module`] from a closure like [`from_fn`], using `name` in error
+/// reporting.
+pub fn from_fn_named<F, S>(name: &'static str, configure: F) -> FnModule<F, S>
+where
+    F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+    F: 'static,
+    S: Scope,
+{
+    FnModule::new(name, configure)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::{Registry, RegistryError};
+    use crate::container::Container;
+    use crate::key;
+    use crate::module::{bind, Configuration};
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    #[test]
+    fn fn_module_configure_succeeds() {
+        let configuration = Configuration::<SingletonScope>::new()
+            .with(from_fn(|configurer| {
+                bind::<i32>().to_instance(42).set_on(configurer);
+                Ok(())
+            }))
+            .with(from_fn_named("strings", |configurer| {
+                bind::<&'static str>().to_instance("str").set_on(configurer);
+                Ok(())
+            }));
+
+        let container = Container::init(configuration).unwrap();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+        assert_eq!(container.get(key::of::<&'static str>()).unwrap(), "str");
+    }
+
+    #[test]
+    fn fn_module_setup_fails_with_module_name() {
+        let module = from_fn_named::<_, SingletonScope>("failing", |_| Err("whatever".into()));
+
+        let err = Container::init(module).err().unwrap();
+        assert!(matches!(
+            err,
+            RegistryError::ModuleInner {
+                module: "failing",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use std::error::Error;
+
+use crate::container::registry::Configurer;
+use crate::module::Module;
+
+/// A [`Module`] whose bindings belong to a named group, which can be turned
+/// off as a unit when building a container.
+///
+/// Groups map to optional features, such as telemetry, which are additive
+/// rather than alternatives of each other. All groups are enabled by default,
+/// and [`ContainerBuilder::disable_group`] disables one, so that the wrapped
+/// module isn't set up at all. Several modules may belong to the same group.
+/// Bindings outside of a [`GroupedModule`] can be gated as well by checking
+/// [`Configurer::is_group_enabled`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::module::{self, GroupedModule};
+/// # use iocc::scope::SingletonScope;
+/// let telemetry = || {
+///     GroupedModule::new(
+///         "telemetry",
+///         module::from_fn(|configurer| {
+///             bind::<&'static str>().to_instance("otlp").set_on(configurer);
+///             Ok(())
+///         }),
+///     )
+/// };
+///
+/// let container = Container::<SingletonScope>::builder()
+///     .module(telemetry())
+///     .build()
+///     .unwrap();
+/// assert!(container.get(key::of::<&str>()).is_ok());
+///
+/// let container = Container::<SingletonScope>::builder()
+///     .disable_group("telemetry")
+///     .module(telemetry())
+///     .build()
+///     .unwrap();
+/// assert!(container.get(key::of::<&str>()).is_err());
+/// ```
+///
+/// [`ContainerBuilder::disable_group`]: crate::container::ContainerBuilder::disable_group
+pub struct GroupedModule<M: Module> {
+    group: &'static str,
+    module: M,
+}
+
+impl<M: Module> GroupedModule<M> {
+    /// Creates a new [`GroupedModule`] putting `module` into `group`.
+    pub fn new(group: &'static str, module: M) -> Self {
+        Self { group, module }
+    }
+
+    /// Returns the name of the group.
+    pub fn group(&self) -> &'static str {
+        self.group
+    }
+}
+
+impl<M: Module> Module for GroupedModule<M> {
+    type Scope = M::Scope;
+
+    fn setup(&self, configurer: &mut dyn Configurer<Scope = Self::Scope>) {
+        if configurer.is_group_enabled(self.group) {
+            self.module.setup(configurer);
+        }
+    }
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if configurer.is_group_enabled(self.group) {
+            self.module.configure(configurer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::Registry;
+    use crate::container::Container;
+    use crate::key;
+    use crate::module::{self, bind, Configuration};
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    fn make_configuration() -> Configuration<SingletonScope> {
+        Configuration::new()
+            .with(GroupedModule::new(
+                "telemetry",
+                module::from_fn(|configurer| {
+                    bind::<i32>().to_instance(1).set_on(configurer);
+                    Ok(())
+                }),
+            ))
+            .with(GroupedModule::new(
+                "cache",
+                module::from_fn(|configurer| {
+                    bind::<i64>().to_instance(2).set_on(configurer);
+                    Ok(())
+                }),
+            ))
+    }
+
+    #[test]
+    fn grouped_module_setup_succeeds() {
+        let container = Container::init(make_configuration()).unwrap();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 1);
+        assert_eq!(container.get(key::of::<i64>()).unwrap(), 2);
+
+        let container = Container::builder()
+            .disable_group("telemetry")
+            .disable_group("cache")
+            .enable_group("cache")
+            .module(make_configuration())
+            .build()
+            .unwrap();
+        assert!(container.get(key::of::<i32>()).is_err());
+        assert_eq!(container.get(key::of::<i64>()).unwrap(), 2);
+    }
+}
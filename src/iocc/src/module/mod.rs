@@ -1,4 +1,7 @@
 pub mod dsl;
+mod function;
+mod grouped;
+mod selecting;
 
 use std::error::Error;
 use std::marker::PhantomData;
@@ -8,6 +11,9 @@ use crate::scope::Scope;
 use crate::util::any::AsAny;
 
 pub use dsl::{bind, bind_key};
+pub use function::{from_fn, from_fn_named, FnModule};
+pub use grouped::GroupedModule;
+pub use selecting::{SelectingModule, SelectionError};
 
 pub trait Module: AsAny + 'static {
     type Scope: Scope;
@@ -46,6 +52,27 @@ impl<S: Scope> Configuration<S> {
         self.modules.append(&mut other.modules);
         self
     }
+
+    /// Keeps only the modules for which `pred` returns true, preserving their
+    /// order. Modules inside nested [`Configuration`]s aren't visited.
+    ///
+    /// The type of a module can be checked with [`DowncastRef::is`].
+    ///
+    /// [`DowncastRef::is`]: crate::container::DowncastRef::is
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&dyn Module<Scope = S>) -> bool,
+    {
+        self.modules.retain(|module| pred(module.as_ref()));
+    }
+
+    /// Returns true if a module of type `M` is directly contained, without
+    /// looking into nested [`Configuration`]s.
+    pub fn contains<M: Module<Scope = S>>(&self) -> bool {
+        self.modules
+            .iter()
+            .any(|module| module.as_ref().as_any().is::<M>())
+    }
 }
 
 impl<S: Scope> Module for Configuration<S> {
@@ -61,3 +88,56 @@ impl<S: Scope> Module for Configuration<S> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::Registry;
+    use crate::container::{Container, DowncastRef};
+    use crate::key;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    struct FirstModule;
+
+    impl Module for FirstModule {
+        type Scope = SingletonScope;
+
+        fn configure(
+            &self,
+            configurer: &mut dyn Configurer<Scope = Self::Scope>,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            bind::<i32>().to_instance(1).set_on(configurer);
+            Ok(())
+        }
+    }
+
+    struct SecondModule;
+
+    impl Module for SecondModule {
+        type Scope = SingletonScope;
+
+        fn configure(
+            &self,
+            configurer: &mut dyn Configurer<Scope = Self::Scope>,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            bind::<i32>().to_instance(2).set_on(configurer);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn configuration_retain_succeeds() {
+        let mut configuration = Configuration::new().with(FirstModule).with(SecondModule);
+        assert!(configuration.contains::<FirstModule>());
+        assert!(configuration.contains::<SecondModule>());
+
+        configuration.retain(|module| !module.is::<FirstModule>());
+        assert!(!configuration.contains::<FirstModule>());
+        assert!(configuration.contains::<SecondModule>());
+
+        let container = Container::init(configuration).unwrap();
+        assert_eq!(container.get(key::of::<i32>()).unwrap(), 2);
+    }
+}
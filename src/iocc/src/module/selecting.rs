@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use snafu::prelude::*;
+
+use crate::container::registry::Configurer;
+use crate::module::Module;
+use crate::scope::Scope;
+
+type Fragment<S> =
+    Box<dyn Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>>;
+
+/// A [`Module`] which installs bindings chosen at runtime from a manifest.
+///
+/// Alternatives are registered in code as binding fragments, each named and
+/// grouped by the slot it fills, e.g. the `"postgres"` and `"memory"`
+/// alternatives of a `"UserRepo"` slot. A manifest then maps slots to the
+/// names of their selected alternatives, such as `{"UserRepo": "postgres"}`,
+/// so that the wiring can be changed without recompiling. Only the selected
+/// alternatives are installed, and slots absent from the manifest are left
+/// unbound.
+///
+/// The manifest is a plain map of strings, which can be deserialized from
+/// any configuration format. Every slot and alternative it mentions must have
+/// been registered, or a [`SelectionError`] is reported by the module, which
+/// makes the container fail with a [`RegistryError`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use iocc::prelude::*;
+/// # use iocc::module::SelectingModule;
+/// # use iocc::scope::SingletonScope;
+/// let manifest = HashMap::from([("UserRepo", "postgres")]);
+///
+/// let module = SelectingModule::<SingletonScope>::new()
+///     .alternative("UserRepo", "postgres", |configurer| {
+///         bind::<&'static str>().to_instance("postgres").set_on(configurer);
+///         Ok(())
+///     })
+///     .alternative("UserRepo", "memory", |configurer| {
+///         bind::<&'static str>().to_instance("memory").set_on(configurer);
+///         Ok(())
+///     })
+///     .with_manifest(manifest);
+///
+/// let container = Container::init(module).unwrap();
+/// assert_eq!(container.get(key::of::<&str>()).unwrap(), "postgres");
+/// ```
+///
+/// [`RegistryError`]: crate::container::registry::RegistryError
+pub struct SelectingModule<S: Scope> {
+    slots: BTreeMap<String, BTreeMap<String, Fragment<S>>>,
+    manifest: BTreeMap<String, String>,
+}
+
+impl<S: Scope> SelectingModule<S> {
+    /// Creates a new [`SelectingModule`] without any alternative.
+    pub fn new() -> Self {
+        Self {
+            slots: BTreeMap::new(),
+            manifest: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the binding fragment `configure` as the alternative `name`
+    /// of `slot`, replacing any alternative previously registered with the
+    /// same name.
+    pub fn alternative<F>(
+        mut self,
+        slot: impl Into<String>,
+        name: impl Into<String>,
+        configure: F,
+    ) -> Self
+    where
+        F: Fn(&mut dyn Configurer<Scope = S>) -> Result<(), Box<dyn Error + Send + Sync>>,
+        F: 'static,
+    {
+        self.slots
+            .entry(slot.into())
+            .or_default()
+            .insert(name.into(), Box::new(configure));
+        self
+    }
+
+    /// Selects the alternative `name` of `slot`, overriding any previous
+    /// selection of the slot.
+    pub fn select(mut self, slot: impl Into<String>, name: impl Into<String>) -> Self {
+        self.manifest.insert(slot.into(), name.into());
+        self
+    }
+
+    /// Selects alternatives from `manifest`, which maps slots to the names of
+    /// their selected alternatives.
+    pub fn with_manifest<I, K, V>(mut self, manifest: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.manifest.extend(
+            manifest
+                .into_iter()
+                .map(|(slot, name)| (slot.into(), name.into())),
+        );
+        self
+    }
+
+    fn selected_fragments(&self) -> Result<Vec<&Fragment<S>>, SelectionError> {
+        self.manifest
+            .iter()
+            .map(|(slot, name)| {
+                let Some(alternatives) = self.slots.get(slot) else {
+                    return UnknownSlotSnafu { slot: slot.clone() }.fail();
+                };
+                alternatives.get(name).context(UnknownAlternativeSnafu {
+                    slot: slot.clone(),
+                    alternative: name.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl<S: Scope> Module for SelectingModule<S> {
+    type Scope = S;
+
+    fn configure(
+        &self,
+        configurer: &mut dyn Configurer<Scope = Self::Scope>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for fragment in self.selected_fragments()? {
+            fragment(configurer)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error reported by a [`SelectingModule`] whose manifest doesn't match
+/// its registered alternatives.
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum SelectionError {
+    #[snafu(display("the manifest selects an alternative of the unknown slot {slot:?}"))]
+    #[non_exhaustive]
+    UnknownSlot { slot: String },
+    #[snafu(display(
+        "the manifest selects the unknown alternative {alternative:?} of slot {slot:?}"
+    ))]
+    #[non_exhaustive]
+    UnknownAlternative { slot: String, alternative: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::{Registry, RegistryError};
+    use crate::container::Container;
+    use crate::key;
+    use crate::module::bind;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    fn make_module() -> SelectingModule<SingletonScope> {
+        SelectingModule::new()
+            .alternative("repo", "postgres", |configurer| {
+                bind::<&'static str>()
+                    .to_instance("postgres")
+                    .set_on(configurer);
+                Ok(())
+            })
+            .alternative("repo", "memory", |configurer| {
+                bind::<&'static str>()
+                    .to_instance("memory")
+                    .set_on(configurer);
+                Ok(())
+            })
+            .alternative("cache", "redis", |configurer| {
+                bind::<i32>().to_instance(6379).set_on(configurer);
+                Ok(())
+            })
+    }
+
+    #[test]
+    fn selecting_module_configure_succeeds() {
+        let module = make_module().with_manifest([("repo", "memory")]);
+
+        let container = Container::init(module).unwrap();
+        assert_eq!(container.get(key::of::<&str>()).unwrap(), "memory");
+        assert!(container.get(key::of::<i32>()).is_err());
+    }
+
+    #[test]
+    fn selecting_module_configure_fails_when_alternative_is_unknown() {
+        let module = make_module().select("repo", "mysql");
+
+        let err = Container::init(module).err().unwrap();
+        let RegistryError::ModuleInner { source, .. } = err else {
+            panic!("the error should be reported by the module");
+        };
+        assert!(matches!(
+            source.downcast_ref::<SelectionError>(),
+            Some(SelectionError::UnknownAlternative { .. })
+        ));
+
+        let module = make_module().select("queue", "kafka");
+        assert!(Container::init(module).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::container::injector::{
+    CallContext, ContextForwardingInjectorProxy, InjectorError, TypedInjector,
+};
+use crate::container::Managed;
+use crate::key::TypedKey;
+use crate::provider::TypedProvider;
+
+/// A [`Provider`] which supplies objects by resolving another key and
+/// converting the result, such as coercing an `Arc<Impl>` to an
+/// `Arc<dyn Trait>`.
+///
+/// Since the conversion can't be done generically at runtime, it's captured
+/// as a closure when the provider is created. If the aliased key refers to a
+/// shared object, the alias should be bound as a transient object, so that
+/// both keys hand out the same cached object instead of two instances.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use iocc::key;
+/// # use iocc::provider::alias::AliasProvider;
+/// trait Service: Send + Sync + 'static {}
+///
+/// struct MyService;
+///
+/// impl Service for MyService {}
+///
+/// let provider = AliasProvider::new(key::of::<Arc<MyService>>(), |service| {
+///     service as Arc<dyn Service>
+/// });
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct AliasProvider<K, T, F>
+where
+    K: TypedKey,
+    T: Managed,
+    F: Fn(K::Target) -> T + Send + Sync + 'static,
+{
+    key: K,
+    convert: Arc<F>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<K, T, F> AliasProvider<K, T, F>
+where
+    K: TypedKey,
+    T: Managed,
+    F: Fn(K::Target) -> T + Send + Sync + 'static,
+{
+    /// Creates a new [`AliasProvider`] which resolves `key` and converts the
+    /// object with `convert`.
+    pub fn new(key: K, convert: F) -> Self {
+        Self {
+            key,
+            convert: Arc::new(convert),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, T, F> Debug for AliasProvider<K, T, F>
+where
+    K: TypedKey,
+    T: Managed,
+    F: Fn(K::Target) -> T + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("AliasProvider<K, T, F>")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, T, F> TypedProvider for AliasProvider<K, T, F>
+where
+    K: TypedKey,
+    T: Managed,
+    F: Fn(K::Target) -> T + Send + Sync + 'static,
+{
+    type Output = T;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let injector = ContextForwardingInjectorProxy::new(injector, context);
+        let object = injector.get(self.key)?;
+        Ok((*self.convert)(object))
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            key: self.key,
+            convert: Arc::clone(&self.convert),
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::MockInjector;
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn alias_provider_succeeds() {
+        let mut injector = MockInjector::new();
+        injector
+            .expect_dyn_get_dependency()
+            .returning(|_, _| Ok(Box::new(Arc::new(42i32))));
+        let provider = AliasProvider::new(key::of::<Arc<i32>>(), |object| *object as i64);
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i64>()));
+        assert_eq!(res.unwrap(), 42);
+    }
+}
@@ -1,10 +1,11 @@
+use std::any::TypeId;
 use std::error::Error;
 
 use crate::container::injector::Injector;
 use crate::container::Managed;
 use crate::key;
 use crate::prelude::{InjectorError, TypedInjector};
-use crate::provider::closure::Closure;
+use crate::provider::closure::{Closure, DependencyGroup, DependencyGroups};
 
 impl<F, T, E> Closure<()> for F
 where
@@ -22,6 +23,10 @@ where
     ) -> Result<Result<Self::Constructed, Self::Error>, InjectorError> {
         Ok(self())
     }
+
+    fn is_leaf(&self) -> bool {
+        true
+    }
 }
 
 macro_rules! for_all_tuples {
@@ -68,12 +73,54 @@ macro_rules! impl_closure {
                 )*
                 Ok(self($($dep,)*))
             }
+
+            fn dependency_types(&self) -> Vec<TypeId> {
+                vec![$(TypeId::of::<$dep>(),)*]
+            }
         }
     };
 }
 
 for_all_tuples!(impl_closure);
 
+macro_rules! impl_dependency_group {
+    ($($dep:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($dep,)*> DependencyGroup for ($($dep,)*)
+        where
+            $($dep: Managed,)*
+        {
+            fn resolve(injector: &dyn Injector) -> Result<Self, InjectorError> {
+                $(
+                    let $dep = injector.get(key::of())?;
+                )*
+                Ok(($($dep,)*))
+            }
+        }
+    };
+}
+
+for_all_tuples!(impl_dependency_group);
+
+macro_rules! impl_dependency_groups {
+    ($($group:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($group,)*> DependencyGroups for ($($group,)*)
+        where
+            $($group: DependencyGroup,)*
+        {
+            fn resolve(injector: &dyn Injector) -> Result<Self, InjectorError> {
+                $(
+                    let $group = $group::resolve(injector)?;
+                )*
+                Ok(($($group,)*))
+            }
+        }
+    };
+}
+
+for_all_tuples!(impl_dependency_groups);
+
 #[cfg(test)]
 mod tests {
     use std::convert::Infallible;
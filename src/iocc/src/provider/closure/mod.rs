@@ -2,6 +2,7 @@ mod closure_implementation;
 mod raw_wrapper;
 mod wrapper;
 
+use std::any::TypeId;
 use std::error::Error;
 
 use crate::container::injector::{Injector, InjectorError};
@@ -24,9 +25,13 @@ pub use wrapper::ClosureProvider;
 /// Theoretically, a [`Closure`] can be implemented by any closure of arbitrary
 /// arity whose arguments are all [`Managed`]. Due to the lack of support for
 /// functions of variable length parameters, [`Closure`] is only implemented by
-/// any function whose arity is at most 16.
+/// any function whose arity is at most 16. For wider constructors, use
+/// `to_closure_tuple()` [`dsl`]s with a closure accepting
+/// [`DependencyGroups`], or the [`component`] macro, which has no limit on
+/// the number of arguments.
 ///
 /// [`dsl`]: crate::module::dsl
+/// [`component`]: crate::component
 pub trait Closure<D>
 where
     Self: Send + Sync + 'static,
@@ -52,6 +57,71 @@ where
         &self,
         injector: &dyn Injector,
     ) -> Result<Result<Self::Constructed, Self::Error>, InjectorError>;
+
+    /// Returns true if the closure doesn't accept any argument, i.e. never
+    /// retrieves any dependency from the injector.
+    fn is_leaf(&self) -> bool {
+        false
+    }
+
+    /// Returns the [`TypeId`]s of the arguments fetched from the injector,
+    /// all of which are retrieved without a qualifier. Closures whose
+    /// dependencies aren't known return an empty list.
+    fn dependency_types(&self) -> Vec<TypeId> {
+        Vec::new()
+    }
+}
+
+/// A tuple of dependencies which can be retrieved from an [`Injector`]
+/// member-wise, each without specifying a qualifier.
+///
+/// It's implemented for tuples of at most 16 [`Managed`] members. Members
+/// which are tuples themselves are retrieved as a whole rather than
+/// member-wise.
+pub trait DependencyGroup: Sized {
+    /// Retrieves all members from the injector.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any member can't be fetched.
+    fn resolve(injector: &dyn Injector) -> Result<Self, InjectorError>;
+}
+
+/// A tuple of [`DependencyGroup`]s, which is accepted by closures bound with
+/// `to_closure_tuple()` [`dsl`]s to get around the arity limit of
+/// [`Closure`].
+///
+/// It's implemented for tuples of at most 16 groups, so that a closure can
+/// receive up to 256 dependencies:
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// let module = module::from_fn(|configurer| {
+///     bind::<i32>().to_instance(1).set_on(configurer);
+///     bind::<i64>().to_instance(2).set_on(configurer);
+///     bind::<u32>().to_instance(3).set_on(configurer);
+///     bind::<String>()
+///         .to_closure_tuple(|((a, b), (c,)): ((i32, i64), (u32,))| {
+///             Ok::<_, Infallible>(format!("{a}{b}{c}"))
+///         })
+///         .set_on(configurer);
+///     Ok(())
+/// });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+/// assert_eq!(container.get(key::of::<String>()).unwrap(), "123");
+/// ```
+///
+/// [`dsl`]: crate::module::dsl
+pub trait DependencyGroups: Sized {
+    /// Retrieves all groups from the injector.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any member of any group can't be fetched.
+    fn resolve(injector: &dyn Injector) -> Result<Self, InjectorError>;
 }
 
 /// A specialized form of [`Fn`] which directly accepts an [`Injector`] and
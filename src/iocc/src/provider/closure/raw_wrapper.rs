@@ -24,7 +24,7 @@ where
     T: Managed,
     C: RawClosure<Constructed = T>,
 {
-    closure: C,
+    closure: Arc<C>,
 }
 
 impl<T, C> RawClosureProvider<T, C>
@@ -34,7 +34,21 @@ where
 {
     /// Creates a new [`RawClosureProvider`] from a [`RawClosure`].
     pub fn new(closure: C) -> Self {
-        Self { closure }
+        Self {
+            closure: Arc::new(closure),
+        }
+    }
+}
+
+impl<T, C> Clone for RawClosureProvider<T, C>
+where
+    T: Managed,
+    C: RawClosure<Constructed = T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            closure: Arc::clone(&self.closure),
+        }
     }
 }
 
@@ -65,7 +79,7 @@ where
         I: TypedInjector + ?Sized,
     {
         let injector = ContextForwardingInjectorProxy::new(injector, context);
-        match (self.closure)(&injector) {
+        match (*self.closure)(&injector) {
             Ok(Ok(obj)) => Ok(obj),
             Ok(Err(err)) => Err(InjectorError::ObjectConstruction {
                 key: context.key().dyn_clone(),
@@ -74,6 +88,10 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
 impl<T, C> TypedSharedProvider for RawClosureProvider<T, C>
@@ -103,4 +121,16 @@ mod tests {
         let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
         assert_eq!(res.unwrap(), 42);
     }
+
+    #[test]
+    fn closure_provider_clone_succeeds() {
+        let injector = MockInjector::new();
+        let provider = RawClosureProvider::new(|_| Ok(Ok::<_, Infallible>(42i32)));
+        let cloned = provider.clone();
+
+        let key = key::of::<i32>();
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        let cloned_res = cloned.provide(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap(), cloned_res.unwrap());
+    }
 }
@@ -29,7 +29,7 @@ where
     C: Closure<D, Constructed = T>,
     D: Send + Sync + 'static,
 {
-    closure: C,
+    closure: Arc<C>,
     _marker: PhantomData<(T, D)>,
 }
 
@@ -42,7 +42,21 @@ where
     /// Creates a new [`ClosureProvider`] from a [`Closure`].
     pub fn new(closure: C) -> Self {
         Self {
-            closure,
+            closure: Arc::new(closure),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C, D> Clone for ClosureProvider<T, C, D>
+where
+    T: Managed,
+    C: Closure<D, Constructed = T>,
+    D: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            closure: Arc::clone(&self.closure),
             _marker: PhantomData,
         }
     }
@@ -86,6 +100,14 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn is_leaf(&self) -> bool {
+        self.closure.is_leaf()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
 impl<T, C, D> TypedSharedProvider for ClosureProvider<T, C, D>
@@ -119,5 +141,25 @@ mod tests {
 
         let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
         assert_eq!(res.unwrap(), 42);
+
+        assert!(!provider.is_leaf());
+        assert!(ClosureProvider::new(|| Ok::<_, Infallible>(42i32)).is_leaf());
+    }
+
+    #[test]
+    fn closure_provider_clone_succeeds() {
+        let mut injector = MockInjector::new();
+        injector
+            .expect_dyn_get_dependency()
+            .returning(|_, _| Ok(Box::new(42i32)));
+
+        let provider = ClosureProvider::new(|v: i32| Ok::<_, Infallible>(v + 1));
+        let cloned = provider.clone();
+
+        let key = key::of::<i32>();
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        let cloned_res = cloned.provide(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap(), 43);
+        assert_eq!(cloned_res.unwrap(), 43);
     }
 }
@@ -0,0 +1,151 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::container::injector::{
+    CallContext, ContextForwardingInjectorProxy, InjectorError, TypedInjector,
+};
+use crate::provider::component::ComponentWith;
+use crate::provider::{DependencyDescriptor, TypedProvider};
+
+/// A [`Provider`] which supplies objects by using their component
+/// constructors, passing a value created by a closure as the extra argument.
+///
+/// The closure is called once for each request. This provider never supplies
+/// shared objects, since objects constructed with different extra values
+/// can't be cached under the same key.
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct ComponentWithProvider<C, X, F>
+where
+    C: ComponentWith<X>,
+    X: 'static,
+    F: Fn() -> X + Send + Sync + 'static,
+{
+    extra: Arc<F>,
+    _marker: PhantomData<fn(X) -> C>,
+}
+
+impl<C, X, F> ComponentWithProvider<C, X, F>
+where
+    C: ComponentWith<X>,
+    X: 'static,
+    F: Fn() -> X + Send + Sync + 'static,
+{
+    /// Creates a new [`ComponentWithProvider`] which creates the extra
+    /// argument with `extra`.
+    pub fn new(extra: F) -> Self {
+        Self {
+            extra: Arc::new(extra),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C, X, F> Debug for ComponentWithProvider<C, X, F>
+where
+    C: ComponentWith<X>,
+    X: 'static,
+    F: Fn() -> X + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ComponentWithProvider<C, X, F>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, X, F> TypedProvider for ComponentWithProvider<C, X, F>
+where
+    C: ComponentWith<X>,
+    X: 'static,
+    F: Fn() -> X + Send + Sync + 'static,
+{
+    type Output = C::Constructed;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let injector = ContextForwardingInjectorProxy::new(injector, context);
+        match C::construct_with(&injector, (self.extra)())
+            .map(|res| res.and_then(|obj| obj.try_post_process()))
+        {
+            Ok(Ok(obj)) => Ok(obj),
+            Ok(Err(err)) => Err(InjectorError::ObjectConstruction {
+                key: context.key().dyn_clone(),
+                source: Arc::from(err.into()),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        C::is_leaf()
+    }
+
+    fn dependencies(&self) -> Vec<DependencyDescriptor> {
+        C::dependencies()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            extra: Arc::clone(&self.extra),
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+
+    use super::*;
+
+    struct Token(u64);
+
+    struct Session {
+        token: u64,
+    }
+
+    impl ComponentWith<Token> for Session {
+        type Constructed = Self;
+
+        type Error = Infallible;
+
+        fn construct_with<I>(
+            _injector: &I,
+            extra: Token,
+        ) -> Result<Result<Self, Self::Error>, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Ok(Self { token: extra.0 }))
+        }
+
+        fn post_process(self) -> Self::Constructed {
+            self
+        }
+    }
+
+    #[test]
+    fn component_with_provider_succeeds() {
+        let injector = MockInjector::new();
+        let counter = AtomicU64::new(0);
+        let provider = ComponentWithProvider::<Session, _, _>::new(move || {
+            Token(counter.fetch_add(1, Ordering::SeqCst))
+        });
+
+        let key = key::of::<Session>();
+        let context = CallContext::new(&key);
+        assert_eq!(provider.provide(&injector, &context).unwrap().token, 0);
+        assert_eq!(provider.provide(&injector, &context).unwrap().token, 1);
+    }
+}
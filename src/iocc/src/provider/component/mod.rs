@@ -1,10 +1,15 @@
+mod extra;
+mod mutable;
 mod wrapper;
 
 use std::error::Error;
 
 use crate::container::injector::{InjectorError, TypedInjector};
 use crate::container::Managed;
+use crate::provider::DependencyDescriptor;
 
+pub use extra::ComponentWithProvider;
+pub use mutable::SharedMutableProvider;
 pub use wrapper::ComponentProvider;
 
 /// A type that has a dedicated constructor for dependency injection.
@@ -55,6 +60,14 @@ pub use wrapper::ComponentProvider;
 /// crate. Or wrap your [`Component`] in a [`ComponentProvider`] if you need
 /// low-level control.
 ///
+/// When a component relies on side effects of constructing another object
+/// without using it, such as a registration of some global state, annotate
+/// its constructor with `#[depends_on(...)]` listing the types of those
+/// objects. The [`component`] macro then retrieves them without qualifiers
+/// before any argument is resolved. This only makes sense for shared objects,
+/// especially singletons, since a transient object is constructed again on
+/// each request and immediately dropped.
+///
 /// [`component`]: crate::component
 /// [`dsl`]: crate::module::dsl
 pub trait Component: Managed + Sized {
@@ -81,6 +94,145 @@ pub trait Component: Managed + Sized {
     /// Converts `self` to [`Component::Constructed`]. Typical usages are
     /// putting `self` to an [`Arc`] and coercing it to an `Arc<dyn Trait>`.
     ///
+    /// Providers never call this directly but go through
+    /// [`Component::try_post_process`].
+    ///
+    /// # Panics
+    ///
+    /// The implementation generated by the [`component`] macro for a
+    /// `fallible` post-processor panics if the post-processor returns an
+    /// error. Call [`Component::try_post_process`] to handle it instead.
+    ///
     /// [`Arc`]: std::sync::Arc
+    /// [`component`]: crate::component
+    fn post_process(self) -> Self::Constructed;
+
+    /// Converts `self` to [`Component::Constructed`] like
+    /// [`Component::post_process`], but is allowed to fail, e.g. when
+    /// validating the fully-built object. Providers call this rather than
+    /// [`Component::post_process`].
+    ///
+    /// The default implementation never fails. The [`component`] macro
+    /// implements this if the `fallible` argument is given after the output
+    /// type and the post-processor function, which returns a [`Result`] in
+    /// that case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Component::Error`] if the object is rejected.
+    ///
+    /// [`component`]: crate::component
+    fn try_post_process(self) -> Result<Self::Constructed, Self::Error> {
+        Ok(self.post_process())
+    }
+
+    /// Returns true if [`Component::construct`] never retrieves any
+    /// dependency from the injector. The [`component`] macro implements this
+    /// for constructors without any argument.
+    ///
+    /// [`component`]: crate::component
+    fn is_leaf() -> bool {
+        false
+    }
+
+    /// Returns descriptions of the dependencies retrieved by
+    /// [`Component::construct`]. The [`component`] macro implements this from
+    /// the constructor's arguments and `#[depends_on(...)]` types.
+    ///
+    /// [`component`]: crate::component
+    fn dependencies() -> Vec<DependencyDescriptor> {
+        Vec::new()
+    }
+}
+
+/// A type whose constructor takes a value of type `X` that can't be retrieved
+/// from containers, such as a per-call token, in addition to its injected
+/// dependencies.
+///
+/// The [`component`] macro implements this trait instead of [`Component`] if
+/// one constructor argument is annotated with `#[extra]`:
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::provider::component::ComponentWith;
+/// pub struct Token(pub u64);
+///
+/// pub struct Session {
+///     token: Token,
+///     name: String,
+/// }
+///
+/// #[component]
+/// impl Session {
+///     #[inject]
+///     pub fn new(#[extra] token: Token, name: String) -> Self {
+///         Self { token, name }
+///     }
+/// }
+///
+/// fn assert_component_with<C: ComponentWith<Token>>() {}
+/// assert_component_with::<Session>();
+/// ```
+///
+/// Use `to_component_with()` [`dsl`]s or a [`ComponentWithProvider`] to bind
+/// it. Since each object is constructed with a fresh extra value, it can only
+/// be bound as a transient object.
+///
+/// Constructors annotated with `#[depends_on(...)]` are supported as described
+/// in [`Component`].
+///
+/// [`component`]: crate::component
+/// [`dsl`]: crate::module::dsl
+pub trait ComponentWith<X>: Managed + Sized {
+    /// The successfully constructed object, like [`Component::Constructed`].
+    type Constructed: Managed;
+
+    /// The error occurred in object construction after all dependencies are
+    /// retrieved.
+    type Error: Into<Box<dyn Error + Send + Sync>>;
+
+    /// Retrieves the dependencies from the injector and creates the object
+    /// with `extra`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all dependencies can't be fetched.
+    ///
+    /// Returns an inner error [`ComponentWith::Error`] wrapped in the outer
+    /// [`Ok`] if the object construction fails.
+    fn construct_with<I>(
+        injector: &I,
+        extra: X,
+    ) -> Result<Result<Self, Self::Error>, InjectorError>
+    where
+        I: TypedInjector + ?Sized;
+
+    /// Converts `self` to [`ComponentWith::Constructed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Component::post_process`].
     fn post_process(self) -> Self::Constructed;
+
+    /// Converts `self` to [`ComponentWith::Constructed`], like
+    /// [`Component::try_post_process`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ComponentWith::Error`] if the object is rejected.
+    fn try_post_process(self) -> Result<Self::Constructed, Self::Error> {
+        Ok(self.post_process())
+    }
+
+    /// Returns true if [`ComponentWith::construct_with`] never retrieves any
+    /// dependency from the injector.
+    fn is_leaf() -> bool {
+        false
+    }
+
+    /// Returns descriptions of the dependencies retrieved by
+    /// [`ComponentWith::construct_with`], like [`Component::dependencies`].
+    fn dependencies() -> Vec<DependencyDescriptor> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,150 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Arc, Mutex};
+
+use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+use crate::provider::component::{Component, ComponentProvider};
+use crate::provider::{DependencyDescriptor, TypedProvider, TypedSharedProvider};
+
+/// A [`Provider`] which supplies components wrapped in an `Arc<Mutex<_>>`,
+/// allowing shared mutable access to them.
+///
+/// The component is constructed by a [`ComponentProvider`] and then moved
+/// into a [`Mutex`]. Bound within a scope, all users of the key get the same
+/// object and observe each other's modifications.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::{Arc, Mutex};
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// #[derive(Default)]
+/// struct Counter {
+///     value: i32,
+/// }
+///
+/// #[component(default)]
+/// impl Counter {}
+///
+/// let module = module::from_fn(|configurer| {
+///     bind::<Arc<Mutex<Counter>>>()
+///         .within(SingletonScope)
+///         .to_shared_mutable::<Counter>()
+///         .set_on(configurer);
+///     Ok(())
+/// });
+///
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+/// let counter = container.get(key::of::<Arc<Mutex<Counter>>>()).unwrap();
+/// counter.lock().unwrap().value += 1;
+///
+/// let counter = container.get(key::of::<Arc<Mutex<Counter>>>()).unwrap();
+/// assert_eq!(counter.lock().unwrap().value, 1);
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct SharedMutableProvider<C>
+where
+    C: Component<Constructed: Send>,
+{
+    inner: ComponentProvider<C>,
+}
+
+impl<C> SharedMutableProvider<C>
+where
+    C: Component<Constructed: Send>,
+{
+    /// Creates a new [`SharedMutableProvider`].
+    pub fn new() -> Self {
+        Self {
+            inner: ComponentProvider::new(),
+        }
+    }
+}
+
+impl<C> Debug for SharedMutableProvider<C>
+where
+    C: Component<Constructed: Send>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SharedMutableProvider<C>")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<C> TypedProvider for SharedMutableProvider<C>
+where
+    C: Component<Constructed: Send>,
+{
+    type Output = Arc<Mutex<C::Constructed>>;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let object = self.inner.provide(injector, context)?;
+        Ok(Arc::new(Mutex::new(object)))
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.inner.is_leaf()
+    }
+
+    fn dependencies(&self) -> Vec<DependencyDescriptor> {
+        self.inner.dependencies()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self::new())
+    }
+}
+
+impl<C> TypedSharedProvider for SharedMutableProvider<C> where C: Component<Constructed: Send> {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+
+    use super::*;
+
+    struct Counter(i32);
+
+    impl Component for Counter {
+        type Constructed = Self;
+
+        type Error = Infallible;
+
+        fn construct<I>(_injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Ok(Self(0)))
+        }
+
+        fn post_process(self) -> Self::Constructed {
+            self
+        }
+    }
+
+    #[test]
+    fn shared_mutable_provider_succeeds() {
+        let injector = MockInjector::new();
+        let provider = SharedMutableProvider::<Counter>::new();
+
+        let key = key::of::<Arc<Mutex<Counter>>>();
+        let object = provider
+            .provide(&injector, &CallContext::new(&key))
+            .unwrap();
+        object.lock().unwrap().0 += 1;
+        assert_eq!(object.lock().unwrap().0, 1);
+    }
+}
@@ -7,7 +7,7 @@ use crate::container::injector::{
 };
 use crate::container::SharedManaged;
 use crate::provider::component::Component;
-use crate::provider::{TypedProvider, TypedSharedProvider};
+use crate::provider::{DependencyDescriptor, TypedProvider, TypedSharedProvider};
 
 /// A [`Provider`] which supplies objects by using their component
 /// constructors.
@@ -57,8 +57,8 @@ where
         I: TypedInjector + ?Sized,
     {
         let injector = ContextForwardingInjectorProxy::new(injector, context);
-        match C::construct(&injector) {
-            Ok(Ok(obj)) => Ok(obj.post_process()),
+        match C::construct(&injector).map(|res| res.and_then(|obj| obj.try_post_process())) {
+            Ok(Ok(obj)) => Ok(obj),
             Ok(Err(err)) => Err(InjectorError::ObjectConstruction {
                 key: context.key().dyn_clone(),
                 source: Arc::from(err.into()),
@@ -66,6 +66,18 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn is_leaf(&self) -> bool {
+        C::is_leaf()
+    }
+
+    fn dependencies(&self) -> Vec<DependencyDescriptor> {
+        C::dependencies()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self::new())
+    }
 }
 
 impl<C> TypedSharedProvider for ComponentProvider<C> where C: Component<Constructed: SharedManaged> {}
@@ -73,8 +85,11 @@ impl<C> TypedSharedProvider for ComponentProvider<C> where C: Component<Construc
 #[cfg(test)]
 mod tests {
     use std::convert::Infallible;
+    use std::fmt;
     use std::sync::Arc;
 
+    use snafu::prelude::*;
+
     use crate::container::injector::MockInjector;
     use crate::key;
     use crate::provider::SharedProvider;
@@ -118,5 +133,78 @@ mod tests {
         assert_is_shared_provider(&provider);
     }
 
+    #[test]
+    fn component_provider_fails_when_construction_fails() {
+        let injector = MockInjector::new();
+        let provider = ComponentProvider::<Failing>::new();
+        let err = provider
+            .provide(&injector, &CallContext::new(&key::of::<Failing>()))
+            .unwrap_err();
+
+        assert_eq!(err.construction_source::<FailingError>().unwrap().code, 42);
+        assert!(err.construction_source::<fmt::Error>().is_none());
+    }
+
+    #[test]
+    fn component_provider_fails_when_post_processing_fails() {
+        let injector = MockInjector::new();
+        let provider = ComponentProvider::<Rejected>::new();
+        let err = provider
+            .provide(&injector, &CallContext::new(&key::of::<Rejected>()))
+            .unwrap_err();
+
+        assert_eq!(err.construction_source::<FailingError>().unwrap().code, 0);
+    }
+
+    #[derive(Debug)]
+    struct Failing;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("fails with code {code}"))]
+    struct FailingError {
+        code: i32,
+    }
+
+    impl Component for Failing {
+        type Constructed = Self;
+
+        type Error = FailingError;
+
+        fn construct<I>(_injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Err(FailingError { code: 42 }))
+        }
+
+        fn post_process(self) -> Self::Constructed {
+            self
+        }
+    }
+
+    #[derive(Debug)]
+    struct Rejected;
+
+    impl Component for Rejected {
+        type Constructed = Self;
+
+        type Error = FailingError;
+
+        fn construct<I>(_injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(Ok(Rejected))
+        }
+
+        fn post_process(self) -> Self::Constructed {
+            self
+        }
+
+        fn try_post_process(self) -> Result<Self::Constructed, Self::Error> {
+            Err(FailingError { code: 0 })
+        }
+    }
+
     fn assert_is_shared_provider(_: &dyn SharedProvider) {}
 }
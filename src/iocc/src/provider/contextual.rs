@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::container::injector::{
+    CallContext, ContextForwardingInjectorProxy, Injector, InjectorError, TypedInjector,
+};
+use crate::container::Managed;
+use crate::key::Key;
+use crate::provider::TypedProvider;
+
+/// A [`Provider`] which supplies objects from a closure aware of the
+/// injection point, i.e. the key of the object requesting it.
+///
+/// The closure has the same shape as a [`RawClosure`], except that it also
+/// receives the key of the object depending on the requested one, or
+/// [`None`] if the object is requested directly from a container. This
+/// allows constructing objects customized for their users, such as loggers
+/// named after the components they're injected into.
+///
+/// Since the constructed object depends on its user, a [`ContextualProvider`]
+/// can only be used for transient objects.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use iocc::provider::contextual::ContextualProvider;
+/// let provider = ContextualProvider::new(|_injector, requester| {
+///     let name = requester.map_or(String::from("root"), |key| key.to_string());
+///     Ok(Ok::<_, Infallible>(name))
+/// });
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+/// [`RawClosure`]: crate::provider::closure::RawClosure
+pub struct ContextualProvider<T, E, F>
+where
+    T: Managed,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector, Option<&dyn Key>) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    closure: Arc<F>,
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E, F> ContextualProvider<T, E, F>
+where
+    T: Managed,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector, Option<&dyn Key>) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    /// Creates a new [`ContextualProvider`] from a closure.
+    pub fn new(closure: F) -> Self {
+        Self {
+            closure: Arc::new(closure),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E, F> Debug for ContextualProvider<T, E, F>
+where
+    T: Managed,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector, Option<&dyn Key>) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ContextualProvider<T, E, F>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, F> TypedProvider for ContextualProvider<T, E, F>
+where
+    T: Managed,
+    E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    F: Fn(&dyn Injector, Option<&dyn Key>) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    type Output = T;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let injector = ContextForwardingInjectorProxy::new(injector, context);
+        match (*self.closure)(&injector, context.requester()) {
+            Ok(Ok(obj)) => Ok(obj),
+            Ok(Err(err)) => Err(InjectorError::ObjectConstruction {
+                key: context.key().dyn_clone(),
+                source: Arc::from(err.into()),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            closure: Arc::clone(&self.closure),
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn contextual_provider_succeeds() {
+        let injector = MockInjector::new();
+        let provider = ContextualProvider::new(|_, requester| {
+            let name = requester.map_or(String::from("root"), |key| key.to_string());
+            Ok(Ok::<_, Infallible>(name))
+        });
+
+        let key = key::of::<String>();
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap(), "root");
+
+        let requester = key::named::<i32>("requester");
+        let context = CallContext::new(&requester);
+        let res = provider.provide(&injector, &context.append(&key));
+        assert_eq!(res.unwrap(), "i32@\"requester\"");
+    }
+}
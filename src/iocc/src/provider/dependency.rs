@@ -0,0 +1,169 @@
+use std::any::TypeId;
+
+use crate::container::injector::Collect;
+use crate::container::Managed;
+use crate::key::{AnyPattern, Key, KeyTypePattern, TypedQualifier};
+
+/// A description of one dependency retrieved by a provider, which allows
+/// inspecting and validating the dependency graph without constructing any
+/// object.
+///
+/// Descriptors of components are generated by the [`component`] macro from
+/// the constructor's arguments, and reported by [`Provider::dyn_dependencies`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::any::TypeId;
+/// # use std::sync::Arc;
+/// # use iocc::prelude::*;
+/// # use iocc::provider::component::Component;
+/// # use iocc::provider::{DependencyDescriptor, QualifierKind};
+/// struct Service;
+///
+/// #[component]
+/// impl Service {
+///     #[inject]
+///     fn new(#[named("port")] _port: u16, #[collect(any)] _names: Vec<Arc<str>>) -> Self {
+///         Self
+///     }
+/// }
+///
+/// assert_eq!(
+///     Service::dependencies(),
+///     [
+///         DependencyDescriptor::named::<u16>("port"),
+///         DependencyDescriptor {
+///             target: TypeId::of::<Arc<str>>(),
+///             qualifier: QualifierKind::Any,
+///             collect: true,
+///         },
+///     ]
+/// );
+/// ```
+///
+/// [`component`]: crate::component
+/// [`Provider::dyn_dependencies`]: crate::provider::Provider::dyn_dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DependencyDescriptor {
+    /// The target type of the dependency, or of each collected object if
+    /// `collect` is true.
+    pub target: TypeId,
+    /// How the dependency is qualified.
+    pub qualifier: QualifierKind,
+    /// Whether all matching objects are collected rather than a single one
+    /// retrieved.
+    pub collect: bool,
+}
+
+impl DependencyDescriptor {
+    /// Describes a single unqualified dependency of type `T`.
+    pub fn of<T: Managed>() -> Self {
+        Self::single::<T>(QualifierKind::None)
+    }
+
+    /// Describes a single dependency of type `T` qualified by `name`.
+    pub fn named<T: Managed>(name: &'static str) -> Self {
+        Self::single::<T>(QualifierKind::Named(name))
+    }
+
+    /// Describes a single dependency of type `T` qualified by a value of type
+    /// `Q`.
+    pub fn qualified<T, Q>(_qualifier: Q) -> Self
+    where
+        T: Managed,
+        Q: TypedQualifier,
+    {
+        Self::single::<T>(QualifierKind::Typed(TypeId::of::<Q>()))
+    }
+
+    /// Describes the collection `C` of objects of type `T` with any
+    /// qualifier.
+    pub fn collect_any<C, T>() -> Self
+    where
+        C: Collect<AnyPattern<T>>,
+        T: Managed,
+    {
+        Self {
+            target: TypeId::of::<T>(),
+            qualifier: QualifierKind::Any,
+            collect: true,
+        }
+    }
+
+    /// Describes the collection `C` of objects of type `T` qualified by
+    /// values of type `Q`.
+    pub fn collect_key_type<C, T, Q>() -> Self
+    where
+        C: Collect<KeyTypePattern<T, Q>>,
+        T: Managed,
+        Q: TypedQualifier,
+    {
+        Self {
+            target: TypeId::of::<T>(),
+            qualifier: QualifierKind::Typed(TypeId::of::<Q>()),
+            collect: true,
+        }
+    }
+
+    /// Returns true if the object identified by `key` satisfies the
+    /// dependency, or is one of the collected objects if `collect` is true.
+    pub fn matches(&self, key: &dyn Key) -> bool {
+        if key.target_type() != self.target {
+            return false;
+        }
+        match self.qualifier {
+            QualifierKind::None => key.qualifier_type() == TypeId::of::<()>(),
+            QualifierKind::Named(name) => key
+                .dyn_qualifier()
+                .as_any()
+                .downcast_ref::<&'static str>()
+                .is_some_and(|qualifier| *qualifier == name),
+            QualifierKind::Typed(qualifier) => key.qualifier_type() == qualifier,
+            QualifierKind::Any => true,
+        }
+    }
+
+    fn single<T: Managed>(qualifier: QualifierKind) -> Self {
+        Self {
+            target: TypeId::of::<T>(),
+            qualifier,
+            collect: false,
+        }
+    }
+}
+
+/// How a dependency described by a [`DependencyDescriptor`] is qualified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QualifierKind {
+    /// The dependency isn't qualified.
+    None,
+    /// The dependency is qualified by a name.
+    Named(&'static str),
+    /// The dependency is qualified by a value of the qualifier type with the
+    /// given [`TypeId`].
+    Typed(TypeId),
+    /// Objects with any qualifier are collected.
+    Any,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn dependency_descriptor_matches_succeeds() {
+        let descriptor = DependencyDescriptor::named::<u16>("port");
+        assert!(descriptor.matches(&key::named::<u16>("port")));
+        assert!(!descriptor.matches(&key::named::<u16>("host")));
+        assert!(!descriptor.matches(&key::named::<u32>("port")));
+        assert!(!descriptor.matches(&key::of::<u16>()));
+
+        let descriptor = DependencyDescriptor::of::<u16>();
+        assert!(descriptor.matches(&key::of::<u16>()));
+        assert!(!descriptor.matches(&key::named::<u16>("port")));
+    }
+}
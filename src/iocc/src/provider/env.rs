@@ -0,0 +1,155 @@
+use std::borrow::Cow;
+use std::env::{self, VarError};
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+use crate::container::Managed;
+use crate::provider::TypedProvider;
+
+/// A [`Provider`] which supplies objects parsed from an environment variable.
+///
+/// The variable is read on each request rather than when the provider is
+/// created, and parsed into the target type via [`FromStr`]. If the variable
+/// is absent, the default value is supplied if there is one. Failures of
+/// reading or parsing the variable are reported as
+/// [`InjectorError::ObjectConstruction`].
+///
+/// This module is only available with the `env` feature enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::provider::env::EnvProvider;
+/// let provider = EnvProvider::<u16>::new("PORT").with_default(8080);
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct EnvProvider<T>
+where
+    T: Managed + FromStr<Err: Into<Box<dyn Error + Send + Sync>>> + Clone,
+{
+    var: Cow<'static, str>,
+    default: Option<T>,
+}
+
+impl<T> EnvProvider<T>
+where
+    T: Managed + FromStr<Err: Into<Box<dyn Error + Send + Sync>>> + Clone,
+{
+    /// Creates a new [`EnvProvider`] reading the environment variable `var`
+    /// without any default value.
+    pub fn new(var: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            var: var.into(),
+            default: None,
+        }
+    }
+
+    /// Sets the value supplied when the environment variable is absent.
+    pub fn with_default(mut self, default: T) -> Self {
+        self.default = Some(default);
+        self
+    }
+}
+
+impl<T> Debug for EnvProvider<T>
+where
+    T: Managed + FromStr<Err: Into<Box<dyn Error + Send + Sync>>> + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("EnvProvider<T>")
+            .field("var", &self.var)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> TypedProvider for EnvProvider<T>
+where
+    T: Managed + FromStr<Err: Into<Box<dyn Error + Send + Sync>>> + Clone,
+{
+    type Output = T;
+
+    fn provide<I>(
+        &self,
+        _injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let res = match (env::var(self.var.as_ref()), self.default.as_ref()) {
+            (Ok(value), _) => value.parse().map_err(Into::into),
+            (Err(VarError::NotPresent), Some(default)) => Ok(default.clone()),
+            (Err(err), _) => Err(err.into()),
+        };
+        res.map_err(|err| InjectorError::ObjectConstruction {
+            key: context.key().dyn_clone(),
+            source: Arc::from(err),
+        })
+    }
+
+    fn is_leaf(&self) -> bool {
+        true
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            var: self.var.clone(),
+            default: self.default.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::injector::MockInjector;
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn env_provider_succeeds() {
+        env::set_var("IOCC_ENV_PROVIDER_SUCCEEDS", "42");
+        let provider = EnvProvider::<u16>::new("IOCC_ENV_PROVIDER_SUCCEEDS").with_default(8080);
+        let injector = MockInjector::new();
+        let key = key::of::<u16>();
+
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap(), 42);
+        assert!(provider.is_leaf());
+    }
+
+    #[test]
+    fn env_provider_succeeds_with_default() {
+        let provider = EnvProvider::<u16>::new("IOCC_ENV_PROVIDER_ABSENT").with_default(8080);
+        let injector = MockInjector::new();
+        let key = key::of::<u16>();
+
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap(), 8080);
+    }
+
+    #[test]
+    fn env_provider_fails_when_value_is_invalid() {
+        env::set_var("IOCC_ENV_PROVIDER_INVALID", "not a port");
+        let provider = EnvProvider::<u16>::new("IOCC_ENV_PROVIDER_INVALID").with_default(8080);
+        let injector = MockInjector::new();
+        let key = key::of::<u16>();
+
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        assert!(matches!(res, Err(InjectorError::ObjectConstruction { .. })));
+    }
+
+    #[test]
+    fn env_provider_fails_when_variable_is_absent() {
+        let provider = EnvProvider::<String>::new("IOCC_ENV_PROVIDER_ABSENT");
+        let injector = MockInjector::new();
+        let key = key::of::<String>();
+
+        let res = provider.provide(&injector, &CallContext::new(&key));
+        assert!(matches!(res, Err(InjectorError::ObjectConstruction { .. })));
+    }
+}
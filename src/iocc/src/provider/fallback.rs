@@ -0,0 +1,256 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use snafu::Snafu;
+
+use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+use crate::container::{Managed, SharedManaged};
+use crate::provider::{Provider, TypedProvider, TypedSharedProvider};
+use crate::util::any::Downcast;
+
+/// A [`Provider`] which tries a chain of alternative providers in order, and
+/// supplies the object from the first one that succeeds.
+///
+/// Only [`InjectorError::ObjectConstruction`] makes the chain fall back to
+/// the next provider. Other errors, such as [`InjectorError::NotFound`] and
+/// [`InjectorError::CyclicDependency`], are returned immediately, since they
+/// indicate a misconfiguration that the remaining providers can't fix. If all
+/// providers fail, the error of the last one is returned, and an empty chain
+/// always fails with [`InjectorError::ObjectConstruction`] caused by an
+/// [`EmptyFallbackChainError`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::provider::closure::RawClosureProvider;
+/// # use iocc::provider::fallback::FallbackProvider;
+/// # use iocc::provider::instance::InstanceProvider;
+/// let provider = FallbackProvider::new()
+///     .or(RawClosureProvider::new(|_injector| Ok(Err::<i32, _>("unavailable"))))
+///     .or(InstanceProvider::new(42i32));
+/// ```
+pub struct FallbackProvider<T: Managed> {
+    providers: Vec<Box<dyn Provider>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Managed> FallbackProvider<T> {
+    /// Creates a new [`FallbackProvider`] without any provider.
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends `provider` to the end of the chain, so that it's tried after
+    /// all providers added before.
+    pub fn or<P>(mut self, provider: P) -> Self
+    where
+        P: TypedProvider<Output = T>,
+    {
+        self.providers.push(Box::new(provider));
+        self
+    }
+}
+
+impl<T, P> FromIterator<P> for FallbackProvider<T>
+where
+    T: Managed,
+    P: TypedProvider<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |chain, provider| chain.or(provider))
+    }
+}
+
+impl<T: Managed> Debug for FallbackProvider<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FallbackProvider<T>")
+            .field("providers", &self.providers)
+            .finish()
+    }
+}
+
+impl<T: Managed> TypedProvider for FallbackProvider<T> {
+    type Output = T;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let mut last_err = None;
+        for provider in &self.providers {
+            match provider.dyn_provide(injector.upcast_dyn(), context) {
+                Ok(object) => match object.downcast::<T>() {
+                    Ok(object) => return Ok(*object),
+                    Err(_) => unreachable!("the object's type should be `T`"),
+                },
+                Err(err @ InjectorError::ObjectConstruction { .. }) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(
+            last_err.unwrap_or_else(|| InjectorError::ObjectConstruction {
+                key: context.key().dyn_clone(),
+                source: Arc::new(EmptyFallbackChainError),
+            }),
+        )
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.providers.iter().all(|provider| provider.dyn_is_leaf())
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        let providers = self
+            .providers
+            .iter()
+            .map(|provider| provider.clone_box())
+            .collect::<Option<_>>()?;
+        Some(Self {
+            providers,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: SharedManaged> TypedSharedProvider for FallbackProvider<T> {}
+
+/// The error returned when a [`FallbackProvider`] doesn't contain any
+/// provider to try.
+#[derive(Debug, Snafu)]
+#[snafu(display("the fallback chain doesn't contain any provider"))]
+pub struct EmptyFallbackChainError;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+    use crate::provider::closure::RawClosureProvider;
+    use crate::provider::instance::InstanceProvider;
+
+    use super::*;
+
+    #[test]
+    fn fallback_provider_succeeds_with_first_successful_provider() {
+        let injector = MockInjector::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = FallbackProvider::new()
+            .or(RawClosureProvider::new(|_| {
+                Ok(Err::<i32, _>("unavailable"))
+            }))
+            .or(InstanceProvider::new(42i32))
+            .or(RawClosureProvider::new({
+                let calls = Arc::clone(&calls);
+                move |_| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(Ok::<_, &'static str>(0i32))
+                }
+            }));
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert!(!provider.is_leaf());
+    }
+
+    #[test]
+    fn fallback_provider_from_iter_succeeds() {
+        let injector = MockInjector::new();
+        let provider: FallbackProvider<i32> = [1i32, 2i32]
+            .into_iter()
+            .map(InstanceProvider::new)
+            .collect();
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 1);
+        assert!(provider.is_leaf());
+    }
+
+    #[test]
+    fn fallback_provider_fails_when_all_providers_fail() {
+        let injector = MockInjector::new();
+        let provider = FallbackProvider::new()
+            .or(RawClosureProvider::new(|_| Ok(Err::<i32, _>("first"))))
+            .or(RawClosureProvider::new(|_| Ok(Err::<i32, _>("second"))));
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        let Err(InjectorError::ObjectConstruction { source, .. }) = res else {
+            panic!("the last error should be returned");
+        };
+        assert_eq!(source.to_string(), "second");
+    }
+
+    #[test]
+    fn fallback_provider_fails_when_chain_is_empty() {
+        let injector = MockInjector::new();
+        let provider = FallbackProvider::<i32>::new();
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        let err = res.unwrap_err();
+        assert!(err
+            .construction_source::<EmptyFallbackChainError>()
+            .is_some());
+    }
+
+    #[test]
+    fn fallback_provider_fails_immediately_when_error_is_not_construction_failure() {
+        let injector = MockInjector::new();
+        let provider = FallbackProvider::new()
+            .or(RawClosureProvider::new(|_| {
+                Err::<Result<i32, &'static str>, _>(InjectorError::NotFound {
+                    key: Box::new(key::of::<i64>()),
+                })
+            }))
+            .or(InstanceProvider::new(42i32));
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert!(matches!(res, Err(InjectorError::NotFound { .. })));
+    }
+
+    #[test]
+    fn fallback_provider_clone_provider_succeeds() {
+        let injector = MockInjector::new();
+        let provider = FallbackProvider::new()
+            .or(RawClosureProvider::new(|_| {
+                Ok(Err::<i32, _>("unavailable"))
+            }))
+            .or(InstanceProvider::new(42i32));
+
+        let cloned = provider.clone_provider().unwrap();
+        let res = cloned.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 42);
+
+        let provider = FallbackProvider::new().or(UncloneableProvider);
+        assert!(provider.clone_provider().is_none());
+    }
+
+    #[derive(Debug)]
+    struct UncloneableProvider;
+
+    impl TypedProvider for UncloneableProvider {
+        type Output = i32;
+
+        fn provide<I>(
+            &self,
+            _injector: &I,
+            _context: &CallContext<'_>,
+        ) -> Result<Self::Output, InjectorError>
+        where
+            I: TypedInjector + ?Sized,
+        {
+            Ok(0)
+        }
+    }
+}
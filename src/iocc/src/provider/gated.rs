@@ -0,0 +1,224 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+use snafu::prelude::*;
+
+use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+use crate::provider::{TypedProvider, TypedSharedProvider};
+
+/// A one-shot readiness signal, such as "migrations complete", which gated
+/// providers wait for before constructing objects.
+///
+/// A [`Gate`] starts closed and stays open once [`Gate::open`] is called.
+/// Copies of a [`Gate`] share the same signal.
+#[derive(Clone)]
+pub struct Gate {
+    state: Arc<GateState>,
+    timeout: Option<Duration>,
+}
+
+struct GateState {
+    opened: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Gate {
+    /// Creates a new closed [`Gate`], which is waited for without a timeout.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(GateState {
+                opened: Mutex::new(false),
+                condvar: Condvar::new(),
+            }),
+            timeout: None,
+        }
+    }
+
+    /// Sets how long to wait for the gate before giving up.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Opens the gate and wakes up all waiters.
+    pub fn open(&self) {
+        *self.state.opened.lock() = true;
+        self.state.condvar.notify_all();
+    }
+
+    /// Returns true if the gate has been opened.
+    pub fn is_open(&self) -> bool {
+        *self.state.opened.lock()
+    }
+
+    fn wait(&self) -> Result<(), GateTimeoutError> {
+        let mut opened = self.state.opened.lock();
+        if let Some(timeout) = self.timeout {
+            let res = self
+                .state
+                .condvar
+                .wait_while_for(&mut opened, |opened| !*opened, timeout);
+            ensure!(!res.timed_out(), GateTimeoutSnafu { timeout });
+        } else {
+            self.state
+                .condvar
+                .wait_while(&mut opened, |opened| !*opened);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Gate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Gate")
+            .field("opened", &self.is_open())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// The error returned when a [`Gate`] isn't opened in time.
+#[derive(Debug, Snafu)]
+#[snafu(display("the gate isn't opened within {timeout:?}"))]
+pub struct GateTimeoutError {
+    timeout: Duration,
+}
+
+/// A [`Provider`] which waits for a [`Gate`] to be opened before delegating
+/// to an inner provider.
+///
+/// If the gate has a timeout and isn't opened in time, the request fails with
+/// [`InjectorError::ObjectConstruction`] caused by a [`GateTimeoutError`].
+///
+/// The waiting thread is blocked while holding the construction of the
+/// object, so the code opening the gate must never depend on the gated
+/// object. Otherwise, the signaler waits for the object, which waits for the
+/// signal in turn, resulting in a deadlock, or a timeout at best.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use std::time::Duration;
+/// # use iocc::provider::closure::RawClosureProvider;
+/// # use iocc::provider::gated::{Gate, GatedProvider};
+/// let gate = Gate::new().with_timeout(Duration::from_secs(30));
+/// let provider = RawClosureProvider::new(|_injector| Ok(Ok::<_, Infallible>(42i32)));
+/// let provider = GatedProvider::new(provider, gate.clone());
+///
+/// // Somewhere else, once the application is ready.
+/// gate.open();
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct GatedProvider<P>
+where
+    P: TypedProvider,
+{
+    provider: P,
+    gate: Gate,
+}
+
+impl<P> GatedProvider<P>
+where
+    P: TypedProvider,
+{
+    /// Creates a new [`GatedProvider`] which waits for `gate` before each
+    /// request to `provider`.
+    pub fn new(provider: P, gate: Gate) -> Self {
+        Self { provider, gate }
+    }
+}
+
+impl<P> Debug for GatedProvider<P>
+where
+    P: TypedProvider,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("GatedProvider<P>")
+            .field("provider", &self.provider)
+            .field("gate", &self.gate)
+            .finish()
+    }
+}
+
+impl<P> TypedProvider for GatedProvider<P>
+where
+    P: TypedProvider,
+{
+    type Output = P::Output;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        self.gate
+            .wait()
+            .map_err(|err| InjectorError::ObjectConstruction {
+                key: context.key().dyn_clone(),
+                source: Arc::new(err),
+            })?;
+        self.provider.provide(injector, context)
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.provider.is_leaf()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            provider: self.provider.clone_provider()?,
+            gate: self.gate.clone(),
+        })
+    }
+}
+
+impl<P> TypedSharedProvider for GatedProvider<P> where P: TypedSharedProvider {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::thread;
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+    use crate::provider::closure::RawClosureProvider;
+
+    use super::*;
+
+    #[test]
+    fn gated_provider_succeeds_after_gate_is_opened() {
+        let gate = Gate::new();
+        let provider = RawClosureProvider::new(|_| Ok(Ok::<_, Infallible>(42i32)));
+        let provider = GatedProvider::new(provider, gate.clone());
+
+        let handle = thread::spawn(move || {
+            let injector = MockInjector::new();
+            let key = key::of::<i32>();
+            provider.provide(&injector, &CallContext::new(&key))
+        });
+        thread::sleep(Duration::from_millis(10));
+        assert!(!gate.is_open());
+        gate.open();
+
+        assert_eq!(handle.join().unwrap().unwrap(), 42);
+        assert!(gate.is_open());
+    }
+
+    #[test]
+    fn gated_provider_fails_when_gate_times_out() {
+        let gate = Gate::new().with_timeout(Duration::from_millis(10));
+        let provider = RawClosureProvider::new(|_| Ok(Ok::<_, Infallible>(42i32)));
+        let provider = GatedProvider::new(provider, gate);
+        let injector = MockInjector::new();
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert!(matches!(res, Err(InjectorError::ObjectConstruction { .. })));
+    }
+}
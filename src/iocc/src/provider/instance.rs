@@ -1,4 +1,6 @@
 use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 use crate::container::injector::{CallContext, InjectorError, TypedInjector};
 use crate::container::{Managed, SharedManaged};
@@ -22,6 +24,23 @@ where
     pub fn new(instance: T) -> Self {
         Self { instance }
     }
+
+    /// Creates a [`ThunkProvider`] which calls `thunk` on each request
+    /// instead of cloning an instance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Instant;
+    /// # use iocc::provider::instance::InstanceProvider;
+    /// let provider = InstanceProvider::from_fn(Instant::now);
+    /// ```
+    pub fn from_fn<F>(thunk: F) -> ThunkProvider<T, F>
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        ThunkProvider::new(thunk)
+    }
 }
 
 impl<T> Debug for InstanceProvider<T>
@@ -50,14 +69,119 @@ where
     {
         Ok(self.instance.clone())
     }
+
+    fn is_leaf(&self) -> bool {
+        true
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self::new(self.instance.clone()))
+    }
 }
 
 impl<T> TypedSharedProvider for InstanceProvider<T> where T: SharedManaged + Clone {}
 
+/// A [`Provider`] which supplies objects produced by a thunk, i.e. a closure
+/// taking no argument, on each request.
+///
+/// Unlike [`RawClosureProvider`], the thunk neither receives the injector nor
+/// resolves any dependency, which fits fresh values like timestamps or IDs
+/// bound as transient objects.
+///
+/// This is a separate type rather than a variant of [`InstanceProvider`],
+/// since it's generic over the thunk, and its objects don't have to be
+/// [`Clone`]. [`InstanceProvider::from_fn`] creates one for objects which
+/// are [`Clone`] anyway.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Instant;
+/// # use iocc::provider::instance::ThunkProvider;
+/// let provider = ThunkProvider::new(Instant::now);
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+/// [`RawClosureProvider`]: crate::provider::closure::RawClosureProvider
+pub struct ThunkProvider<T, F>
+where
+    T: Managed,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    thunk: Arc<F>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, F> ThunkProvider<T, F>
+where
+    T: Managed,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    /// Creates a new [`ThunkProvider`] calling `thunk` on each request.
+    pub fn new(thunk: F) -> Self {
+        Self {
+            thunk: Arc::new(thunk),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, F> Debug for ThunkProvider<T, F>
+where
+    T: Managed,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ThunkProvider<T, F>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F> TypedProvider for ThunkProvider<T, F>
+where
+    T: Managed,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    type Output = T;
+
+    fn provide<I>(
+        &self,
+        _injector: &I,
+        _context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        Ok((*self.thunk)())
+    }
+
+    fn is_leaf(&self) -> bool {
+        true
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            thunk: Arc::clone(&self.thunk),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T, F> TypedSharedProvider for ThunkProvider<T, F>
+where
+    T: SharedManaged,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
     use crate::container::injector::MockInjector;
     use crate::key;
+    use crate::provider::Provider;
+    use crate::util::any::Downcast;
 
     use super::*;
 
@@ -71,5 +195,44 @@ mod tests {
 
         let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
         assert_eq!(res.unwrap(), 42);
+
+        assert!(provider.is_leaf());
+    }
+
+    #[test]
+    fn thunk_provider_succeeds() {
+        let counter = AtomicI32::new(0);
+        let provider = ThunkProvider::new(move || counter.fetch_add(1, Ordering::Relaxed));
+        let injector = MockInjector::new();
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 0);
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 1);
+
+        assert!(provider.is_leaf());
+    }
+
+    #[test]
+    fn instance_provider_from_fn_succeeds() {
+        let provider = InstanceProvider::from_fn(|| String::from("fresh"));
+        let injector = MockInjector::new();
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<String>()));
+        assert_eq!(res.unwrap(), "fresh");
+    }
+
+    #[test]
+    fn instance_provider_clone_box_succeeds() {
+        let provider: Box<dyn Provider> = Box::new(InstanceProvider::new(42));
+        let injector = MockInjector::new();
+
+        let cloned = provider.clone_box().unwrap();
+        let res = cloned.dyn_provide(&injector, &CallContext::new(&key::of::<i32>()));
+        let Ok(object) = res.unwrap().downcast::<i32>() else {
+            panic!("the object's type should be `i32`");
+        };
+        assert_eq!(*object, 42);
     }
 }
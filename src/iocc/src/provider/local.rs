@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::container::injector::{
+    CallContext, ContextForwardingInjectorProxy, Injector, InjectorError, TypedInjector,
+};
+use crate::container::LocalManaged;
+use crate::provider::TypedLocalProvider;
+
+/// A [`LocalProvider`] which supplies local objects from a closure.
+///
+/// The closure has the same shape as a [`RawClosure`], except that the
+/// constructed object is only required to be [`Send`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::cell::Cell;
+/// # use std::convert::Infallible;
+/// # use iocc::provider::local::LocalClosureProvider;
+/// let provider = LocalClosureProvider::new(|_injector| Ok(Ok::<_, Infallible>(Cell::new(42i32))));
+/// ```
+///
+/// [`LocalProvider`]: crate::provider::LocalProvider
+/// [`RawClosure`]: crate::provider::closure::RawClosure
+pub struct LocalClosureProvider<T, E, F>
+where
+    T: LocalManaged,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    closure: Arc<F>,
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E, F> LocalClosureProvider<T, E, F>
+where
+    T: LocalManaged,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    /// Creates a new [`LocalClosureProvider`] from a closure.
+    pub fn new(closure: F) -> Self {
+        Self {
+            closure: Arc::new(closure),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E, F> Debug for LocalClosureProvider<T, E, F>
+where
+    T: LocalManaged,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    F: Fn(&dyn Injector) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("LocalClosureProvider<T, E, F>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, F> TypedLocalProvider for LocalClosureProvider<T, E, F>
+where
+    T: LocalManaged,
+    E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    F: Fn(&dyn Injector) -> Result<Result<T, E>, InjectorError>,
+    F: Send + Sync + 'static,
+{
+    type Output = T;
+
+    fn provide_local<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let injector = ContextForwardingInjectorProxy::new(injector, context);
+        match (self.closure)(&injector) {
+            Ok(Ok(obj)) => Ok(obj),
+            Ok(Err(err)) => Err(InjectorError::ObjectConstruction {
+                key: context.key().dyn_clone(),
+                source: Arc::from(err.into()),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn clone_local_provider(&self) -> Option<Self> {
+        Some(Self {
+            closure: Arc::clone(&self.closure),
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::convert::Infallible;
+
+    use crate::container::injector::MockInjector;
+    use crate::container::Local;
+    use crate::key;
+
+    use super::*;
+
+    #[test]
+    fn local_closure_provider_succeeds() {
+        let injector = MockInjector::new();
+        let provider = LocalClosureProvider::new(|_| Ok(Ok::<_, Infallible>(Cell::new(42i32))));
+
+        let key = key::of::<Local<Cell<i32>>>();
+        let res = provider.provide_local(&injector, &CallContext::new(&key));
+        assert_eq!(res.unwrap().get(), 42);
+    }
+}
@@ -1,11 +1,26 @@
+pub mod alias;
 pub mod closure;
 pub mod component;
+pub mod contextual;
+mod dependency;
+#[cfg(feature = "env")]
+pub mod env;
+pub mod fallback;
+pub mod gated;
 pub mod instance;
+pub mod local;
+pub mod proxy;
+pub mod retry;
 
+use std::any;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use crate::container::injector::{CallContext, Injector, InjectorError, TypedInjector};
-use crate::container::{Managed, SharedManaged};
+use crate::container::{LocalManaged, Managed, SharedManaged};
+use crate::key;
+
+pub use dependency::{DependencyDescriptor, QualifierKind};
 
 /// A universal factory which constructs objects of one type.
 ///
@@ -36,6 +51,32 @@ pub trait Provider: Debug + Send + Sync + 'static {
         injector: &dyn Injector,
         context: &CallContext<'_>,
     ) -> Result<Box<dyn Managed>, InjectorError>;
+
+    /// Returns true if the provider never retrieves any dependency from the
+    /// [`Injector`], which allows containers to skip cyclic dependency
+    /// detection for objects it provides.
+    fn dyn_is_leaf(&self) -> bool {
+        false
+    }
+
+    /// Returns descriptions of the dependencies the provider retrieves from
+    /// the [`Injector`], which is empty if they are unknown.
+    fn dyn_dependencies(&self) -> Vec<DependencyDescriptor> {
+        Vec::new()
+    }
+
+    /// Returns a boxed copy of the provider, or [`None`] if the provider
+    /// can't be cloned. Copies supply objects the same way as the original
+    /// one, which allows duplicating provider definitions independently.
+    fn clone_box(&self) -> Option<Box<dyn Provider>> {
+        None
+    }
+
+    /// Returns the name of the provider's type, which is only intended for
+    /// diagnostics.
+    fn type_name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
 }
 
 /// A static variant of the [`Provider`] trait, leveraging static dispatch and
@@ -43,6 +84,12 @@ pub trait Provider: Debug + Send + Sync + 'static {
 ///
 /// Usually, you don't need to implement [`Provider`] manually, since this is
 /// automatically done by [`TypedProvider`]'s blanket implementation.
+///
+/// Although [`TypedProvider::provide`] is generic over the injector, containers
+/// only ever call it through the blanket [`Provider`] implementation, where
+/// the injector is always a `dyn Injector`. Therefore, each provider is
+/// monomorphized once regardless of how many injector types exist, and only
+/// lookups such as [`TypedInjector::get`] are instantiated per target type.
 pub trait TypedProvider: Provider {
     /// The return type in response to each request to the provider.
     type Output: Managed;
@@ -63,6 +110,77 @@ pub trait TypedProvider: Provider {
     ) -> Result<Self::Output, InjectorError>
     where
         I: TypedInjector + ?Sized;
+
+    /// Returns true if the provider never retrieves any dependency from the
+    /// [`TypedInjector`]. Returning true for a provider which actually has
+    /// dependencies may make cyclic dependencies undetected.
+    fn is_leaf(&self) -> bool {
+        false
+    }
+
+    /// Returns descriptions of the dependencies the provider retrieves from
+    /// the [`TypedInjector`]. The default implementation reports none, as if
+    /// they were unknown.
+    fn dependencies(&self) -> Vec<DependencyDescriptor> {
+        Vec::new()
+    }
+
+    /// Returns a copy of the provider, or [`None`] if the provider can't be
+    /// cloned, which is the default.
+    ///
+    /// All providers of this crate can be cloned, as long as the providers
+    /// they wrap can. Closures are shared between copies rather than cloned,
+    /// so closures capturing non-[`Clone`] state are supported as well. Whole
+    /// sets of bindings are copied by [`Catalog::try_clone`].
+    ///
+    /// [`Catalog::try_clone`]: crate::container::Catalog::try_clone
+    fn clone_provider(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Turns the provider into a plain factory closure, which provides a new
+    /// object from `injector` on each call. It helps integrating with APIs
+    /// expecting an `Fn() -> T`.
+    ///
+    /// Each call starts a fresh [`CallContext`] keyed by an unqualified key of
+    /// [`TypedProvider::Output`]. Dependencies are resolved from `injector`,
+    /// so it should be the container the provider is meant for, or one of its
+    /// sub-containers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::convert::Infallible;
+    /// # use iocc::prelude::*;
+    /// # use iocc::module;
+    /// # use iocc::provider::closure::ClosureProvider;
+    /// # use iocc::provider::TypedProvider;
+    /// # use iocc::scope::SingletonScope;
+    /// let module = module::from_fn(|configurer| {
+    ///     bind::<i32>().to_instance(21).set_on(configurer);
+    ///     Ok(())
+    /// });
+    /// let container = Container::<SingletonScope>::init(module).unwrap();
+    ///
+    /// let provider = ClosureProvider::new(|value: i32| Ok::<_, Infallible>(value as i64 * 2));
+    /// let make = provider.into_factory(container.into_injector());
+    /// assert_eq!(make().unwrap(), 42);
+    /// ```
+    fn into_factory(
+        self,
+        injector: Arc<dyn Injector>,
+    ) -> impl Fn() -> Result<Self::Output, InjectorError> + Send + Sync + 'static
+    where
+        Self: Sized,
+    {
+        move || {
+            let key = key::of::<Self::Output>();
+            self.provide(injector.as_ref(), &CallContext::new(&key))
+        }
+    }
 }
 
 impl<T: TypedProvider> Provider for T {
@@ -74,6 +192,19 @@ impl<T: TypedProvider> Provider for T {
         self.provide(injector, context)
             .map(|obj| -> Box<dyn Managed> { Box::new(obj) })
     }
+
+    fn dyn_is_leaf(&self) -> bool {
+        self.is_leaf()
+    }
+
+    fn dyn_dependencies(&self) -> Vec<DependencyDescriptor> {
+        self.dependencies()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Provider>> {
+        self.clone_provider()
+            .map(|provider| -> Box<dyn Provider> { Box::new(provider) })
+    }
 }
 
 /// A variant of the [`TypedProvider`] trait, which produces a shareable object.
@@ -100,6 +231,10 @@ pub trait SharedProvider: Provider {
 
     /// Returns a reference to `self` as a [`Provider`].
     fn upcast_provider(&self) -> &dyn Provider;
+
+    /// Returns a boxed copy of the provider, or [`None`] if the provider
+    /// can't be cloned.
+    fn clone_shared_box(&self) -> Option<Box<dyn SharedProvider>>;
 }
 
 /// A static variant of the [`Provider`] trait, which produces a shareable object.
@@ -127,4 +262,91 @@ impl<T: TypedSharedProvider> SharedProvider for T {
     fn upcast_provider(&self) -> &dyn Provider {
         self
     }
+
+    fn clone_shared_box(&self) -> Option<Box<dyn SharedProvider>> {
+        self.clone_provider()
+            .map(|provider| -> Box<dyn SharedProvider> { Box::new(provider) })
+    }
+}
+
+/// A factory which constructs local objects of one type.
+///
+/// A [`LocalProvider`] plays the same role as a [`Provider`], except that its
+/// objects are only required to be [`Send`]. Such objects are owned by the
+/// container rather than handed out, so they are never obtained through an
+/// [`Injector`], and thus can't be dependencies of other objects.
+pub trait LocalProvider: Debug + Send + Sync + 'static {
+    /// Provides a newly created type-erased local object. A [`Injector`] is
+    /// needed since some other objects may be dependent on the object
+    /// requested by the caller. The `context` preserves all additional
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all dependencies can't be fetched or the object
+    /// construction fails.
+    fn dyn_provide_local(
+        &self,
+        injector: &dyn Injector,
+        context: &CallContext<'_>,
+    ) -> Result<Box<dyn LocalManaged>, InjectorError>;
+
+    /// Returns a boxed copy of the provider, or [`None`] if the provider
+    /// can't be cloned.
+    fn clone_local_box(&self) -> Option<Box<dyn LocalProvider>> {
+        None
+    }
+
+    /// Returns the name of the provider's type, which is only intended for
+    /// diagnostics.
+    fn type_name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
+}
+
+/// A static variant of the [`LocalProvider`] trait, leveraging static
+/// dispatch and type-safety.
+pub trait TypedLocalProvider: LocalProvider {
+    /// The return type in response to each request to the provider.
+    type Output: LocalManaged;
+
+    /// Provides a newly created local object of type
+    /// [`TypedLocalProvider::Output`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all dependencies can't be fetched or the object
+    /// construction fails.
+    fn provide_local<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized;
+
+    /// Returns a copy of the provider, or [`None`] if the provider can't be
+    /// cloned, which is the default.
+    fn clone_local_provider(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+impl<T: TypedLocalProvider> LocalProvider for T {
+    fn dyn_provide_local(
+        &self,
+        injector: &dyn Injector,
+        context: &CallContext<'_>,
+    ) -> Result<Box<dyn LocalManaged>, InjectorError> {
+        self.provide_local(injector, context)
+            .map(|obj| -> Box<dyn LocalManaged> { Box::new(obj) })
+    }
+
+    fn clone_local_box(&self) -> Option<Box<dyn LocalProvider>> {
+        self.clone_local_provider()
+            .map(|provider| -> Box<dyn LocalProvider> { Box::new(provider) })
+    }
 }
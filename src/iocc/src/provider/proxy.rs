@@ -0,0 +1,187 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use crate::container::injector::{CallContext, Injector, InjectorError, TypedInjector};
+use crate::provider::{TypedProvider, TypedSharedProvider};
+
+/// A [`Provider`] which lets an inner provider retrieve its dependencies
+/// through a proxy wrapping the real [`Injector`].
+///
+/// The proxy is created by a closure on each request, receiving the injector
+/// the object is requested from. It may rewrite or filter dependency lookups,
+/// which makes it possible to mock or decorate dependencies of a single
+/// provider without touching other bindings. Lookups not rewritten by the
+/// proxy should be forwarded to [`Injector::dyn_get_dependency`] of the inner
+/// injector, so that cyclic dependencies are still detected.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::any::TypeId;
+/// # use std::convert::Infallible;
+/// # use iocc::container::Managed;
+/// # use iocc::container::injector::{CallContext, Injector, InjectorError};
+/// # use iocc::key::Key;
+/// # use iocc::provider::closure::RawClosureProvider;
+/// # use iocc::provider::proxy::ProxiedProvider;
+/// struct LoggingInjector<'a> {
+///     inner: &'a dyn Injector,
+/// }
+///
+/// impl Injector for LoggingInjector<'_> {
+///     fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+///         println!("get {key}");
+///         self.inner.dyn_get(key)
+///     }
+///
+///     fn dyn_get_dependency<'a>(
+///         &self,
+///         key: &dyn Key,
+///         context: &'a CallContext<'a>,
+///     ) -> Result<Box<dyn Managed>, InjectorError> {
+///         println!("get {key}");
+///         self.inner.dyn_get_dependency(key, context)
+///     }
+///
+///     fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+///         self.inner.keys(type_id)
+///     }
+/// }
+///
+/// let provider = RawClosureProvider::new(|_injector| Ok(Ok::<_, Infallible>(42i32)));
+/// let provider = ProxiedProvider::new(provider, |inner| Box::new(LoggingInjector { inner }));
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct ProxiedProvider<P, F>
+where
+    P: TypedProvider,
+    F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+    F: Send + Sync + 'static,
+{
+    provider: P,
+    proxy: Arc<F>,
+}
+
+impl<P, F> ProxiedProvider<P, F>
+where
+    P: TypedProvider,
+    F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+    F: Send + Sync + 'static,
+{
+    /// Creates a new [`ProxiedProvider`] which wraps the injector with the
+    /// proxy created by `proxy`.
+    pub fn new(provider: P, proxy: F) -> Self {
+        Self {
+            provider,
+            proxy: Arc::new(proxy),
+        }
+    }
+}
+
+impl<P, F> Debug for ProxiedProvider<P, F>
+where
+    P: TypedProvider,
+    F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+    F: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ProxiedProvider<P, F>")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, F> TypedProvider for ProxiedProvider<P, F>
+where
+    P: TypedProvider,
+    F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+    F: Send + Sync + 'static,
+{
+    type Output = P::Output;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let proxy = (self.proxy)(injector.upcast_dyn());
+        self.provider.provide(proxy.as_ref(), context)
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.provider.is_leaf()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            provider: self.provider.clone_provider()?,
+            proxy: Arc::clone(&self.proxy),
+        })
+    }
+}
+
+impl<P, F> TypedSharedProvider for ProxiedProvider<P, F>
+where
+    P: TypedSharedProvider,
+    F: for<'a> Fn(&'a dyn Injector) -> Box<dyn Injector + 'a>,
+    F: Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+    use std::convert::Infallible;
+
+    use crate::container::injector::MockInjector;
+    use crate::container::Managed;
+    use crate::key::{self, Key};
+    use crate::provider::closure::RawClosureProvider;
+
+    use super::*;
+
+    struct OverridingInjector<'a> {
+        inner: &'a dyn Injector,
+    }
+
+    impl Injector for OverridingInjector<'_> {
+        fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+            if key == &key::of::<i64>() as &dyn Key {
+                Ok(Box::new(7i64))
+            } else {
+                self.inner.dyn_get(key)
+            }
+        }
+
+        fn dyn_get_dependency<'a>(
+            &self,
+            key: &dyn Key,
+            _context: &'a CallContext<'a>,
+        ) -> Result<Box<dyn Managed>, InjectorError> {
+            self.dyn_get(key)
+        }
+
+        fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+            self.inner.keys(type_id)
+        }
+    }
+
+    #[test]
+    fn proxied_provider_succeeds() {
+        let mut injector = MockInjector::new();
+        injector.expect_dyn_get_dependency().never();
+        let provider = RawClosureProvider::new(|injector| {
+            let value: i64 = injector.get(key::of())?;
+            Ok(Ok::<_, Infallible>(value as i32))
+        });
+        let provider =
+            ProxiedProvider::new(provider, |inner| Box::new(OverridingInjector { inner }));
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 7);
+    }
+}
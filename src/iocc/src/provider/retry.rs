@@ -0,0 +1,218 @@
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::container::injector::{CallContext, InjectorError, TypedInjector};
+use crate::provider::{TypedProvider, TypedSharedProvider};
+
+/// A [`Provider`] which retries object construction of an inner provider on
+/// transient failures.
+///
+/// Only [`InjectorError::ObjectConstruction`] is considered transient and
+/// triggers a retry. Other errors, such as [`InjectorError::NotFound`] and
+/// [`InjectorError::CyclicDependency`], are returned immediately, since
+/// retrying never helps resolve them. Before each retry, the current thread
+/// sleeps for the duration returned by the backoff closure, which receives the
+/// number of the upcoming retry starting from `1`.
+///
+/// A failed construction of a shared object is never cached by containers, so
+/// every subsequent request repeats the whole construct-or-wait cycle, and
+/// other threads waiting for the object receive the same error. Retrying
+/// inside the provider is therefore the right layer to tolerate transient
+/// failures, because waiters are only notified once all attempts are done.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use std::time::Duration;
+/// # use iocc::provider::closure::RawClosureProvider;
+/// # use iocc::provider::retry::RetryingProvider;
+/// let provider = RawClosureProvider::new(|_injector| Ok(Ok::<_, Infallible>(42i32)));
+/// let provider = RetryingProvider::new(provider, 3)
+///     .with_backoff(|attempt| Duration::from_millis(10 * attempt as u64));
+/// ```
+///
+/// [`Provider`]: crate::provider::Provider
+pub struct RetryingProvider<P, B = fn(usize) -> Duration>
+where
+    P: TypedProvider,
+    B: Fn(usize) -> Duration + Send + Sync + 'static,
+{
+    provider: P,
+    retries: usize,
+    backoff: Arc<B>,
+}
+
+impl<P> RetryingProvider<P>
+where
+    P: TypedProvider,
+{
+    /// Creates a new [`RetryingProvider`] which retries at most `retries`
+    /// times without any backoff.
+    pub fn new(provider: P, retries: usize) -> Self {
+        Self {
+            provider,
+            retries,
+            backoff: Arc::new(|_| Duration::ZERO),
+        }
+    }
+}
+
+impl<P, B> RetryingProvider<P, B>
+where
+    P: TypedProvider,
+    B: Fn(usize) -> Duration + Send + Sync + 'static,
+{
+    /// Replaces the backoff closure, which computes how long to wait before
+    /// the given retry.
+    pub fn with_backoff<NewB>(self, backoff: NewB) -> RetryingProvider<P, NewB>
+    where
+        NewB: Fn(usize) -> Duration + Send + Sync + 'static,
+    {
+        RetryingProvider {
+            provider: self.provider,
+            retries: self.retries,
+            backoff: Arc::new(backoff),
+        }
+    }
+}
+
+impl<P, B> Debug for RetryingProvider<P, B>
+where
+    P: TypedProvider,
+    B: Fn(usize) -> Duration + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("RetryingProvider<P, B>")
+            .field("provider", &self.provider)
+            .field("retries", &self.retries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, B> TypedProvider for RetryingProvider<P, B>
+where
+    P: TypedProvider,
+    B: Fn(usize) -> Duration + Send + Sync + 'static,
+{
+    type Output = P::Output;
+
+    fn provide<I>(
+        &self,
+        injector: &I,
+        context: &CallContext<'_>,
+    ) -> Result<Self::Output, InjectorError>
+    where
+        I: TypedInjector + ?Sized,
+    {
+        let mut attempt = 0;
+        loop {
+            match self.provider.provide(injector, context) {
+                Err(InjectorError::ObjectConstruction { .. }) if attempt < self.retries => {
+                    attempt += 1;
+                    let delay = (self.backoff)(attempt);
+                    if !delay.is_zero() {
+                        thread::sleep(delay);
+                    }
+                }
+                res => return res,
+            }
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.provider.is_leaf()
+    }
+
+    fn clone_provider(&self) -> Option<Self> {
+        Some(Self {
+            provider: self.provider.clone_provider()?,
+            retries: self.retries,
+            backoff: Arc::clone(&self.backoff),
+        })
+    }
+}
+
+impl<P, B> TypedSharedProvider for RetryingProvider<P, B>
+where
+    P: TypedSharedProvider,
+    B: Fn(usize) -> Duration + Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::container::injector::MockInjector;
+    use crate::key;
+    use crate::provider::closure::RawClosureProvider;
+
+    use super::*;
+
+    #[test]
+    fn retrying_provider_succeeds_after_transient_failures() {
+        let injector = MockInjector::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = RawClosureProvider::new({
+            let calls = Arc::clone(&calls);
+            move |_| match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Ok(Err("transient failure")),
+                _ => Ok(Ok(42i32)),
+            }
+        });
+        let backoffs = Arc::new(AtomicUsize::new(0));
+        let provider = RetryingProvider::new(provider, 3).with_backoff({
+            let backoffs = Arc::clone(&backoffs);
+            move |attempt| {
+                backoffs.fetch_add(attempt, Ordering::SeqCst);
+                Duration::ZERO
+            }
+        });
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert_eq!(res.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(backoffs.load(Ordering::SeqCst), 1 + 2);
+    }
+
+    #[test]
+    fn retrying_provider_fails_when_retries_are_exhausted() {
+        let injector = MockInjector::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = RawClosureProvider::new({
+            let calls = Arc::clone(&calls);
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(Err::<i32, _>("persistent failure"))
+            }
+        });
+        let provider = RetryingProvider::new(provider, 2);
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert!(matches!(res, Err(InjectorError::ObjectConstruction { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn retrying_provider_fails_immediately_when_error_is_not_transient() {
+        let injector = MockInjector::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = RawClosureProvider::new({
+            let calls = Arc::clone(&calls);
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<Result<i32, &'static str>, _>(InjectorError::NotFound {
+                    key: Box::new(key::of::<i64>()),
+                })
+            }
+        });
+        let provider = RetryingProvider::new(provider, 3);
+
+        let res = provider.provide(&injector, &CallContext::new(&key::of::<i32>()));
+        assert!(matches!(res, Err(InjectorError::NotFound { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
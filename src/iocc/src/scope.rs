@@ -1,24 +1,141 @@
+use std::any::TypeId;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::key::Key;
 
 /// A type that specifies how long a managed object can live.
 ///
 /// A [`Scope`] is typically implemented as an `enum`, whose each variant
 /// represents a possible scope. In other words, a [`Scope`] is not a single
 /// scope but a set of supported scopes. Variants in a [`Scope`] are analogous
-/// to sets, where each variant has at most one predecesssor and at most one
-/// successor in a *subset* partial order relationship. Note that the [`Ord`]
-/// trait is required by [`Scope`], and the implementation should satisfy:
+/// to sets, where each variant has at most one predecesssor and usually at
+/// most one successor in a *subset* partial order relationship. Note that the
+/// [`Ord`] trait is required by [`Scope`], and the implementation should
+/// satisfy:
 ///
 /// - `a >= b` if `a` is a superset of `b` (either true superset or equal)
 /// - `a <= b` if `a` is a subset of `b` (either true subset or equal)
 ///
+/// The hierarchy may also branch, where a scope has several sub scopes which
+/// are unrelated to each other, e.g. a `Request` scope and a `Job` scope both
+/// within the singleton scope. Such a [`Scope`] returns all branches from
+/// [`Scope::sub_scopes`], and overrides [`Scope::outlive`], since [`Ord`] can't
+/// tell unrelated scopes apart. Its [`Ord`] implementation must still keep each
+/// scope below the scopes outliving it, with [`Scope::MIN`] as the minimum,
+/// while unrelated branches are ordered arbitrarily. A sub-container is created
+/// for a single branch, chosen with [`Container::sub_container_to`]. Requests
+/// for objects bound within an outer scope are forwarded along the branch to
+/// the container of that scope, while objects bound within an unrelated branch
+/// are constructed anew on each request, like those bound within a narrower
+/// scope.
+///
 /// Each [`Registry`] is associated with a [`Scope`], since it stores all
 /// definitions of how to construct managed objects, which should be aware of
 /// the lifetime of those objects.
 ///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use std::fmt::{Display, Formatter, Result as FmtResult};
+/// # use std::sync::Arc;
+/// # use iocc::container::registry::TypedConfigurer;
+/// # use iocc::prelude::*;
+/// # use iocc::provider::closure::RawClosureProvider;
+/// # use iocc::scope::Scope;
+/// // `Request` and `Job` are unrelated, but both are ordered below
+/// // `Singleton`.
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// enum ForkScope {
+///     Request,
+///     Job,
+///     Singleton,
+/// }
+///
+/// impl Display for ForkScope {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+///         write!(f, "{}", self.to_str())
+///     }
+/// }
+///
+/// impl Scope for ForkScope {
+///     const SINGLETON: Self = Self::Singleton;
+///
+///     const MIN: Self = Self::Request;
+///
+///     fn outlive(self, other: Self) -> bool {
+///         self == other || self == Self::Singleton
+///     }
+///
+///     fn super_scope(self) -> Option<Self> {
+///         match self {
+///             Self::Singleton => None,
+///             Self::Request | Self::Job => Some(Self::Singleton),
+///         }
+///     }
+///
+///     fn sub_scope(self) -> Option<Self> {
+///         match self {
+///             Self::Singleton => Some(Self::Request),
+///             Self::Request | Self::Job => None,
+///         }
+///     }
+///
+///     fn sub_scopes(self) -> Vec<Self> {
+///         match self {
+///             Self::Singleton => vec![Self::Request, Self::Job],
+///             Self::Request | Self::Job => Vec::new(),
+///         }
+///     }
+///
+///     fn to_str(&self) -> &'static str {
+///         match self {
+///             Self::Singleton => "Singleton",
+///             Self::Request => "Request",
+///             Self::Job => "Job",
+///         }
+///     }
+/// }
+///
+/// let module = iocc::module::from_fn(|configurer| {
+///     for scope in [ForkScope::Singleton, ForkScope::Request, ForkScope::Job] {
+///         configurer.register_shared(
+///             key::qualified::<Arc<String>>(scope.to_str()),
+///             RawClosureProvider::new(move |_| {
+///                 Ok(Ok::<_, Infallible>(Arc::new(scope.to_string())))
+///             }),
+///             scope,
+///         );
+///     }
+///     Ok(())
+/// });
+///
+/// let root = Container::<ForkScope>::init(module).unwrap();
+/// let request = root.sub_container().unwrap();
+/// let job = root.sub_container_to(ForkScope::Job).unwrap();
+/// assert_eq!(request.scope_chain(), [ForkScope::Singleton, ForkScope::Request]);
+/// assert_eq!(job.scope_chain(), [ForkScope::Singleton, ForkScope::Job]);
+/// assert!(job.sub_container_to(ForkScope::Request).is_none());
+///
+/// // Singletons are shared by both branches.
+/// let singleton1 = request.get(key::named::<Arc<String>>("Singleton")).unwrap();
+/// let singleton2 = job.get(key::named::<Arc<String>>("Singleton")).unwrap();
+/// assert!(Arc::ptr_eq(&singleton1, &singleton2));
+///
+/// // Objects bound within `Job` are cached in the job branch only.
+/// let job1 = job.get(key::named::<Arc<String>>("Job")).unwrap();
+/// let job2 = job.get(key::named::<Arc<String>>("Job")).unwrap();
+/// assert!(Arc::ptr_eq(&job1, &job2));
+/// let job1 = request.get(key::named::<Arc<String>>("Job")).unwrap();
+/// let job2 = request.get(key::named::<Arc<String>>("Job")).unwrap();
+/// assert!(!Arc::ptr_eq(&job1, &job2));
+/// ```
+///
 /// [`Registry`]: crate::container::registry::Registry
+/// [`Container::sub_container_to`]: crate::container::Container::sub_container_to
 pub trait Scope: Copy + Debug + Display + Ord + Hash + Sized + Send + Sync + 'static {
     /// A scope corresponded to objects that are created only once and managed
     /// through out the container's lifetime.
@@ -34,15 +151,29 @@ pub trait Scope: Copy + Debug + Display + Ord + Hash + Sized + Send + Sync + 'st
 
     /// Returns true if `self` is a true subset of `other` or is equal to it.
     fn within(self, other: Self) -> bool {
-        self <= other
+        other.outlive(self)
     }
 
     /// Returns the shortest scope which strictly outlives `self`.
     fn super_scope(self) -> Option<Self>;
 
     /// Returns the longest scope which is strictly within `self`.
+    ///
+    /// If the hierarchy branches at `self`, this is the branch entered by
+    /// [`Container::sub_container`].
+    ///
+    /// [`Container::sub_container`]: crate::container::Container::sub_container
     fn sub_scope(self) -> Option<Self>;
 
+    /// Returns all scopes directly within `self`, one for each branch of the
+    /// hierarchy.
+    ///
+    /// The default implementation returns [`Scope::sub_scope`] only, which
+    /// fits hierarchies without any branch.
+    fn sub_scopes(self) -> Vec<Self> {
+        self.sub_scope().into_iter().collect()
+    }
+
     /// Returns the name of the current scope in a string literal.
     fn to_str(&self) -> &'static str;
 }
@@ -120,6 +251,109 @@ impl Scope for WebScope {
     }
 }
 
+/// A [`Scope`] whose variants are corresponded to possible lifetimes in actor
+/// systems, where each actor, or any other entity like a connection, owns a
+/// sub-container of the singleton container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ActorScope {
+    Singleton = 2,
+    Actor = 1,
+}
+
+impl Display for ActorScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl Scope for ActorScope {
+    const SINGLETON: Self = Self::Singleton;
+
+    const MIN: Self = Self::Actor;
+
+    fn super_scope(self) -> Option<Self> {
+        match self {
+            Self::Singleton => None,
+            Self::Actor => Some(Self::Singleton),
+        }
+    }
+
+    fn sub_scope(self) -> Option<Self> {
+        match self {
+            Self::Singleton => Some(Self::Actor),
+            Self::Actor => None,
+        }
+    }
+
+    fn to_str(&self) -> &'static str {
+        match self {
+            Self::Singleton => "Singleton",
+            Self::Actor => "Actor",
+        }
+    }
+}
+
+/// A read-only description of the scope a container runs in, which can be
+/// injected as a dependency with `key::of::<ScopeContext>()`.
+///
+/// Containers synthesize a [`ScopeContext`] on request instead of looking up
+/// a binding, so the key is reserved, and binding it is reported as
+/// [`RegistryError::ReservedKey`]. The context describes the
+/// container serving the request, e.g. the root container for objects bound
+/// within the singleton scope. It's lighter than depending on the whole
+/// container when only the scope is needed, such as for logging or deriving
+/// cache keys.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::scope::{ScopeContext, WebScope};
+/// let module = module::from_fn(|configurer| {
+///     bind::<String>()
+///         .to_closure(|context: ScopeContext| Ok::<_, Infallible>(context.current().to_owned()))
+///         .set_on(configurer);
+///     Ok(())
+/// });
+///
+/// let root = Container::<WebScope>::init(module).unwrap();
+/// let session = root.sub_container().unwrap();
+/// assert_eq!(session.get(key::of::<String>()).unwrap(), "Session");
+/// ```
+///
+/// [`RegistryError::ReservedKey`]: crate::container::registry::RegistryError::ReservedKey
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScopeContext {
+    chain: Arc<[&'static str]>,
+}
+
+impl ScopeContext {
+    pub(crate) fn new<S: Scope>(chain: &[S]) -> Self {
+        debug_assert!(!chain.is_empty(), "the scope chain shouldn't be empty");
+        Self {
+            chain: chain.iter().map(Scope::to_str).collect(),
+        }
+    }
+
+    /// Returns true if `key` is the reserved key of [`ScopeContext`].
+    pub(crate) fn is_key(key: &dyn Key) -> bool {
+        key.target_type() == TypeId::of::<Self>() && key.qualifier_type() == TypeId::of::<()>()
+    }
+
+    /// Returns the name of the scope of the container.
+    pub fn current(&self) -> &'static str {
+        self.chain[self.chain.len() - 1]
+    }
+
+    /// Returns the names of the scopes from the root container down to the
+    /// container itself.
+    pub fn chain(&self) -> &[&'static str] {
+        &self.chain
+    }
+}
+
 /// A type that represents arbitrary lifetimes for objects.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Transient;
@@ -0,0 +1,451 @@
+//! Utilities for testing components in isolation.
+//!
+//! This module is only available with the `test-util` feature enabled.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::container::injector::{CallContext, ConstructedVisitor, Injector, InjectorError};
+use crate::container::{Container, Managed};
+use crate::key::{Key, TypedKey};
+use crate::scope::Scope;
+
+type Stub = Box<dyn Fn() -> Box<dyn Managed> + Send + Sync>;
+
+type Fallback = Box<dyn Fn(&dyn Key) -> Result<Box<dyn Managed>, InjectorError> + Send + Sync>;
+
+/// An [`Injector`] which supplies preset stubs instead of constructing
+/// objects, so that a single component can be tested without configuring a
+/// whole container.
+///
+/// Requesting a key without a stub is delegated to the fallback set by
+/// [`StubContainer::with_fallback`], or panics with the key in the message if
+/// there is no fallback, which is usually a mistake in the test.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use iocc::prelude::*;
+/// # use iocc::provider::component::Component;
+/// # use iocc::test_util::StubContainer;
+/// struct Greeter {
+///     name: String,
+/// }
+///
+/// #[component]
+/// impl Greeter {
+///     #[inject]
+///     fn new(name: String) -> Self {
+///         Self { name }
+///     }
+/// }
+///
+/// let stubs = StubContainer::new().stub(key::of(), String::from("world"));
+/// let greeter = Greeter::construct(&stubs).unwrap().unwrap();
+/// assert_eq!(greeter.name, "world");
+/// ```
+pub struct StubContainer {
+    stubs: HashMap<Box<dyn Key>, Stub>,
+    fallback: Option<Fallback>,
+}
+
+impl StubContainer {
+    /// Creates a new [`StubContainer`] without any stub.
+    pub fn new() -> Self {
+        Self {
+            stubs: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    /// Registers `value` as the stub of `key`, which is cloned on each
+    /// request.
+    pub fn stub<K>(mut self, key: K, value: K::Target) -> Self
+    where
+        K: TypedKey<Target: Clone>,
+    {
+        let stub: Stub = Box::new(move || Box::new(value.clone()));
+        self.stubs.insert(Box::new(key), stub);
+        self
+    }
+
+    /// Registers the default value of the target type as the stub of `key`.
+    pub fn stub_default<K>(self, key: K) -> Self
+    where
+        K: TypedKey<Target: Clone + Default>,
+    {
+        self.stub(key, K::Target::default())
+    }
+
+    /// Sets the fallback used for keys without any stub.
+    pub fn with_fallback<F>(mut self, fallback: F) -> Self
+    where
+        F: Fn(&dyn Key) -> Result<Box<dyn Managed>, InjectorError> + Send + Sync + 'static,
+    {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+}
+
+impl Debug for StubContainer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("StubContainer")
+            .field("stubs", &self.stubs.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Injector for StubContainer {
+    fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+        if let Some(stub) = self.stubs.get(key) {
+            Ok(stub())
+        } else if let Some(fallback) = self.fallback.as_ref() {
+            fallback(key)
+        } else {
+            panic!("no stub for key {key}, register one with `StubContainer::stub`")
+        }
+    }
+
+    fn dyn_get_dependency<'a>(
+        &self,
+        key: &dyn Key,
+        _context: &'a CallContext<'a>,
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        self.dyn_get(key)
+    }
+
+    fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+        self.stubs
+            .keys()
+            .filter(|key| key.target_type() == type_id)
+            .map(|key| key.dyn_clone())
+            .collect()
+    }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.stubs.keys().any(|key| key.target_type() == type_id)
+    }
+}
+
+/// An [`Injector`] which resolves objects through another injector and
+/// records the key of every request, so that tests can assert on the
+/// dependencies a component resolves.
+///
+/// Unlike [`StubContainer`], nothing is stubbed, and objects are resolved by
+/// the inner injector, usually a real container. Only requests made directly
+/// on the [`TracingInjector`] are recorded, in their order, while the inner
+/// injector resolves nested dependencies on its own.
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::module;
+/// # use iocc::provider::component::Component;
+/// # use iocc::scope::SingletonScope;
+/// # use iocc::test_util::TracingInjector;
+/// struct Greeter;
+///
+/// #[component]
+/// impl Greeter {
+///     #[inject]
+///     fn new(#[named("greeting")] _greeting: String, _times: u32) -> Self {
+///         Self
+///     }
+/// }
+///
+/// let module = module::from_fn(|configurer| {
+///     bind::<String>()
+///         .qualified_by("greeting")
+///         .to_instance(String::from("hello"))
+///         .set_on(configurer);
+///     bind::<u32>().to_instance(3).set_on(configurer);
+///     Ok(())
+/// });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+///
+/// let tracing = TracingInjector::new(&container);
+/// Greeter::construct(&tracing).unwrap().unwrap();
+///
+/// let keys = tracing.recorded();
+/// assert_eq!(keys[0].as_ref(), &key::named::<String>("greeting") as &dyn Key);
+/// assert_eq!(keys[1].as_ref(), &key::of::<u32>() as &dyn Key);
+/// ```
+pub struct TracingInjector<'a> {
+    inner: &'a dyn Injector,
+    log: Arc<Mutex<Vec<Box<dyn Key>>>>,
+}
+
+impl<'a> TracingInjector<'a> {
+    /// Creates a new [`TracingInjector`] resolving objects through `inner`.
+    pub fn new(inner: &'a dyn Injector) -> Self {
+        Self::with_log(inner, Arc::new(Mutex::new(Vec::new())))
+    }
+
+    /// Creates a new [`TracingInjector`] appending requested keys to `log`,
+    /// which may be shared with other tracing injectors.
+    pub fn with_log(inner: &'a dyn Injector, log: Arc<Mutex<Vec<Box<dyn Key>>>>) -> Self {
+        Self { inner, log }
+    }
+
+    /// Returns the shared log of requested keys.
+    pub fn log(&self) -> &Arc<Mutex<Vec<Box<dyn Key>>>> {
+        &self.log
+    }
+
+    /// Returns the keys requested so far, in their order.
+    pub fn recorded(&self) -> Vec<Box<dyn Key>> {
+        self.lock_log().iter().map(|key| key.dyn_clone()).collect()
+    }
+
+    fn record(&self, key: &dyn Key) {
+        self.lock_log().push(key.dyn_clone());
+    }
+
+    fn lock_log(&self) -> MutexGuard<'_, Vec<Box<dyn Key>>> {
+        self.log.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Debug for TracingInjector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TracingInjector")
+            .field("log", &self.log)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Injector for TracingInjector<'_> {
+    fn dyn_get(&self, key: &dyn Key) -> Result<Box<dyn Managed>, InjectorError> {
+        self.record(key);
+        self.inner.dyn_get(key)
+    }
+
+    fn dyn_get_dependency<'a>(
+        &self,
+        key: &dyn Key,
+        context: &'a CallContext<'a>,
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        self.record(key);
+        self.inner.dyn_get_dependency(key, context)
+    }
+
+    fn dyn_get_with_context(
+        &self,
+        key: &dyn Key,
+        user_data: &(dyn Any + Sync),
+    ) -> Result<Box<dyn Managed>, InjectorError> {
+        self.record(key);
+        self.inner.dyn_get_with_context(key, user_data)
+    }
+
+    fn keys(&self, type_id: TypeId) -> Vec<Box<dyn Key>> {
+        self.inner.keys(type_id)
+    }
+
+    fn contains_target(&self, type_id: TypeId) -> bool {
+        self.inner.contains_target(type_id)
+    }
+
+    fn dyn_with_constructed(&self, key: &dyn Key, visitor: &mut dyn ConstructedVisitor) -> bool {
+        // A miss falls back to `dyn_get`, which records the key itself.
+        let found = self.inner.dyn_with_constructed(key, visitor);
+        if found {
+            self.record(key);
+        }
+        found
+    }
+}
+
+impl<S: Scope> Container<S> {
+    /// Resolves `key` and discards the object, or panics with a detailed
+    /// message if it fails, which is handy for smoke tests of module wiring.
+    ///
+    /// Unlike unwrapping the result of [`TypedInjector::get`], the message
+    /// lists the whole chain of underlying errors, along with how the
+    /// container would resolve `key` according to
+    /// [`Container::resolve_path`], and the reported dependencies of `key`
+    /// without any matching binding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object of `key` fails to be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// # use iocc::prelude::*;
+    /// # use iocc::scope::SingletonScope;
+    /// let container = Container::<SingletonScope>::init(Configuration::new()).unwrap();
+    /// container.assert_resolvable(key::of::<i32>());
+    /// ```
+    ///
+    /// [`TypedInjector::get`]: crate::container::injector::TypedInjector::get
+    #[track_caller]
+    pub fn assert_resolvable<K: TypedKey>(&self, key: K) {
+        let Err(err) = self.dyn_get(&key) else {
+            return;
+        };
+
+        let mut message = format!("the object {key} can't be resolved: {err}");
+        let mut source = err.source();
+        while let Some(err) = source {
+            let _ = write!(message, "\n  caused by: {err}");
+            source = err.source();
+        }
+        match self.resolve_path(&key) {
+            Ok(plan) => {
+                let _ = write!(message, "\n  resolution plan: {plan:?}");
+                for dependency in plan.dependencies.iter().filter(|dependency| {
+                    !self
+                        .keys(dependency.target)
+                        .iter()
+                        .any(|key| dependency.matches(key.as_ref()))
+                }) {
+                    let _ = write!(message, "\n  unbound dependency: {dependency:?}");
+                }
+            }
+            Err(_) => message.push_str("\n  resolution plan: the key isn't bound"),
+        }
+        panic!("{message}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    use crate::container::injector::TypedInjector;
+    use crate::container::registry::Registry;
+    use crate::key::{self, KeyTypePattern};
+    use crate::module::bind;
+    use crate::provider::component::Component;
+    use crate::provider::DependencyDescriptor;
+    use crate::scope::SingletonScope;
+
+    use super::*;
+
+    #[test]
+    fn stub_container_get_succeeds() {
+        let stubs = StubContainer::new()
+            .stub(key::of(), 42i32)
+            .stub(key::named("name"), 1i32)
+            .stub_default(key::of::<String>());
+
+        assert_eq!(stubs.get(key::of::<i32>()).unwrap(), 42);
+        assert_eq!(stubs.get(key::named::<i32>("name")).unwrap(), 1);
+        assert_eq!(stubs.get(key::of::<String>()).unwrap(), "");
+
+        let objects: Vec<i32> = stubs.collect(KeyTypePattern::<_, &str>::new()).unwrap();
+        assert_eq!(objects, vec![1]);
+    }
+
+    #[test]
+    fn stub_container_get_succeeds_with_fallback() {
+        let stubs = StubContainer::new().with_fallback(|key| {
+            Err(InjectorError::NotFound {
+                key: key.dyn_clone(),
+            })
+        });
+
+        assert!(matches!(
+            stubs.get(key::of::<i32>()),
+            Err(InjectorError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn container_assert_resolvable_succeeds() {
+        let module = crate::module::from_fn(|configurer| {
+            bind::<i32>().to_instance(42).set_on(configurer);
+            Ok(())
+        });
+        let container = Container::<SingletonScope>::init(module).unwrap();
+        container.assert_resolvable(key::of::<i32>());
+    }
+
+    #[test]
+    #[should_panic(expected = "caused by: unavailable")]
+    fn container_assert_resolvable_fails_when_construction_fails() {
+        let module = crate::module::from_fn(|configurer| {
+            bind::<i32>()
+                .to_raw_closure(|_| Ok(Err::<i32, _>("unavailable")))
+                .set_on(configurer);
+            Ok(())
+        });
+        let container = Container::<SingletonScope>::init(module).unwrap();
+        container.assert_resolvable(key::of::<i32>());
+    }
+
+    #[test]
+    #[should_panic(expected = "unbound dependency")]
+    fn container_assert_resolvable_fails_when_dependency_is_unbound() {
+        struct Service;
+
+        impl Component for Service {
+            type Constructed = Arc<Self>;
+
+            type Error = Infallible;
+
+            fn construct<I>(injector: &I) -> Result<Result<Self, Self::Error>, InjectorError>
+            where
+                I: TypedInjector + ?Sized,
+            {
+                injector.get(key::named::<u16>("port"))?;
+                Ok(Ok(Self))
+            }
+
+            fn post_process(self) -> Self::Constructed {
+                Arc::new(self)
+            }
+
+            fn dependencies() -> Vec<DependencyDescriptor> {
+                vec![DependencyDescriptor::named::<u16>("port")]
+            }
+        }
+
+        let module = crate::module::from_fn(|configurer| {
+            bind::<u16>()
+                .qualified_by("host")
+                .to_instance(80)
+                .set_on(configurer);
+            bind::<Arc<Service>>()
+                .within(SingletonScope)
+                .to_component::<Service>()
+                .set_on(configurer);
+            Ok(())
+        });
+        let container = Container::<SingletonScope>::init(module).unwrap();
+        container.assert_resolvable(key::of::<Arc<Service>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "no stub for key i32")]
+    fn stub_container_get_fails_when_stub_is_missing() {
+        let _ = StubContainer::new().get(key::of::<i32>());
+    }
+
+    #[test]
+    fn tracing_injector_get_succeeds() {
+        let stubs = StubContainer::new()
+            .stub(key::of(), 42i32)
+            .stub(key::named("name"), 1i32);
+        let tracing = TracingInjector::new(&stubs);
+
+        assert_eq!(tracing.get(key::named::<i32>("name")).unwrap(), 1);
+        assert_eq!(tracing.get(key::of::<i32>()).unwrap(), 42);
+        let objects: Vec<i32> = tracing.collect(KeyTypePattern::<_, ()>::new()).unwrap();
+        assert_eq!(objects, vec![42]);
+
+        let keys = tracing.recorded();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].as_ref(), &key::named::<i32>("name") as &dyn Key);
+        assert_eq!(keys[1].as_ref(), &key::of::<i32>() as &dyn Key);
+        assert_eq!(keys[2].as_ref(), &key::of::<i32>() as &dyn Key);
+    }
+}
@@ -105,6 +105,8 @@ impl<T: Any> AsAny for T {
     }
 }
 
+/// Downcasting a reference to a type-erased object, such as `&dyn Managed`,
+/// to a concrete type.
 pub trait DowncastRef {
     fn is<T: Any>(&self) -> bool;
 
@@ -126,6 +128,7 @@ where
     }
 }
 
+/// Downcasting a mutable reference to a type-erased object to a concrete type.
 pub trait DowncastMut: DowncastRef {
     fn downcast_mut<T: Any>(&mut self) -> Option<&mut T>;
 }
@@ -140,6 +143,29 @@ where
     }
 }
 
+/// Downcasting an owned type-erased object, such as `Box<dyn Managed>`
+/// returned by [`Injector::dyn_get`], to a concrete type.
+///
+/// # Examples
+///
+/// ```rust
+/// # use iocc::prelude::*;
+/// # use iocc::container::injector::Injector;
+/// # use iocc::container::Downcast;
+/// # use iocc::key::Key;
+/// # use iocc::module;
+/// # use iocc::scope::SingletonScope;
+/// # let module = module::from_fn(|configurer| {
+/// #     bind::<i32>().to_instance(42).set_on(configurer);
+/// #     Ok(())
+/// # });
+/// let container = Container::<SingletonScope>::init(module).unwrap();
+/// let key: Box<dyn Key> = Box::new(key::of::<i32>());
+/// let object = container.dyn_get(key.as_ref()).unwrap();
+/// assert_eq!(*object.downcast::<i32>().ok().unwrap(), 42);
+/// ```
+///
+/// [`Injector::dyn_get`]: crate::container::injector::Injector::dyn_get
 pub trait Downcast: DowncastMut + Sized {
     type Output<T>;
 
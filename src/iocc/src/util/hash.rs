@@ -1,5 +1,197 @@
 use std::any::Any;
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap as StdHashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// The [`BuildHasher`] used by maps internal to containers, whose keys are
+/// never controlled by attackers, so that a faster hasher can be enabled by
+/// the `fast-hash` feature.
+///
+/// [`BuildHasher`]: std::hash::BuildHasher
+#[cfg(feature = "fast-hash")]
+pub type BuildHasherImpl = rustc_hash::FxBuildHasher;
+
+/// The [`BuildHasher`] used by maps internal to containers, whose keys are
+/// never controlled by attackers, so that a faster hasher can be enabled by
+/// the `fast-hash` feature.
+///
+/// [`BuildHasher`]: std::hash::BuildHasher
+#[cfg(not(feature = "fast-hash"))]
+pub type BuildHasherImpl = std::hash::RandomState;
+
+/// A [`HashMap`] using [`BuildHasherImpl`].
+///
+/// [`HashMap`]: std::collections::HashMap
+pub type HashMap<K, V> = StdHashMap<K, V, BuildHasherImpl>;
+
+/// A [`HashMap`] keyed by [`Key`]s using a [`KeyBuildHasher`].
+///
+/// [`HashMap`]: std::collections::HashMap
+/// [`Key`]: crate::key::Key
+pub type KeyMap<K, V> = StdHashMap<K, V, KeyBuildHasher>;
+
+/// The [`BuildHasher`] used by maps keyed by [`Key`]s, which is
+/// [`BuildHasherImpl`] unless a custom one is supplied.
+///
+/// Keys may carry qualifiers of user-supplied types, so users may need to
+/// pick the hasher themselves, e.g. a DoS-resistant one for qualifiers
+/// derived from untrusted input. Custom hashers are dispatched dynamically,
+/// so that containers don't have to be generic over them, at the cost of
+/// boxing a [`Hasher`] for each hash computed.
+///
+/// [`Key`]: crate::key::Key
+#[derive(Clone)]
+pub struct KeyBuildHasher(KeyBuildHasherKind);
+
+#[derive(Clone)]
+enum KeyBuildHasherKind {
+    Default(BuildHasherImpl),
+    Custom(Arc<dyn DynBuildHasher>),
+}
+
+impl KeyBuildHasher {
+    pub fn custom<H>(hasher: H) -> Self
+    where
+        H: BuildHasher + Send + Sync + 'static,
+        H::Hasher: 'static,
+    {
+        Self(KeyBuildHasherKind::Custom(Arc::new(hasher)))
+    }
+}
+
+impl Default for KeyBuildHasher {
+    fn default() -> Self {
+        Self(KeyBuildHasherKind::Default(BuildHasherImpl::default()))
+    }
+}
+
+impl Debug for KeyBuildHasher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let custom = matches!(self.0, KeyBuildHasherKind::Custom(_));
+        f.debug_struct("KeyBuildHasher")
+            .field("custom", &custom)
+            .finish()
+    }
+}
+
+impl BuildHasher for KeyBuildHasher {
+    type Hasher = KeyHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match &self.0 {
+            KeyBuildHasherKind::Default(hasher) => KeyHasher::Default(hasher.build_hasher()),
+            KeyBuildHasherKind::Custom(hasher) => KeyHasher::Custom(hasher.dyn_build_hasher()),
+        }
+    }
+}
+
+/// The [`Hasher`] built by [`KeyBuildHasher`].
+pub enum KeyHasher {
+    Default(<BuildHasherImpl as BuildHasher>::Hasher),
+    Custom(Box<dyn Hasher>),
+}
+
+/// Forwards integer writes to the inner hasher, so that hashers specializing
+/// them see the same calls as without [`KeyHasher`]. `write_str` and
+/// `write_length_prefix` are still unstable, so they fall back to the
+/// default implementations calling [`Hasher::write`].
+macro_rules! forward_write_int {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(&mut self, i: $ty) {
+                match self {
+                    Self::Default(hasher) => hasher.$method(i),
+                    Self::Custom(hasher) => hasher.$method(i),
+                }
+            }
+        )*
+    };
+}
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        match self {
+            Self::Default(hasher) => hasher.finish(),
+            Self::Custom(hasher) => hasher.finish(),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Default(hasher) => hasher.write(bytes),
+            Self::Custom(hasher) => hasher.write(bytes),
+        }
+    }
+
+    forward_write_int! {
+        write_u8(u8),
+        write_u16(u16),
+        write_u32(u32),
+        write_u64(u64),
+        write_u128(u128),
+        write_usize(usize),
+        write_i8(i8),
+        write_i16(i16),
+        write_i32(i32),
+        write_i64(i64),
+        write_i128(i128),
+        write_isize(isize),
+    }
+}
+
+trait DynBuildHasher: Send + Sync {
+    fn dyn_build_hasher(&self) -> Box<dyn Hasher>;
+}
+
+impl<H> DynBuildHasher for H
+where
+    H: BuildHasher + Send + Sync,
+    H::Hasher: 'static,
+{
+    fn dyn_build_hasher(&self) -> Box<dyn Hasher> {
+        Box::new(self.build_hasher())
+    }
+}
+
+/// A [`Hasher`] implementing the 64-bit FNV-1a algorithm with its standard
+/// offset basis.
+///
+/// Unlike the hashers from the standard library, its output only depends on
+/// the bytes written and never on the process, the platform or the Rust
+/// release, so hashes can be persisted. Integers should be written in a fixed
+/// byte order with [`Hasher::write`] to keep hashes portable.
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    /// Writes `s` prefixed by its length, so that consecutive strings can't
+    /// be confused with each other.
+    pub fn write_len_prefixed(&mut self, s: &str) {
+        self.write(&(s.len() as u64).to_le_bytes());
+        self.write(s.as_bytes());
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+}
 
 pub trait DynHash: Any {
     fn dyn_eq(&self, other: &dyn Any) -> bool;
@@ -22,7 +214,7 @@ impl<T: Eq + Hash + 'static> DynHash for T {
 
 #[cfg(test)]
 mod tests {
-    use std::hash::DefaultHasher;
+    use std::hash::{BuildHasherDefault, DefaultHasher, RandomState};
 
     use super::*;
 
@@ -58,6 +250,71 @@ mod tests {
         assert_ne!(hash_val(&b1), hash_val(&b2));
     }
 
+    #[test]
+    fn stable_hasher_succeeds() {
+        let hasher = StableHasher::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+
+        let mut hasher = StableHasher::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let mut first = StableHasher::new();
+        first.write_len_prefixed("ab");
+        first.write_len_prefixed("c");
+        let mut second = StableHasher::new();
+        second.write_len_prefixed("a");
+        second.write_len_prefixed("bc");
+        assert_ne!(first.finish(), second.finish());
+    }
+
+    #[test]
+    fn key_build_hasher_custom_succeeds() {
+        let state = RandomState::new();
+        let hasher = KeyBuildHasher::custom(state.clone());
+        assert_eq!(hasher.hash_one("a"), state.hash_one("a"));
+        assert_eq!(hasher.hash_one(42u64), state.hash_one(42u64));
+        assert_ne!(hasher.hash_one(1u64), hasher.hash_one(2u64));
+    }
+
+    #[test]
+    fn key_hasher_forwards_integer_writes_succeeds() {
+        #[derive(Default)]
+        struct IntHasher(u64);
+
+        impl Hasher for IntHasher {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, _bytes: &[u8]) {
+                panic!("integer writes should be forwarded");
+            }
+
+            fn write_u16(&mut self, i: u16) {
+                self.0 = i.into();
+            }
+
+            fn write_u128(&mut self, i: u128) {
+                self.0 = i as u64;
+            }
+
+            fn write_i32(&mut self, i: i32) {
+                self.0 = i as u64;
+            }
+
+            fn write_i64(&mut self, i: i64) {
+                self.0 = i as u64;
+            }
+        }
+
+        let hasher = KeyBuildHasher::custom(BuildHasherDefault::<IntHasher>::default());
+        assert_eq!(hasher.hash_one(1u16), 1);
+        assert_eq!(hasher.hash_one(2u128), 2);
+        assert_eq!(hasher.hash_one(3i32), 3);
+        assert_eq!(hasher.hash_one(4i64), 4);
+    }
+
     fn hash_val(val: &dyn DynHash) -> u64 {
         let mut hasher = DefaultHasher::new();
         val.dyn_hash(&mut hasher);
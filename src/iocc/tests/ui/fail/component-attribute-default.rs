@@ -0,0 +1,14 @@
+use iocc::prelude::*;
+
+#[derive(Default)]
+struct Test;
+
+#[component(default)]
+impl Test {
+    #[inject]
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iocc::prelude::*;
+
+struct Test;
+
+#[component(fallible)]
+impl Test {
+    #[inject]
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iocc::prelude::*;
+
+struct Test;
+
+#[component(Box<Test>, Box::new, impl_only)]
+impl Test {
+    #[inject]
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use iocc::prelude::*;
+
+struct Test;
+
+#[component]
+impl Test {
+    #[inject]
+    #[depends_on]
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iocc::prelude::*;
+
+struct Test;
+
+#[component]
+impl Test {
+    #[inject]
+    fn new(#[extra] _first: i32, #[extra] _second: i64) -> Self {
+        Self
+    }
+}
+
+fn main() {}
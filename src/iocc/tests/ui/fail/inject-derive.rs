@@ -0,0 +1,14 @@
+use iocc::prelude::*;
+
+#[derive(Inject)]
+enum NotStruct {
+    A,
+}
+
+#[derive(Inject)]
+struct ExtraField {
+    #[extra]
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use std::cell::Cell;
+
+use iocc::managed_newtype;
+
+managed_newtype! {
+    pub struct Counter(Cell<i32>);
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iocc::prelude::*;
+
+struct Test;
+
+#[component]
+impl Test {
+    #[inject]
+    fn new(#[named()] _name: &'static str) -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iocc::key::Qualifier;
+
+#[derive(Qualifier, Debug, Clone, PartialEq, Eq, Hash)]
+struct NotCopy(u32);
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[qualifier(name)]
+struct UnknownArgument;
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Generic<T>(T);
+
+fn main() {}
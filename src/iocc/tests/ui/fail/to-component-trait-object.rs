@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+pub trait Handler: Send + Sync + 'static {}
+
+pub struct MyHandler;
+
+#[component(Arc<MyHandler>, Arc::new)]
+impl MyHandler {
+    #[inject]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {
+    let _ = module::from_fn(|configurer: &mut dyn Configurer<Scope = SingletonScope>| {
+        bind::<Arc<dyn Handler>>()
+            .to_component::<MyHandler>()
+            .set_on(configurer);
+        Ok(())
+    });
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use iocc::key::Qualifier;
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegionId {
+    Europe,
+    America,
+}
+
+pub trait Provider: Send + Sync + 'static {
+    fn endpoint(&self) -> &'static str;
+}
+
+pub struct StaticProvider(&'static str);
+
+impl Provider for StaticProvider {
+    fn endpoint(&self) -> &'static str {
+        self.0
+    }
+}
+
+pub struct Router {
+    providers: Arc<HashMap<RegionId, Arc<dyn Provider>>>,
+}
+
+#[component]
+impl Router {
+    #[inject]
+    pub fn new(#[collect(key)] providers: Arc<HashMap<RegionId, Arc<dyn Provider>>>) -> Self {
+        Self { providers }
+    }
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<dyn Provider>>()
+            .qualified_by(RegionId::Europe)
+            .to_instance(Arc::new(StaticProvider("eu.example.com")))
+            .set_on(configurer);
+        bind::<Arc<dyn Provider>>()
+            .qualified_by(RegionId::America)
+            .to_instance(Arc::new(StaticProvider("us.example.com")))
+            .set_on(configurer);
+        bind::<Router>().set_on(configurer);
+        Ok(())
+    });
+
+    let container = Container::<SingletonScope>::init(module).unwrap();
+    let router = container.get(key::of::<Router>()).unwrap();
+    assert_eq!(router.providers.len(), 2);
+    assert_eq!(
+        router.providers[&RegionId::Europe].endpoint(),
+        "eu.example.com"
+    );
+}
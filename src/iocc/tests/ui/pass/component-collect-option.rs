@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+pub trait Metrics: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+}
+
+pub struct StatsdMetrics;
+
+impl Metrics for StatsdMetrics {
+    fn name(&self) -> &'static str {
+        "statsd"
+    }
+}
+
+pub struct Server {
+    metrics: Option<Arc<dyn Metrics>>,
+    tracer: Option<Arc<String>>,
+}
+
+#[component]
+impl Server {
+    #[inject]
+    pub fn new(
+        #[collect(any)] metrics: Option<Arc<dyn Metrics>>,
+        #[collect(any)] tracer: Option<Arc<String>>,
+    ) -> Self {
+        Self { metrics, tracer }
+    }
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<dyn Metrics>>()
+            .to_instance(Arc::new(StatsdMetrics))
+            .set_on(configurer);
+        bind::<Server>().set_on(configurer);
+        Ok(())
+    });
+
+    let container = Container::<SingletonScope>::init(module).unwrap();
+    let server = container.get(key::of::<Server>()).unwrap();
+    assert_eq!(server.metrics.unwrap().name(), "statsd");
+    assert!(server.tracer.is_none());
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::provider::component::Component;
+use iocc::scope::SingletonScope;
+
+pub struct Shard<const N: usize> {
+    address: String,
+}
+
+#[component(Arc<Shard<N>>, Arc::new)]
+impl<const N: usize> Shard<N> {
+    #[inject]
+    pub fn new(#[qualified(N)] address: String) -> Self {
+        Self { address }
+    }
+}
+
+pub struct Cache<T> {
+    value: T,
+}
+
+#[component]
+impl<T> Cache<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    #[inject]
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+fn assert_leaf<C: Component>() -> bool {
+    C::is_leaf()
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<String>()
+            .to_instance(String::from("shard0.local"))
+            .qualified_by(0usize)
+            .set_on(configurer);
+        bind::<String>()
+            .to_instance(String::from("shard1.local"))
+            .qualified_by(1usize)
+            .set_on(configurer);
+        bind::<Arc<Shard<0>>>()
+            .within(SingletonScope)
+            .to_component::<Shard<0>>()
+            .set_on(configurer);
+        bind::<Arc<Shard<1>>>()
+            .within(SingletonScope)
+            .to_component::<Shard<1>>()
+            .set_on(configurer);
+        bind::<i32>().to_instance(42).set_on(configurer);
+        bind::<Cache<i32>>().to_component::<Cache<i32>>().set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(Configuration::new().with(module)).unwrap();
+
+    let shard0 = container.get(key::of::<Arc<Shard<0>>>()).unwrap();
+    let shard1 = container.get(key::of::<Arc<Shard<1>>>()).unwrap();
+    assert_eq!(shard0.address, "shard0.local");
+    assert_eq!(shard1.address, "shard1.local");
+    assert_eq!(container.get(key::of::<Cache<i32>>()).unwrap().value, 42);
+    assert!(!assert_leaf::<Shard<0>>());
+}
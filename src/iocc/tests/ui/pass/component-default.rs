@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::provider::component::Component;
+use iocc::scope::SingletonScope;
+
+#[derive(Default)]
+pub struct Settings {
+    retries: u32,
+}
+
+#[component(default)]
+impl Settings {}
+
+#[derive(Default)]
+pub struct Counter;
+
+#[component(Arc<Counter>, Arc::new, default)]
+impl Counter {}
+
+fn main() {
+    assert!(Settings::is_leaf());
+
+    let module = module::from_fn(|configurer| {
+        bind::<Settings>().to_component::<Settings>().set_on(configurer);
+        bind::<Arc<Counter>>()
+            .within(SingletonScope)
+            .to_component::<Counter>()
+            .set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(module).unwrap();
+    assert_eq!(container.get(key::of::<Settings>()).unwrap().retries, 0);
+    let _ = container.get(key::of::<Arc<Counter>>()).unwrap();
+}
@@ -0,0 +1,72 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use iocc::key::Qualifier;
+use iocc::prelude::*;
+use iocc::provider::component::{Component, ComponentProvider, ComponentWith};
+use iocc::provider::{DependencyDescriptor, Provider, QualifierKind};
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Replica;
+
+pub struct Registrar;
+
+#[component(Arc<Registrar>, Arc::new)]
+impl Registrar {
+    #[inject]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+pub struct Service;
+
+#[component]
+impl Service {
+    #[inject]
+    #[depends_on(Arc<Registrar>)]
+    pub fn new(
+        _port: u16,
+        #[qualified(Replica)] _replica: Arc<String>,
+        #[collect(key)] _names: HashMap<&'static str, i32>,
+    ) -> Self {
+        Self
+    }
+}
+
+pub struct Session;
+
+#[component]
+impl Session {
+    #[inject]
+    pub fn new(#[extra] _token: u64, #[named("user")] _user: Arc<String>) -> Self {
+        Self
+    }
+}
+
+fn main() {
+    assert!(Registrar::dependencies().is_empty());
+
+    assert_eq!(
+        Service::dependencies(),
+        [
+            DependencyDescriptor::of::<Arc<Registrar>>(),
+            DependencyDescriptor::of::<u16>(),
+            DependencyDescriptor::qualified::<Arc<String>, _>(Replica),
+            DependencyDescriptor {
+                target: TypeId::of::<i32>(),
+                qualifier: QualifierKind::Typed(TypeId::of::<&'static str>()),
+                collect: true,
+            },
+        ]
+    );
+
+    let provider: Box<dyn Provider> = Box::new(ComponentProvider::<Service>::new());
+    assert_eq!(provider.dyn_dependencies(), Service::dependencies());
+
+    assert_eq!(
+        <Session as ComponentWith<u64>>::dependencies(),
+        [DependencyDescriptor::named::<Arc<String>>("user")]
+    );
+}
@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iocc::prelude::*;
+use iocc::module;
+use iocc::provider::component::Component;
+use iocc::scope::SingletonScope;
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+pub struct Registrar;
+
+#[component(Arc<Registrar>, Arc::new)]
+impl Registrar {
+    #[inject]
+    pub fn new() -> Self {
+        REGISTERED.store(true, Ordering::SeqCst);
+        Self
+    }
+}
+
+pub struct Reader {
+    registered: bool,
+}
+
+#[component]
+impl Reader {
+    #[inject]
+    #[depends_on(Arc<Registrar>)]
+    pub fn new() -> Self {
+        Self {
+            registered: REGISTERED.load(Ordering::SeqCst),
+        }
+    }
+}
+
+fn main() {
+    assert!(!Reader::is_leaf());
+
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<Registrar>>()
+            .to_component::<Registrar>()
+            .within(SingletonScope)
+            .set_on(configurer);
+        bind::<Reader>().as_transient().set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(module).unwrap();
+
+    let reader = container.get(key::of::<Reader>()).unwrap();
+    assert!(reader.registered);
+}
@@ -0,0 +1,72 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use iocc::prelude::*;
+use iocc::provider::component::Component;
+
+#[derive(Debug)]
+pub struct SpecificError;
+
+impl Display for SpecificError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "specific error")
+    }
+}
+
+impl Error for SpecificError {}
+
+#[derive(Debug)]
+pub struct BroadError(SpecificError);
+
+impl Display for BroadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "broad error: {}", self.0)
+    }
+}
+
+impl Error for BroadError {}
+
+impl From<SpecificError> for BroadError {
+    fn from(err: SpecificError) -> Self {
+        Self(err)
+    }
+}
+
+pub struct Test1;
+
+#[component(error = "BroadError")]
+impl Test1 {
+    #[inject]
+    pub fn new() -> Result<Self, SpecificError> {
+        Err(SpecificError)
+    }
+}
+
+pub struct Test2;
+
+#[component(Arc<Test2>, Arc::new, error = "BroadError")]
+impl Test2 {
+    #[inject]
+    pub fn new() -> Result<Self, SpecificError> {
+        Ok(Self)
+    }
+}
+
+pub struct Test3;
+
+#[component(error = "BroadError")]
+impl Test3 {
+    #[inject]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn assert_error_type<C: Component<Error = BroadError>>() {}
+
+fn main() {
+    assert_error_type::<Test1>();
+    assert_error_type::<Test2>();
+    assert_error_type::<Test3>();
+}
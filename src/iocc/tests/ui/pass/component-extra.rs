@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use iocc::prelude::*;
+use iocc::module;
+use iocc::provider::component::ComponentWith;
+use iocc::scope::SingletonScope;
+
+pub struct Token(pub u64);
+
+pub struct Handler {
+    token: Token,
+    prefix: Arc<String>,
+}
+
+#[component]
+impl Handler {
+    #[inject]
+    pub fn new(prefix: Arc<String>, #[extra] token: Token) -> Self {
+        Self { token, prefix }
+    }
+}
+
+pub struct Leaf;
+
+#[component]
+impl Leaf {
+    #[inject]
+    pub fn new(#[extra] _token: Token) -> Self {
+        Self
+    }
+}
+
+fn main() {
+    assert!(<Leaf as ComponentWith<Token>>::is_leaf());
+    assert!(!<Handler as ComponentWith<Token>>::is_leaf());
+
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<String>>()
+            .to_instance(Arc::new(String::from("handler")))
+            .within(SingletonScope)
+            .set_on(configurer);
+        let counter = AtomicU64::new(0);
+        bind::<Handler>()
+            .to_component_with::<Handler, _, _>(move || {
+                Token(counter.fetch_add(1, Ordering::SeqCst))
+            })
+            .set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(module).unwrap();
+
+    let first = container.get(key::of::<Handler>()).unwrap();
+    let second = container.get(key::of::<Handler>()).unwrap();
+    assert_eq!(first.prefix.as_str(), "handler");
+    assert_eq!(first.token.0, 0);
+    assert_eq!(second.token.0, 1);
+}
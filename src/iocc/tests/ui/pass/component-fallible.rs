@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use iocc::container::injector::InjectorErrorKind;
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+#[derive(Debug)]
+pub struct InvalidPort(u16);
+
+impl Display for InvalidPort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "invalid port {}", self.0)
+    }
+}
+
+impl Error for InvalidPort {}
+
+pub struct Server {
+    port: u16,
+}
+
+#[component(Arc<Server>, validate, fallible, error = "InvalidPort")]
+impl Server {
+    #[inject]
+    pub fn new(port: u16) -> Self {
+        Self { port }
+    }
+}
+
+fn validate(server: Server) -> Result<Arc<Server>, InvalidPort> {
+    if server.port == 0 {
+        Err(InvalidPort(server.port))
+    } else {
+        Ok(Arc::new(server))
+    }
+}
+
+fn init(port: u16) -> Container<SingletonScope> {
+    let module = module::from_fn(move |configurer| {
+        bind::<u16>().to_instance(port).set_on(configurer);
+        bind::<Arc<Server>>()
+            .within(SingletonScope)
+            .to_component::<Server>()
+            .set_on(configurer);
+        Ok(())
+    });
+    Container::init(Configuration::new().with(module)).unwrap()
+}
+
+fn main() {
+    let server = init(8080).get(key::of::<Arc<Server>>()).unwrap();
+    assert_eq!(server.port, 8080);
+
+    let Err(err) = init(0).get(key::of::<Arc<Server>>()) else {
+        panic!("the port should be rejected");
+    };
+    assert_eq!(err.kind(), InjectorErrorKind::ObjectConstruction);
+    assert_eq!(err.construction_source::<InvalidPort>().unwrap().0, 0);
+}
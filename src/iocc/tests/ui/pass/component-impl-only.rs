@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use iocc::prelude::*;
+use iocc::module;
+use iocc::scope::SingletonScope;
+
+pub struct Config {
+    pub port: u16,
+}
+
+pub struct Server {
+    port: u16,
+}
+
+#[component(impl_only)]
+impl Server {
+    #[inject]
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { port: config.port }
+    }
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<Config>>()
+            .to_instance(Arc::new(Config { port: 8080 }))
+            .set_on(configurer);
+        bind::<Arc<Server>>()
+            .to_raw_closure(|injector| Ok(Server::__inject(injector)?.map(Arc::new)))
+            .set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(module).unwrap();
+    let server = container.get(key::of::<Arc<Server>>()).unwrap();
+    assert_eq!(server.port, 8080);
+}
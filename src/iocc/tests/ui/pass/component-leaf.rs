@@ -0,0 +1,27 @@
+use iocc::prelude::*;
+use iocc::provider::component::Component;
+
+pub struct Leaf;
+
+#[component]
+impl Leaf {
+    #[inject]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+pub struct NonLeaf;
+
+#[component]
+impl NonLeaf {
+    #[inject]
+    pub fn new(_leaf: Leaf) -> Self {
+        Self
+    }
+}
+
+fn main() {
+    assert!(Leaf::is_leaf());
+    assert!(!NonLeaf::is_leaf());
+}
@@ -0,0 +1,39 @@
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+pub struct Repository {
+    primary: &'static str,
+    service: &'static str,
+}
+
+#[component]
+impl Repository {
+    #[inject]
+    pub fn new(
+        #[named(concat!("db.", "primary"))] primary: &'static str,
+        #[named(env!("CARGO_PKG_NAME"))] service: &'static str,
+    ) -> Self {
+        Self { primary, service }
+    }
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<&'static str>()
+            .qualified_by("db.primary")
+            .to_instance("postgres")
+            .set_on(configurer);
+        bind::<&'static str>()
+            .qualified_by(env!("CARGO_PKG_NAME"))
+            .to_instance("service")
+            .set_on(configurer);
+        bind::<Repository>().set_on(configurer);
+        Ok(())
+    });
+
+    let container = Container::<SingletonScope>::init(module).unwrap();
+    let repository = container.get(key::of::<Repository>()).unwrap();
+    assert_eq!(repository.primary, "postgres");
+    assert_eq!(repository.service, "service");
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use iocc::container::Managed;
+use iocc::key::Qualifier;
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+pub trait Repository: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+}
+
+pub struct MemoryRepository;
+
+impl Repository for MemoryRepository {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Replica;
+
+#[derive(Inject)]
+pub struct Deps {
+    repo: Arc<dyn Repository>,
+    #[named("primary")]
+    db: Arc<String>,
+    #[qualified(Replica)]
+    replica: Arc<String>,
+    #[collect(any)]
+    ports: Vec<u16>,
+}
+
+#[derive(Inject)]
+pub struct Pair(u16, #[named("primary")] Arc<String>);
+
+#[derive(Inject)]
+pub struct Named<T: Managed> {
+    #[collect(key)]
+    objects: HashMap<&'static str, T>,
+}
+
+#[derive(Inject)]
+pub struct Nothing;
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<Arc<dyn Repository>>()
+            .to_instance(Arc::new(MemoryRepository))
+            .set_on(configurer);
+        bind::<Arc<String>>()
+            .qualified_by("primary")
+            .to_instance(Arc::new("db://primary".to_string()))
+            .set_on(configurer);
+        bind::<Arc<String>>()
+            .qualified_by(Replica)
+            .to_instance(Arc::new("db://replica".to_string()))
+            .set_on(configurer);
+        bind::<u16>().to_instance(8080).set_on(configurer);
+        bind::<i32>().qualified_by("a").to_instance(1).set_on(configurer);
+        Ok(())
+    });
+
+    let container = Container::<SingletonScope>::init(module).unwrap();
+
+    let deps: Deps = container.inject().unwrap();
+    assert_eq!(deps.repo.name(), "memory");
+    assert_eq!(*deps.db, "db://primary");
+    assert_eq!(*deps.replica, "db://replica");
+    assert_eq!(deps.ports, [8080]);
+
+    let Pair(port, db) = container.inject().unwrap();
+    assert_eq!(port, 8080);
+    assert_eq!(*db, "db://primary");
+
+    let named: Named<i32> = container.inject().unwrap();
+    assert_eq!(named.objects["a"], 1);
+
+    let Nothing = container.inject().unwrap();
+}
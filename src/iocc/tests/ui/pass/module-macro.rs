@@ -0,0 +1,73 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::WebScope;
+
+pub trait Repository: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+}
+
+pub struct MemoryRepository;
+
+#[component(Arc<dyn Repository>, Arc::new)]
+impl MemoryRepository {
+    #[inject]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Repository for MemoryRepository {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+pub struct Service {
+    repository: Arc<dyn Repository>,
+    retries: u32,
+}
+
+#[component]
+impl Service {
+    #[inject]
+    pub fn new(repository: Arc<dyn Repository>, #[named("retries")] retries: u32) -> Self {
+        Self {
+            repository,
+            retries,
+        }
+    }
+}
+
+module! {
+    /// The module of the whole application.
+    pub struct AppModule;
+    scope: WebScope;
+    bind::<Arc<dyn Repository>>()
+        .to_component::<MemoryRepository>()
+        .within(WebScope::Singleton);
+    bind::<u32>().qualified_by("retries").to_instance(3);
+    bind::<Arc<Service>>()
+        .to_closure(|service: Service| Ok::<_, Infallible>(Arc::new(service)))
+        .within(WebScope::Request);
+    bind::<Service>();
+    bind_key(key::named::<i64>("answer")).to_raw_closure(|_| Ok(Ok::<_, Infallible>(42)));
+}
+
+fn main() {
+    let container = Container::init(AppModule).unwrap();
+    let request = container.sub_container_to(WebScope::Request).unwrap();
+    let service = request.get(key::of::<Arc<Service>>()).unwrap();
+    assert_eq!(service.repository.name(), "memory");
+    assert_eq!(service.retries, 3);
+    assert_eq!(container.get(key::named::<i64>("answer")).unwrap(), 42);
+
+    let module = module! {
+        scope: WebScope;
+        bind::<i32>().to_instance(42);
+    };
+    let container = Container::init(module).unwrap();
+    assert_eq!(container.get(key::of::<i32>()).unwrap(), 42);
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use iocc::module;
+use iocc::prelude::*;
+use iocc::scope::SingletonScope;
+
+fn collect_all<I, C, P>(injector: &I, pattern: P) -> C
+where
+    I: TypedInjector + ?Sized,
+    C: Collect<P>,
+    P: Pattern,
+{
+    injector.collect(pattern).unwrap()
+}
+
+fn main() {
+    let module = module::from_fn(|configurer| {
+        bind::<i32>().to_instance(1).qualified_by("one").set_on(configurer);
+        bind::<i32>().to_instance(2).qualified_by("two").set_on(configurer);
+        Ok(())
+    });
+    let container = Container::<SingletonScope>::init(Configuration::new().with(module)).unwrap();
+
+    let all: Vec<i32> = collect_all(&container, AnyPattern::new());
+    assert_eq!(all.len(), 2);
+
+    let named: HashMap<&str, i32> = collect_all(&container, KeyTypePattern::new());
+    assert_eq!(named["two"], 2);
+}
@@ -0,0 +1,25 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use iocc::key::{self, Qualifier};
+
+#[derive(Qualifier, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Plain {
+    First,
+}
+
+#[derive(Qualifier, Clone, Copy, PartialEq, Eq, Hash)]
+#[qualifier(display)]
+enum Region {
+    Europe,
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "eu")
+    }
+}
+
+fn main() {
+    assert_eq!(key::qualified::<i32>(Plain::First).to_string(), "i32@First");
+    assert_eq!(key::qualified::<i32>(Region::Europe).to_string(), "i32@eu");
+}